@@ -1,12 +1,15 @@
 use clap::{Parser, ValueEnum};
 use director_core::export::render_export;
-use director_core::scripting::register_rhai_api;
+use director_core::scripting::{register_rhai_api, run_script};
+use director_core::systems::profiler::Profiler;
 use director_core::DefaultAssetLoader;
 use rhai::Engine;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{error, info};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[derive(Parser, Debug)]
@@ -27,6 +30,18 @@ struct Cli {
     /// Log format
     #[arg(long, value_enum, default_value_t = LogFormat::Pretty)]
     log_format: LogFormat,
+
+    /// Supersampling factor for anti-aliasing (overrides the script setting)
+    #[arg(long, value_enum)]
+    supersample: Option<Supersample>,
+
+    /// Render frames in tiles of this many pixels to limit memory on very large outputs
+    #[arg(long, value_name = "PIXELS")]
+    tile_size: Option<u32>,
+
+    /// Write per-node and per-frame timings to this JSON file (plus a `.folded` flame graph file)
+    #[arg(long, value_name = "REPORT")]
+    profile: Option<PathBuf>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -56,6 +71,26 @@ enum LogFormat {
     Json,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Supersample {
+    #[value(name = "1")]
+    X1,
+    #[value(name = "2")]
+    X2,
+    #[value(name = "4")]
+    X4,
+}
+
+impl Supersample {
+    fn factor(self) -> u32 {
+        match self {
+            Supersample::X1 => 1,
+            Supersample::X2 => 2,
+            Supersample::X4 => 4,
+        }
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -64,20 +99,24 @@ fn main() {
         .with_default_directive(cli.log_level.to_string().parse().unwrap())
         .from_env_lossy();
 
-    let subscriber_builder = fmt::Subscriber::builder()
-        .with_env_filter(filter)
-        .with_writer(std::io::stderr)
-        .with_target(false); // Clean output for humans (default)
+    // Clean output for humans (default)
+    let fmt_layer = fmt::layer().with_writer(std::io::stderr).with_target(false);
+
+    // The profiler sees every span regardless of the log level
+    let profiler = cli.profile.as_ref().map(|_| Profiler::new());
+    let registry = tracing_subscriber::registry().with(
+        profiler
+            .as_ref()
+            .map(|p| p.layer().with_filter(filter_fn(|meta| meta.is_span()))),
+    );
 
     match cli.log_format {
         LogFormat::Json => {
             // For JSON, we might want target included, but user said strict NDJSON.
-            // subscriber.json() enables JSON formatting.
-            // We need to finish building.
-            subscriber_builder.json().init();
+            registry.with(fmt_layer.json().with_filter(filter)).init();
         }
         LogFormat::Pretty => {
-            subscriber_builder.pretty().init();
+            registry.with(fmt_layer.pretty().with_filter(filter)).init();
         }
     }
 
@@ -105,12 +144,23 @@ fn main() {
     let mut engine = Engine::new();
     register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
 
-    match engine.eval::<director_core::scripting::MovieHandle>(&script) {
+    match run_script(&engine, &script) {
         Ok(movie) => {
             info!("Script evaluated successfully. Starting render...");
             let mut director = movie.director.lock().unwrap();
+            if let Some(supersample) = cli.supersample {
+                director.supersampling = supersample.factor();
+            }
+            if let Some(tile_size) = cli.tile_size {
+                director.tile_size = Some(tile_size);
+            }
             match render_export(&mut director, output_path, None, None) {
-                Ok(_) => info!("Render complete."),
+                Ok(_) => {
+                    info!("Render complete.");
+                    if let (Some(profiler), Some(path)) = (&profiler, &cli.profile) {
+                        write_profile(profiler, path);
+                    }
+                }
                 Err(e) => {
                     error!("Render failed: {}", e);
                     std::process::exit(1);
@@ -123,3 +173,23 @@ fn main() {
         }
     }
 }
+
+fn write_profile(profiler: &Profiler, path: &Path) {
+    let report = profiler.report();
+    info!("Profile:\n{}", report.summary(10));
+
+    let json = match report.to_json() {
+        Ok(json) => json,
+        Err(e) => {
+            error!("Failed to serialize profile: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = fs::write(path, json) {
+        error!("Failed to write profile {:?}: {}", path, e);
+    }
+    let folded_path = path.with_extension("folded");
+    if let Err(e) = fs::write(&folded_path, report.folded()) {
+        error!("Failed to write profile {:?}: {}", folded_path, e);
+    }
+}
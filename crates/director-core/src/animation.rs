@@ -255,6 +255,13 @@ where
         self.sequence.advance_to(time);
         self.current_value = self.sequence.now();
     }
+
+    /// Evaluates the keyframes at `time` without changing `current_value`.
+    pub fn value_at(&self, time: f64) -> T {
+        let mut sequence = self.sequence.clone();
+        sequence.advance_to(time);
+        sequence.now()
+    }
 }
 
 impl Animated<f32> {
@@ -295,6 +302,44 @@ impl Animated<f32> {
     }
 }
 
+impl Animated<f64> {
+    /// Adds a spring animation from the current value to the target.
+    ///
+    /// The spring is solved on the distance to travel, so large values keep their precision.
+    pub fn add_spring(&mut self, target: f64, config: SpringConfig) {
+        let start = self
+            .raw_keyframes
+            .last()
+            .map_or(self.current_value, |last| last.0);
+        self.add_spring_with_start(start, target, config);
+    }
+
+    /// Adds a spring animation starting from an explicit value.
+    pub fn add_spring_with_start(&mut self, start: f64, target: f64, config: SpringConfig) {
+        if let Some(last) = self.raw_keyframes.last() {
+            if last.0 != start {
+                self.add_keyframe(start, 0.0, EasingType::Linear);
+            }
+        } else {
+            *self = Self::new(start);
+        }
+
+        let frames = solve_spring(0.0, (target - start) as f32, config);
+        let last = frames.len().saturating_sub(1);
+        let mut previous_time = 0.0;
+        for (i, (offset, time)) in frames.into_iter().enumerate() {
+            // The settled frame lands exactly on the target
+            let value = if i == last {
+                target
+            } else {
+                start + offset as f64
+            };
+            self.add_keyframe(value, time - previous_time, EasingType::Linear);
+            previous_time = time;
+        }
+    }
+}
+
 /// Simulates a spring physics system and returns a list of (value, time) tuples.
 fn solve_spring(start: f32, end: f32, config: SpringConfig) -> Vec<(f32, f64)> {
     let mut frames = Vec::new();
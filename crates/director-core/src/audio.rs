@@ -41,6 +41,21 @@ pub struct AudioTrack {
     pub loop_audio: bool,
 }
 
+impl AudioTrack {
+    /// Seconds into the samples playing at global `time`, and the length that plays.
+    ///
+    /// Looping tracks wrap round; other tracks are cut short by their duration.
+    pub fn playback_position(&self, time: f64, sample_rate: u32) -> (f64, f64) {
+        let length = (self.samples.len() / 2) as f64 / sample_rate as f64;
+        let elapsed = time - self.start_time;
+        if self.loop_audio && length > 0.0 && elapsed > 0.0 {
+            (elapsed.rem_euclid(length), length)
+        } else {
+            (elapsed, self.duration.map_or(length, |d| d.min(length)))
+        }
+    }
+}
+
 /// Manages mixing of multiple audio tracks.
 #[derive(Clone, Debug)]
 pub struct AudioMixer {
@@ -4,7 +4,7 @@
 //!
 //! ## Responsibilities
 //! - **Timeline Management**: Maintains a `Vec<TimelineItem>` of scenes.
-//! - **Update Loop**: Drives animation, audio sync, and scene transitions.
+//! - **Update Loop**: Drives animation, audio sync, script callbacks and scene transitions.
 //! - **Scene Coordination**: Manages active scenes and their time ranges.
 //!
 //! ## Key Types
@@ -14,16 +14,19 @@
 
 // use rayon::prelude::*; // Rayon disabled due to Taffy !Send
 use crate::audio::{AudioAnalyzer, AudioMixer, AudioTrack};
-use crate::scene::SceneGraph;
+use crate::element::{FrameInfo, FrameStage};
+use crate::scene::{DriverInput, PropertyDriver, SceneGraph, SceneNode};
 use crate::systems::assets::AssetManager;
+use crate::systems::render_cache::update_cache_keys;
 use crate::systems::transitions::Transition;
-use crate::types::NodeId;
+use crate::types::{ColorInterpolation, NodeId, WorkingColorSpace};
 use crate::video_wrapper::RenderMode;
 use crate::AssetLoader;
 use skia_safe::textlayout::{FontCollection, TypefaceFontProvider};
 use skia_safe::PathMeasure;
 use skia_safe::{Data, FontMgr};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use tracing::instrument;
 
@@ -70,14 +73,34 @@ pub struct Director {
     pub samples_per_frame: u32,
     /// Shutter angle in degrees (0.0 to 360.0). Default: 180.0.
     pub shutter_angle: f32,
+    /// Export render scale for anti-aliasing: 1 (off), 2 or 4. Frames are downsampled before encoding.
+    pub supersampling: u32,
+    /// Tile edge in output pixels for tiled export; `None` renders each frame in one surface.
+    pub tile_size: Option<u32>,
+    /// Margin rendered around each tile so blurs and filters bleed across tile edges.
+    pub tile_overlap: u32,
     /// Render Mode (Preview or Export).
     pub render_mode: RenderMode,
+    /// Colour space frames are composited in during export (default: 8-bit sRGB).
+    pub color_space: WorkingColorSpace,
+    /// How colour animations blend between keyframes (default: sRGB channels).
+    pub color_interpolation: ColorInterpolation,
+    /// Seed of the script's random generator, also used for `noise` in callbacks.
+    pub seed: u64,
     /// Audio Mixer state.
     pub audio_mixer: AudioMixer,
     /// Audio Analyzer for FFT-based spectrum analysis.
     pub audio_analyzer: AudioAnalyzer,
     /// Shared Asset Manager.
     pub assets: AssetManager,
+    /// Script callbacks invoked every frame with the global time (`movie.on_frame`).
+    pub frame_callbacks: Vec<rhai::FnPtr>,
+    /// Sandboxed engine used to run `on_frame` callbacks (created on first registration).
+    pub callback_engine: Option<Rc<rhai::Engine>>,
+    /// Functions defined by the script, callable from `on_frame` callbacks (set by `run_script`).
+    pub script_functions: rhai::AST,
+    /// Replay recordings of subtrees that didn't change since the last frame (default: true).
+    pub render_caching: bool,
 }
 
 impl Director {
@@ -137,10 +160,20 @@ impl Director {
             fps,
             samples_per_frame: 1, // Default to no motion blur
             shutter_angle: 180.0,
+            supersampling: 1,
+            tile_size: None,
+            tile_overlap: 64,
             render_mode,
+            color_space: WorkingColorSpace::default(),
+            color_interpolation: ColorInterpolation::default(),
+            seed: 0,
             audio_mixer: AudioMixer::new(48000),
             audio_analyzer: AudioAnalyzer::new(2048, 48000),
             assets,
+            frame_callbacks: Vec::new(),
+            callback_engine: None,
+            script_functions: rhai::AST::empty(),
+            render_caching: true,
         }
     }
 
@@ -200,149 +233,384 @@ impl Director {
     /// and calls `update()` on the underlying Elements.
     #[instrument(level = "debug", skip(self), fields(time = global_time))]
     pub fn update(&mut self, global_time: f64) {
-        // Pass 1: Mark active nodes and set local time
+        let _interpolation = self.color_interpolation.enter();
+
+        // Pass 0: Rebuild symbol instances whose master was edited
+        crate::scripting::sync_symbol_instances(self);
+
+        // Pass 1: Collect the active set and set local time
         let mut active_roots = Vec::new();
         for item in &self.timeline {
             if global_time >= item.start_time && global_time < item.start_time + item.duration {
                 let local_time = global_time - item.start_time;
-                active_roots.push((item.scene_root, local_time));
+                active_roots.push((item.scene_root, local_time, item.duration));
             }
         }
 
-        let mut stack = Vec::new();
-        for (root, t) in active_roots {
-            stack.push((root, t));
-        }
+        let mut active = std::mem::take(&mut self.scene.active_nodes);
+        active.clear();
+        self.scene.active_roots.clear();
+        self.scene
+            .active_roots
+            .extend(active_roots.iter().map(|&(root, _, _)| root));
 
-        while let Some((id, time)) = stack.pop() {
-            if id >= self.scene.nodes.len() {
-                continue;
-            }
-            if self.scene.nodes[id].is_none() {
-                continue;
-            }
+        let mut stack = active_roots;
 
-            let node = self.scene.nodes[id].as_mut().unwrap();
+        while let Some((id, time, duration)) = stack.pop() {
+            let Some(node) = self.scene.get_node_mut(id) else {
+                continue;
+            };
 
             node.local_time = time;
+            node.scene_duration = duration;
             node.last_visit_time = global_time;
+            active.push(id);
 
-            let children = node.children.clone();
-            for child in children {
-                stack.push((child, time));
+            for &child in &node.children {
+                stack.push((child, time, duration));
             }
 
             // Also traverse mask node to ensure it gets updates
             if let Some(mask_id) = node.mask_node {
-                stack.push((mask_id, time));
+                stack.push((mask_id, time, duration));
             }
         }
 
+        // Later passes visit nodes in id order, independent of traversal order
+        active.sort_unstable();
+        active.dedup();
+        self.scene.active_nodes = active;
+
         // Pass 2: Serial Update (Rayon removed)
-        for (node_id, node_opt) in self.scene.nodes.iter_mut().enumerate() {
-            if let Some(node) = node_opt {
-                if (node.last_visit_time - global_time).abs() < 0.0001 {
-                    if node.element.update(node.local_time) {
-                        node.dirty_style = true;
-                    }
+        let SceneGraph {
+            nodes,
+            active_nodes,
+            ..
+        } = &mut self.scene;
+        for &node_id in active_nodes.iter() {
+            let Some(node) = nodes.get_mut(node_id).and_then(|n| n.as_mut()) else {
+                continue;
+            };
+            let _span = tracing::trace_span!("update_node", node = node_id).entered();
+            if node.element.update(node.local_time) {
+                node.dirty_style = true;
+            }
 
-                    // Update Transform Animations
-                    node.transform.scale_x.update(node.local_time);
-                    node.transform.scale_y.update(node.local_time);
-                    node.transform.rotation.update(node.local_time);
-                    node.transform.skew_x.update(node.local_time);
-                    node.transform.skew_y.update(node.local_time);
-                    node.transform.translate_x.update(node.local_time);
-                    node.transform.translate_y.update(node.local_time);
-
-                    // Update Path Animation
-                    if let Some(path_anim) = &mut node.path_animation {
-                        path_anim.progress.update(node.local_time);
-                        let mut measure = PathMeasure::new(&path_anim.path, false, None);
-                        let length = measure.length();
-                        let dist = path_anim.progress.current_value * length;
-                        if let Some((p, _tangent)) = measure.pos_tan(dist) {
-                            node.transform.translate_x.current_value = p.x;
-                            node.transform.translate_y.current_value = p.y;
-                        }
-                    }
+            // Update Transform Animations
+            node.transform.scale_x.update(node.local_time);
+            node.transform.scale_y.update(node.local_time);
+            node.transform.rotation.update(node.local_time);
+            node.transform.skew_x.update(node.local_time);
+            node.transform.skew_y.update(node.local_time);
+            node.transform.translate_x.update(node.local_time);
+            node.transform.translate_y.update(node.local_time);
+
+            if let Some(clip) = &mut node.clip_path {
+                clip.update(node.local_time);
+            }
 
-                    if node.transform.translate_x.current_value > 0.0 {
-                        tracing::debug!(
-                            "Node {} updated: x={}, local_time={}",
-                            node_id,
-                            node.transform.translate_x.current_value,
-                            node.local_time
-                        );
-                    }
+            // Update Path Animation
+            if let Some(path_anim) = &mut node.path_animation {
+                path_anim.progress.update(node.local_time);
+                let mut measure = PathMeasure::new(&path_anim.path, false, None);
+                let length = measure.length();
+                let dist = path_anim.progress.current_value * length;
+                if let Some((p, _tangent)) = measure.pos_tan(dist) {
+                    node.transform.translate_x.current_value = p.x;
+                    node.transform.translate_y.current_value = p.y;
                 }
             }
+
+            if node.transform.translate_x.current_value > 0.0 {
+                tracing::debug!(
+                    "Node {} updated: x={}, local_time={}",
+                    node_id,
+                    node.transform.translate_x.current_value,
+                    node.local_time
+                );
+            }
         }
 
-        // Pass 3: Audio Reactive Bindings
+        // Pass 2b: Property Drivers
+        // Sources have been updated in Pass 2; drivers override their targets' values
+        self.apply_property_drivers(global_time);
+
+        // Pass 3: Audio Reactive Bindings and Frame Hooks
         // Process after animations so audio values take priority
-        for node_opt in self.scene.nodes.iter_mut() {
-            if let Some(node) = node_opt {
-                if (node.last_visit_time - global_time).abs() < 0.0001 {
-                    for binding in &mut node.audio_bindings {
-                        // Get audio samples from the track
-                        let energy = if let Some(Some(track)) =
-                            self.audio_mixer.tracks.get(binding.track_id)
-                        {
-                            self.audio_analyzer.get_energy(
-                                &track.samples,
-                                global_time,
-                                &binding.band,
-                            )
-                        } else {
-                            0.0
-                        };
-
-                        // Map energy (0-1) to output range
-                        let raw_value =
-                            binding.min_value + energy * (binding.max_value - binding.min_value);
-
-                        // Apply temporal smoothing
-                        let smoothed = if binding.smoothing > 0.0 {
-                            binding.prev_value * binding.smoothing
-                                + raw_value * (1.0 - binding.smoothing)
-                        } else {
-                            raw_value
-                        };
-                        binding.prev_value = smoothed;
-
-                        // Apply to property
-                        match binding.property.as_str() {
-                            "scale" => {
-                                node.transform.scale_x.current_value = smoothed;
-                                node.transform.scale_y.current_value = smoothed;
-                            }
-                            "scale_x" => node.transform.scale_x.current_value = smoothed,
-                            "scale_y" => node.transform.scale_y.current_value = smoothed,
-                            "x" => node.transform.translate_x.current_value = smoothed,
-                            "y" => node.transform.translate_y.current_value = smoothed,
-                            "rotation" => node.transform.rotation.current_value = smoothed,
-                            // Note: opacity requires Element trait extension
-                            // "opacity" => node.element.set_opacity(smoothed),
-                            _ => {}
-                        }
+        let movie_duration = self.movie_duration();
+        let SceneGraph {
+            nodes,
+            active_nodes,
+            ..
+        } = &mut self.scene;
+        for &node_id in active_nodes.iter() {
+            let Some(node) = nodes.get_mut(node_id).and_then(|n| n.as_mut()) else {
+                continue;
+            };
+            for binding in &mut node.audio_bindings {
+                // Get audio samples from the track
+                let energy =
+                    if let Some(Some(track)) = self.audio_mixer.tracks.get(binding.track_id) {
+                        self.audio_analyzer
+                            .get_energy(&track.samples, global_time, &binding.band)
+                    } else {
+                        0.0
+                    };
+
+                // Map energy (0-1) to output range
+                let raw_value =
+                    binding.min_value + energy * (binding.max_value - binding.min_value);
+
+                // Apply temporal smoothing
+                let smoothed = if binding.smoothing > 0.0 {
+                    binding.prev_value * binding.smoothing + raw_value * (1.0 - binding.smoothing)
+                } else {
+                    raw_value
+                };
+                binding.prev_value = smoothed;
+
+                // Apply to property
+                match binding.property.as_str() {
+                    "scale" => {
+                        node.transform.scale_x.current_value = smoothed;
+                        node.transform.scale_y.current_value = smoothed;
+                    }
+                    "scale_x" => node.transform.scale_x.current_value = smoothed,
+                    "scale_y" => node.transform.scale_y.current_value = smoothed,
+                    "x" => node.transform.translate_x.current_value = smoothed,
+                    "y" => node.transform.translate_y.current_value = smoothed,
+                    "rotation" => node.transform.rotation.current_value = smoothed,
+                    _ => {
+                        node.element.set_property(&binding.property, smoothed);
                     }
                 }
             }
+
+            let frame = FrameInfo {
+                stage: FrameStage::Update,
+                global_time,
+                local_time: node.local_time,
+                scene_duration: node.scene_duration,
+                movie_duration,
+                mixer: &self.audio_mixer,
+                analyzer: &self.audio_analyzer,
+                child_outlines: &[],
+            };
+            if node.element.frame(&frame) {
+                node.mark_content_changed();
+            }
+        }
+
+        // Pass 4: Script Callbacks
+        // Run last so procedural values override keyframes and audio bindings
+        if let Some(engine) = self.callback_engine.clone() {
+            crate::scripting::callbacks::run_frame_callbacks(&engine, self, global_time);
+        }
+    }
+
+    /// Evaluates the property drivers of all active nodes.
+    ///
+    /// Drivers whose source is itself driven are evaluated after that source,
+    /// so chains resolve within a single frame. Cycles are broken arbitrarily.
+    /// Delayed drivers replay their sources at the earlier time (see `driver_input`).
+    fn apply_property_drivers(&mut self, global_time: f64) {
+        let driven: Vec<NodeId> = self
+            .scene
+            .active_nodes
+            .iter()
+            .copied()
+            .filter(|&id| {
+                self.scene
+                    .get_node(id)
+                    .is_some_and(|node| !node.drivers.is_empty())
+            })
+            .collect();
+
+        let mut visited = HashSet::new();
+        for id in driven {
+            self.evaluate_drivers(id, global_time, &mut visited);
+        }
+    }
+
+    fn evaluate_drivers(&mut self, id: NodeId, global_time: f64, visited: &mut HashSet<NodeId>) {
+        if !visited.insert(id) {
+            return;
+        }
+
+        let Some(node) = self.scene.get_node_mut(id) else {
+            return;
+        };
+        let mut drivers = std::mem::take(&mut node.drivers);
+
+        let sources: Vec<NodeId> = drivers
+            .iter()
+            .flat_map(|d| d.inputs.iter().map(|input| input.source))
+            .collect();
+        for source in sources {
+            if self
+                .scene
+                .get_node(source)
+                .is_some_and(|n| !n.drivers.is_empty())
+            {
+                self.evaluate_drivers(source, global_time, visited);
+            }
+        }
+
+        for driver in &mut drivers {
+            let Some(raw) = self.driver_raw(driver, driver.delay, 0) else {
+                continue;
+            };
+            let value = driver.map(raw);
+            if let Some(node) = self.scene.get_node_mut(id) {
+                driver.apply(node, value);
+            }
+        }
+
+        if let Some(node) = self.scene.get_node_mut(id) {
+            node.drivers = drivers;
         }
     }
 
+    /// Sum of `driver`'s weighted inputs `lag` seconds before the current frame, or `None`
+    /// if a source is missing.
+    fn driver_raw(&self, driver: &PropertyDriver, lag: f64, depth: usize) -> Option<f32> {
+        driver
+            .inputs
+            .iter()
+            .map(|input| self.driver_input(input, lag, depth))
+            .sum()
+    }
+
+    /// Weighted value of `input` `lag` seconds before the current frame.
+    ///
+    /// Without a lag this is the source's current value. With one, the source is replayed
+    /// at that time: through its own driver if it has one for the property, otherwise
+    /// from its keyframes. Values set at runtime (audio, callbacks) can't be replayed.
+    fn driver_input(&self, input: &DriverInput, lag: f64, depth: usize) -> Option<f32> {
+        let node = self.scene.get_node(input.source)?;
+        let source_driver = node
+            .drivers
+            .iter()
+            .find(|d| d.property == input.property)
+            .filter(|_| depth < 16);
+        let value = if lag <= 0.0 {
+            node.get_property(&input.property)?
+        } else if let Some(driver) = source_driver {
+            driver.map(self.driver_raw(driver, lag + driver.delay, depth + 1)?)
+        } else {
+            node.property_at(&input.property, node.local_time - lag)?
+        };
+        Some(value * input.weight)
+    }
+
     /// Triggers `post_layout` on all active nodes.
     ///
     /// This is called after the Layout Engine has computed the final boxes, allowing elements
     /// to adjust their internal state (e.g., text resizing) based on the final layout.
+    /// Then runs the `FrameStage::Layout` frame hooks and refreshes the render cache keys of
+    /// the active scenes.
     pub fn run_post_layout(&mut self, global_time: f64) {
-        for node_opt in self.scene.nodes.iter_mut() {
-            if let Some(node) = node_opt {
-                if (node.last_visit_time - global_time).abs() < 0.0001 {
-                    node.element.post_layout(node.layout_rect);
-                }
+        let SceneGraph {
+            nodes,
+            active_nodes,
+            ..
+        } = &mut self.scene;
+        for &id in active_nodes.iter() {
+            if let Some(node) = nodes.get_mut(id).and_then(|n| n.as_mut()) {
+                node.element.post_layout(node.layout_rect);
             }
         }
+
+        self.run_layout_frame_hooks(global_time);
+
+        // Layout and element state are final, so render cache keys can be computed.
+        // Frame callbacks may touch any node, so their presence disables caching.
+        let enabled = self.render_caching && self.callback_engine.is_none();
+        let roots = self.scene.active_roots.clone();
+        for root in roots {
+            update_cache_keys(&mut self.scene, root, enabled, 0);
+        }
+    }
+
+    /// End of the last scene on the timeline.
+    fn movie_duration(&self) -> f64 {
+        self.timeline
+            .iter()
+            .map(|item| item.start_time + item.duration)
+            .fold(0.0, f64::max)
+    }
+
+    /// Calls `Element::frame` at `FrameStage::Layout` for every active node.
+    ///
+    /// Children are visited before their parents, so elements built from their
+    /// children's outlines (boolean shapes) can be nested.
+    fn run_layout_frame_hooks(&mut self, global_time: f64) {
+        let movie_duration = self.movie_duration();
+        for id in self.children_first() {
+            let outlines = match self.scene.get_node(id) {
+                Some(node) if node.element.needs_child_outlines() => self.child_outlines(node),
+                Some(_) => Vec::new(),
+                None => continue,
+            };
+            let Some(node) = self.scene.get_node_mut(id) else {
+                continue;
+            };
+            let frame = FrameInfo {
+                stage: FrameStage::Layout,
+                global_time,
+                local_time: node.local_time,
+                scene_duration: node.scene_duration,
+                movie_duration,
+                mixer: &self.audio_mixer,
+                analyzer: &self.audio_analyzer,
+                child_outlines: &outlines,
+            };
+            if node.element.frame(&frame) {
+                node.mark_content_changed();
+            }
+        }
+    }
+
+    /// Active nodes in post-order: every node after its children and mask.
+    fn children_first(&self) -> Vec<NodeId> {
+        let mut order = Vec::with_capacity(self.scene.active_nodes.len());
+        let mut visited = HashSet::new();
+        let mut stack: Vec<(NodeId, bool)> = self
+            .scene
+            .active_roots
+            .iter()
+            .rev()
+            .map(|&root| (root, false))
+            .collect();
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                order.push(id);
+                continue;
+            }
+            let Some(node) = self.scene.get_node(id) else {
+                continue;
+            };
+            if !visited.insert(id) {
+                continue;
+            }
+            stack.push((id, true));
+            stack.extend(node.children.iter().rev().map(|&child| (child, false)));
+            stack.extend(node.mask_node.map(|mask| (mask, false)));
+        }
+        order
+    }
+
+    /// Outlines of `node`'s children in its coordinates, including their transforms.
+    fn child_outlines(&self, node: &SceneNode) -> Vec<skia_safe::Path> {
+        node.children
+            .iter()
+            .filter_map(|&child| {
+                let child = self.scene.get_node(child)?;
+                let bounds =
+                    skia_safe::Rect::from_wh(child.layout_rect.width(), child.layout_rect.height());
+                let outline = child.element.outline_path(bounds)?;
+                Some(outline.with_transform(&child.local_matrix()))
+            })
+            .collect()
     }
 }
@@ -4,14 +4,18 @@
 //!
 //! ## Responsibilities
 //! - **Element Trait**: Defines `update`, `render`, `layout_style`, `measure`.
+//! - **Frame Hook**: `Element::frame` with a `FrameInfo` for elements that depend on
+//!   audio, the timeline or their children.
 //! - **Text Types**: `TextSpan`, `TextShadow`, `TextFit` for rich text.
 //!
 //! ## Key Types
 //! - `Element`: The trait every node type implements.
+//! - `FrameInfo`: What the director knows about the current frame.
 //! - `TextSpan`: A styled span for rich text rendering.
 
+use crate::audio::{AudioAnalyzer, AudioMixer};
 use crate::types::{Color, GradientConfig};
-use skia_safe::{Canvas, Rect};
+use skia_safe::{Canvas, Path, Rect};
 use std::any::Any;
 use taffy::geometry::Size;
 use taffy::style::AvailableSpace;
@@ -54,6 +58,34 @@ pub struct TextShadow {
     pub offset: (f32, f32),
 }
 
+/// When in the frame `Element::frame` is called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameStage {
+    /// After animations, drivers and audio bindings, before script callbacks and layout.
+    Update,
+    /// After layout, children before their parents.
+    Layout,
+}
+
+/// The state of the current frame, passed to `Element::frame`.
+pub struct FrameInfo<'a> {
+    pub stage: FrameStage,
+    /// Movie time in seconds.
+    pub global_time: f64,
+    /// Seconds since the node's scene started.
+    pub local_time: f64,
+    /// Length of the node's scene in seconds.
+    pub scene_duration: f64,
+    /// End of the last scene on the timeline, in seconds.
+    pub movie_duration: f64,
+    pub mixer: &'a AudioMixer,
+    pub analyzer: &'a AudioAnalyzer,
+    /// Outlines of the node's children in its own coordinates, transforms included.
+    ///
+    /// Only filled at `FrameStage::Layout` for elements whose `needs_child_outlines` is true.
+    pub child_outlines: &'a [Path],
+}
+
 /// Helper trait for cloning trait objects (`Box<dyn Element>`).
 pub trait ElementClone {
     fn clone_box(&self) -> Box<dyn Element>;
@@ -125,6 +157,28 @@ pub trait Element: std::fmt::Debug + ElementClone {
         draw_children: &mut dyn FnMut(&Canvas),
     ) -> Result<(), crate::RenderError>;
 
+    /// Returns the element's vector outline inside `layout_rect`, if it has one.
+    ///
+    /// Used as an operand of boolean path operations.
+    fn outline_path(&self, _layout_rect: Rect) -> Option<Path> {
+        None
+    }
+
+    /// Whether `frame` needs the outlines of this element's children.
+    fn needs_child_outlines(&self) -> bool {
+        false
+    }
+
+    /// Syncs the element with the rest of the frame: audio, the timeline and its children.
+    ///
+    /// Called for every active node once per `FrameStage`.
+    ///
+    /// # Returns
+    /// * `true` if the element's appearance changed (invalidating its cached recording).
+    fn frame(&mut self, _frame: &FrameInfo) -> bool {
+        false
+    }
+
     /// Animates a specific named property.
     fn animate_property(
         &mut self,
@@ -135,6 +189,27 @@ pub trait Element: std::fmt::Debug + ElementClone {
         easing: &str,
     );
 
+    /// Returns the current value of a named animatable property, if the element has it.
+    fn get_property(&self, _property: &str) -> Option<f32> {
+        None
+    }
+
+    /// Returns the value a named animatable property has at `time`, from its keyframes.
+    ///
+    /// Used by delayed property drivers. `None` means the element can't replay the
+    /// property, and its current value is used instead.
+    fn property_at(&self, _property: &str, _time: f64) -> Option<f32> {
+        None
+    }
+
+    /// Overrides the current value of a named animatable property.
+    ///
+    /// The override lasts until the next `update()` re-evaluates the animation.
+    /// Returns `false` if the element has no such property.
+    fn set_property(&mut self, _property: &str, _value: f32) -> bool {
+        false
+    }
+
     /// Animates a property using physics-based spring dynamics.
     fn animate_property_spring(
         &mut self,
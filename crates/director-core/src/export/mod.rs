@@ -6,7 +6,10 @@
 //! - **Video Encoding**: FFmpeg integration via video-rs.
 //! - **Motion Blur**: Multi-sample frame accumulation.
 //! - **Audio Mixing**: Synchronizes audio with video frames.
+//! - **Tiled Rendering**: Renders very large frames and stills tile by tile.
 
+pub mod tiles;
 pub mod video;
 
+pub use tiles::render_still_tiled;
 pub use video::render_export;
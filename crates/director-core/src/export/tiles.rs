@@ -0,0 +1,172 @@
+//! # Tiled Rendering
+//!
+//! Renders frames in tiles for outputs too large for a single surface.
+//!
+//! ## Responsibilities
+//! - **Tile Grid**: Splits the frame into tiles of `Director::tile_size`.
+//! - **Overlap**: Renders a margin of `Director::tile_overlap` around each tile and
+//!   keeps only the inner region, so blurs, shadows and backdrop filters read the
+//!   same neighbouring pixels as an untiled render.
+//! - **Assembly**: Resolves each tile into a tile-sized 8-bit surface and reads it
+//!   back into its rows of the RGBA output buffer.
+//!
+//! Peak memory is one tile surface (plus two for transitions), one 8-bit tile and
+//! the 8-bit output frame, regardless of supersampling or working colour space.
+
+use crate::director::Director;
+use crate::errors::RenderError;
+use crate::systems::layout::LayoutEngine;
+use crate::systems::renderer::{draw_frame, prepare_frame};
+use skia_safe::canvas::SrcRectConstraint;
+use skia_safe::{
+    AlphaType, ColorSpace, ColorType, FilterMode, IRect, ImageInfo, MipmapMode, Paint, Rect,
+    SamplingOptions, Surface,
+};
+
+/// Splits a `width` x `height` frame into tiles of at most `tile` pixels, row by row.
+pub fn tile_grid(width: i32, height: i32, tile: i32) -> Vec<IRect> {
+    let tile = tile.max(1);
+    let mut tiles = Vec::new();
+    for top in (0..height).step_by(tile as usize) {
+        for left in (0..width).step_by(tile as usize) {
+            tiles.push(IRect::from_ltrb(
+                left,
+                top,
+                (left + tile).min(width),
+                (top + tile).min(height),
+            ));
+        }
+    }
+    tiles
+}
+
+/// Image info for a tile surface: the tile plus overlap on each side, at the render scale.
+pub(crate) fn tile_info(director: &Director, tile: i32, scale: i32) -> ImageInfo {
+    let side = (tile + 2 * director.tile_overlap as i32) * scale;
+    director.color_space.image_info((side, side))
+}
+
+/// Renders a still frame in tiles, returning RGBA8888 (premultiplied sRGB) pixels.
+///
+/// Use this for stills too large to rasterize in one surface. The result matches
+/// `render_frame` up to rounding as long as `tile_overlap` covers the widest blur.
+pub fn render_still_tiled(
+    director: &mut Director,
+    time: f64,
+    tile_size: u32,
+) -> Result<Vec<u8>, RenderError> {
+    let tile = tile_size.max(1) as i32;
+    let scale = director.supersampling.clamp(1, 4) as i32;
+    let info = tile_info(director, tile, scale);
+    let mut surface =
+        skia_safe::surfaces::raster(&info, None, None).ok_or(RenderError::SurfaceFailure)?;
+    let mut transition_surfaces = None;
+    if !director.transitions.is_empty() {
+        let surf_a =
+            skia_safe::surfaces::raster(&info, None, None).ok_or(RenderError::SurfaceFailure)?;
+        let surf_b =
+            skia_safe::surfaces::raster(&info, None, None).ok_or(RenderError::SurfaceFailure)?;
+        transition_surfaces = Some((surf_a, surf_b));
+    }
+
+    let mut layout_engine = LayoutEngine::new();
+    render_tiled_frame(
+        director,
+        &mut layout_engine,
+        &[time],
+        tile,
+        scale,
+        &mut surface,
+        &mut transition_surfaces,
+    )
+}
+
+/// Renders one frame tile by tile into a new RGBA8888 buffer.
+///
+/// `sample_times` holds one time per motion blur sample. Samples are the outer
+/// loop so each time is updated once; each tile is averaged with the rows already
+/// in the output before being read back. `surface` and `transition_surfaces` must
+/// be sized by `tile_info`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_tiled_frame(
+    director: &mut Director,
+    layout_engine: &mut LayoutEngine,
+    sample_times: &[f64],
+    tile: i32,
+    scale: i32,
+    surface: &mut Surface,
+    transition_surfaces: &mut Option<(Surface, Surface)>,
+) -> Result<Vec<u8>, RenderError> {
+    let (width, height) = (director.width, director.height);
+    let overlap = director.tile_overlap as i32;
+
+    let rgba = |w: i32, h: i32| {
+        ImageInfo::new(
+            (w, h),
+            ColorType::RGBA8888,
+            AlphaType::Premul,
+            Some(ColorSpace::new_srgb()),
+        )
+    };
+    let row_bytes = width.max(0) as usize * 4;
+    let mut pixels = vec![0u8; row_bytes * height.max(0) as usize];
+    let mut tile_frame = skia_safe::surfaces::raster(&rgba(tile, tile), None, None)
+        .ok_or(RenderError::SurfaceFailure)?;
+
+    let tiles = tile_grid(width, height, tile);
+    let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear);
+
+    for (s, &time) in sample_times.iter().enumerate() {
+        prepare_frame(director, layout_engine, time);
+
+        // Tiles are opaque, so the first sample replaces and later ones average in
+        let mut paint = Paint::default();
+        paint.set_alpha_f(1.0 / (s as f32 + 1.0));
+
+        for bounds in &tiles {
+            let canvas = surface.canvas();
+            canvas.save();
+            canvas.scale((scale as f32, scale as f32));
+            canvas.translate((
+                (overlap - bounds.left) as f32,
+                (overlap - bounds.top) as f32,
+            ));
+            let result = draw_frame(director, time, canvas, transition_surfaces);
+            canvas.restore();
+            result?;
+
+            let image = surface.image_snapshot();
+            let image = if scale > 1 {
+                image.with_default_mipmaps().unwrap_or(image)
+            } else {
+                image
+            };
+            let inner = Rect::from_xywh(
+                (overlap * scale) as f32,
+                (overlap * scale) as f32,
+                (bounds.width() * scale) as f32,
+                (bounds.height() * scale) as f32,
+            );
+            // The tile's rows in the output, starting at its top-left pixel
+            let region = rgba(bounds.width(), bounds.height());
+            let offset = bounds.top as usize * row_bytes + bounds.left as usize * 4;
+            if s > 0 {
+                tile_frame
+                    .canvas()
+                    .write_pixels(&region, &pixels[offset..], row_bytes, (0, 0));
+            }
+            tile_frame.canvas().draw_image_rect_with_sampling_options(
+                &image,
+                Some((&inner, SrcRectConstraint::Fast)),
+                Rect::from_iwh(bounds.width(), bounds.height()),
+                sampling,
+                &paint,
+            );
+            if !tile_frame.read_pixels(&region, &mut pixels[offset..], row_bytes, (0, 0)) {
+                return Err(RenderError::SurfaceFailure);
+            }
+        }
+    }
+
+    Ok(pixels)
+}
@@ -6,6 +6,9 @@
 //! - **Frame Loop**: Iterates through all frames.
 //! - **Encoding**: FFmpeg H.264/AAC encoding.
 //! - **Motion Blur**: Shutter angle / multi-sample accumulation.
+//! - **Supersampling**: Renders at 2x/4x and downsamples each frame before encoding.
+//! - **Tiling**: Frames larger than `Director::tile_size` are rendered tile by tile (see `export::tiles`).
+//! - **Colour Management**: Renders in the director's working colour space, outputs Rec.709.
 //! - **Audio Sync**: Mixes audio per frame.
 //!
 //! ## Key Functions
@@ -14,12 +17,13 @@
 use crate::audio::load_audio_bytes;
 use crate::director::Director;
 use crate::errors::RenderError;
+use crate::export::tiles::{render_tiled_frame, tile_info};
 use crate::systems::layout::LayoutEngine;
 use crate::systems::renderer::{render_at_time, GpuContext};
 use crate::video_wrapper::{Encoder, EncoderSettings, Locator, Time};
 use anyhow::Result;
 use ndarray::Array3;
-use skia_safe::{AlphaType, ColorSpace, ColorType};
+use skia_safe::{AlphaType, ColorSpace, ColorType, FilterMode, MipmapMode, SamplingOptions};
 use std::path::PathBuf;
 use tracing::instrument;
 
@@ -33,7 +37,7 @@ use skia_safe::gpu::{Budgeted, SurfaceOrigin};
 /// 2. Iterates through every frame of the timeline.
 /// 3. Updates the Scene Graph (Update -> Layout -> PostLayout).
 /// 4. Rasterizes the scene to a Skia Surface.
-/// 5. Handles Motion Blur via accumulation buffer (if configured).
+/// 5. Handles Motion Blur via accumulation buffer and supersampling (if configured).
 /// 6. Sends pixels to the encoder.
 /// 7. Mixes and encodes audio.
 ///
@@ -80,22 +84,33 @@ pub fn render_export(
 
     let mut encoder = Encoder::new(&destination, settings)?;
 
-    let info = skia_safe::ImageInfo::new(
-        (width, height),
-        ColorType::RGBA8888,
-        AlphaType::Premul,
-        Some(ColorSpace::new_srgb()),
-    );
+    let info = director.color_space.image_info((width, height));
+
+    // Supersampling renders at a multiple of the output size and downsamples each frame
+    let scale = director.supersampling.clamp(1, 4) as i32;
+
+    // Tiled frames only need surfaces the size of one tile
+    let tile = director
+        .tile_size
+        .map(|t| t.max(1) as i32)
+        .filter(|&t| t < width || t < height);
+    let render_info = match tile {
+        Some(tile) => tile_info(director, tile, scale),
+        None => director
+            .color_space
+            .image_info((width * scale, height * scale)),
+    };
 
     #[allow(unused_mut)]
     let mut surface = None;
 
+    // Tiles are assembled into a CPU buffer, so tiled exports render on the CPU
     #[cfg(feature = "vulkan")]
-    if let Some(ctx) = gpu_context {
+    if let Some(ctx) = gpu_context.filter(|_| tile.is_none()) {
         surface = skia_safe::gpu::surfaces::render_target(
             ctx,
             Budgeted::Yes,
-            &info,
+            &render_info,
             0,
             SurfaceOrigin::TopLeft,
             None,
@@ -105,10 +120,20 @@ pub fn render_export(
     }
 
     let mut surface = surface
-        .or_else(|| skia_safe::surfaces::raster(&info, None, None))
+        .or_else(|| skia_safe::surfaces::raster(&render_info, None, None))
         .ok_or(RenderError::SurfaceFailure)?;
 
-    let mut accumulation_surface = if director.samples_per_frame > 1 {
+    let mut accumulation_surface = if director.samples_per_frame > 1 && tile.is_none() {
+        Some(
+            surface
+                .new_surface(&render_info)
+                .ok_or(RenderError::SurfaceFailure)?,
+        )
+    } else {
+        None
+    };
+
+    let mut output_surface = if scale > 1 && tile.is_none() {
         Some(
             surface
                 .new_surface(&info)
@@ -129,10 +154,10 @@ pub fn render_export(
 
     // Pre-allocate transition surfaces to avoid per-frame allocation churn
     let mut transition_surfaces = if !director.transitions.is_empty() {
-        let surf_a =
-            skia_safe::surfaces::raster(&info, None, None).ok_or(RenderError::SurfaceFailure)?;
-        let surf_b =
-            skia_safe::surfaces::raster(&info, None, None).ok_or(RenderError::SurfaceFailure)?;
+        let surf_a = skia_safe::surfaces::raster(&render_info, None, None)
+            .ok_or(RenderError::SurfaceFailure)?;
+        let surf_b = skia_safe::surfaces::raster(&render_info, None, None)
+            .ok_or(RenderError::SurfaceFailure)?;
         Some((surf_a, surf_b))
     } else {
         None
@@ -140,70 +165,115 @@ pub fn render_export(
 
     for i in 0..total_frames {
         let frame_start_time = i as f64 / fps as f64;
+        let _frame = tracing::trace_span!("frame", index = i, time = frame_start_time).entered();
         let shutter_start_time = frame_start_time - (shutter_duration / 2.0);
 
-        if samples == 1 {
-            render_at_time(
+        if let Some(tile) = tile {
+            let sample_times: Vec<f64> = (0..samples)
+                .map(|s| {
+                    if samples > 1 {
+                        shutter_start_time + (s as f64 / (samples - 1) as f64) * shutter_duration
+                    } else {
+                        frame_start_time
+                    }
+                })
+                .collect();
+            let bytes = render_tiled_frame(
                 director,
                 &mut layout_engine,
-                frame_start_time,
-                surface.canvas(),
+                &sample_times,
+                tile,
+                scale,
+                &mut surface,
                 &mut transition_surfaces,
             )?;
+            let frame_shape = (height as usize, width as usize, 4);
+            let frame = Array3::from_shape_vec(frame_shape, bytes)?;
+            encoder.encode(&frame, Time::from_secs_f64(i as f64 / fps as f64))?;
         } else {
-            let scratch_surface = accumulation_surface.as_mut().unwrap();
-
-            for s in 0..samples {
-                let t_offset = if samples > 1 {
-                    (s as f64 / (samples - 1) as f64) * shutter_duration
-                } else {
-                    0.0
-                };
-                let sample_time = shutter_start_time + t_offset;
-
+            if samples == 1 {
+                let canvas = surface.canvas();
+                canvas.save();
+                canvas.scale((scale as f32, scale as f32));
                 render_at_time(
                     director,
                     &mut layout_engine,
-                    sample_time,
-                    scratch_surface.canvas(),
+                    frame_start_time,
+                    canvas,
                     &mut transition_surfaces,
                 )?;
+                canvas.restore();
+            } else {
+                let scratch_surface = accumulation_surface.as_mut().unwrap();
 
-                let weight = 1.0 / (s as f32 + 1.0);
-                let mut paint = skia_safe::Paint::default();
-                paint.set_alpha_f(weight);
-                let image = scratch_surface.image_snapshot();
+                for s in 0..samples {
+                    let t_offset = if samples > 1 {
+                        (s as f64 / (samples - 1) as f64) * shutter_duration
+                    } else {
+                        0.0
+                    };
+                    let sample_time = shutter_start_time + t_offset;
 
-                if s == 0 {
-                    surface.canvas().clear(skia_safe::Color::BLACK);
+                    let canvas = scratch_surface.canvas();
+                    canvas.save();
+                    canvas.scale((scale as f32, scale as f32));
+                    render_at_time(
+                        director,
+                        &mut layout_engine,
+                        sample_time,
+                        canvas,
+                        &mut transition_surfaces,
+                    )?;
+                    canvas.restore();
+
+                    let weight = 1.0 / (s as f32 + 1.0);
+                    let mut paint = skia_safe::Paint::default();
+                    paint.set_alpha_f(weight);
+                    let image = scratch_surface.image_snapshot();
+
+                    if s == 0 {
+                        surface.canvas().clear(skia_safe::Color::BLACK);
+                    }
+                    surface.canvas().draw_image(&image, (0, 0), Some(&paint));
                 }
-                surface.canvas().draw_image(&image, (0, 0), Some(&paint));
             }
-        }
 
-        if let Some(pixmap) = surface.peek_pixels() {
-            if let Some(bytes) = pixmap.bytes() {
-                let frame_shape = (height as usize, width as usize, 4);
-                if bytes.len() == width as usize * height as usize * 4 {
-                    let vec_bytes = bytes.to_vec();
-                    let frame = Array3::from_shape_vec(frame_shape, vec_bytes)?;
+            let surface = match output_surface.as_mut() {
+                Some(output) => {
+                    downsample(&mut surface, output);
+                    output
+                }
+                None => &mut surface,
+            };
+
+            // 8-bit sRGB surfaces can be sent as-is; linear F16 surfaces are converted on readback
+            let direct = surface
+                .peek_pixels()
+                .filter(|pixmap| pixmap.color_type() == ColorType::RGBA8888);
+            if let Some(pixmap) = direct {
+                if let Some(bytes) = pixmap.bytes() {
+                    let frame_shape = (height as usize, width as usize, 4);
+                    if bytes.len() == width as usize * height as usize * 4 {
+                        let vec_bytes = bytes.to_vec();
+                        let frame = Array3::from_shape_vec(frame_shape, vec_bytes)?;
+                        encoder.encode(&frame, Time::from_secs_f64(i as f64 / fps as f64))?;
+                    }
+                }
+            } else {
+                let mut bytes = Vec::with_capacity((width * height * 4) as usize);
+                bytes.resize((width * height * 4) as usize, 0);
+                let info = skia_safe::ImageInfo::new(
+                    (width, height),
+                    ColorType::RGBA8888,
+                    AlphaType::Premul,
+                    Some(ColorSpace::new_srgb()),
+                );
+                if surface.read_pixels(&info, &mut bytes, (width * 4) as usize, (0, 0)) {
+                    let frame_shape = (height as usize, width as usize, 4);
+                    let frame = Array3::from_shape_vec(frame_shape, bytes)?;
                     encoder.encode(&frame, Time::from_secs_f64(i as f64 / fps as f64))?;
                 }
             }
-        } else {
-            let mut bytes = Vec::with_capacity((width * height * 4) as usize);
-            bytes.resize((width * height * 4) as usize, 0);
-            let info = skia_safe::ImageInfo::new(
-                (width, height),
-                ColorType::RGBA8888,
-                AlphaType::Premul,
-                None,
-            );
-            if surface.read_pixels(&info, &mut bytes, (width * 4) as usize, (0, 0)) {
-                let frame_shape = (height as usize, width as usize, 4);
-                let frame = Array3::from_shape_vec(frame_shape, bytes)?;
-                encoder.encode(&frame, Time::from_secs_f64(i as f64 / fps as f64))?;
-            }
         }
 
         let audio_samples = director.mix_audio(samples_per_frame, frame_start_time);
@@ -214,3 +284,20 @@ pub fn render_export(
 
     Ok(())
 }
+
+/// Scales a supersampled frame down to the output surface.
+///
+/// Linear mipmap sampling averages whole blocks of source pixels for 2x/4x factors,
+/// which is a box filter over each output pixel.
+fn downsample(source: &mut skia_safe::Surface, target: &mut skia_safe::Surface) {
+    let image = source.image_snapshot();
+    let image = image.with_default_mipmaps().unwrap_or(image);
+    let bounds = skia_safe::Rect::from_iwh(target.width(), target.height());
+    let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear);
+
+    let mut paint = skia_safe::Paint::default();
+    paint.set_blend_mode(skia_safe::BlendMode::Src);
+    target
+        .canvas()
+        .draw_image_rect_with_sampling_options(&image, None, bounds, sampling, &paint);
+}
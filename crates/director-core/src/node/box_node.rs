@@ -3,14 +3,18 @@ use crate::element::Element;
 use crate::errors::RenderError;
 use crate::node::{parse_easing, EffectType, build_effect_filter};
 use crate::types::Color;
-use skia_safe::{Canvas, ClipOp, Paint, PaintStyle, RRect, Rect};
+use skia_safe::canvas::SaveLayerRec;
+use skia_safe::{
+    color_filters, image_filters, BlendMode, Canvas, ClipOp, ColorMatrix, ImageFilter, Paint,
+    PaintStyle, RRect, Rect, TileMode,
+};
 use std::any::Any;
 use taffy::style::{AlignItems, FlexDirection, JustifyContent, Style};
 
 // --- Box Node ---
 /// A fundamental layout and styling block (div-like).
 ///
-/// Supports background color, borders, shadows, and rounded corners.
+/// Supports background color, borders, shadows, rounded corners and backdrop blur (frosted glass).
 #[derive(Debug, Clone)]
 pub struct BoxNode {
     pub style: Style,
@@ -26,6 +30,12 @@ pub struct BoxNode {
     pub border_width: Animated<f32>,
     pub border_color: Option<Animated<Color>>,
     pub overflow: String,
+    /// Blur sigma applied to whatever is drawn behind the box (0 = off).
+    pub backdrop_blur: Animated<f32>,
+    /// Saturation of the blurred backdrop (1.0 = unchanged, 0.0 = grayscale).
+    pub backdrop_saturation: Animated<f32>,
+    /// Colour mixed over the backdrop, weighted by its alpha.
+    pub backdrop_tint: Option<Animated<Color>>,
 }
 
 impl BoxNode {
@@ -49,8 +59,63 @@ impl BoxNode {
             border_width: Animated::new(0.0),
             border_color: None,
             overflow: "visible".to_string(),
+            backdrop_blur: Animated::new(0.0),
+            backdrop_saturation: Animated::new(1.0),
+            backdrop_tint: None,
         }
     }
+
+    /// Current values of every animated property, compared by `update` to detect changes.
+    fn animated_values(&self) -> ([Option<Color>; 4], [f32; 9]) {
+        let color = |c: &Option<Animated<Color>>| c.as_ref().map(|a| a.current_value);
+        (
+            [
+                color(&self.bg_color),
+                color(&self.shadow_color),
+                color(&self.border_color),
+                color(&self.backdrop_tint),
+            ],
+            [
+                self.opacity.current_value,
+                self.blur.current_value,
+                self.shadow_blur.current_value,
+                self.shadow_offset_x.current_value,
+                self.shadow_offset_y.current_value,
+                self.border_radius.current_value,
+                self.border_width.current_value,
+                self.backdrop_blur.current_value,
+                self.backdrop_saturation.current_value,
+            ],
+        )
+    }
+
+    /// Builds the filter applied to the backdrop, or `None` if the box has no backdrop effect.
+    fn backdrop_filter(&self) -> Option<ImageFilter> {
+        let blur = self.backdrop_blur.current_value;
+        let saturation = self.backdrop_saturation.current_value;
+        if blur <= 0.0 && (saturation - 1.0).abs() < f32::EPSILON && self.backdrop_tint.is_none() {
+            return None;
+        }
+
+        // Clamp so the edges of the backdrop don't fade to transparent
+        let mut filter = if blur > 0.0 {
+            image_filters::blur((blur, blur), TileMode::Clamp, None, None)
+        } else {
+            None
+        };
+        if (saturation - 1.0).abs() >= f32::EPSILON {
+            let mut cm = ColorMatrix::default();
+            cm.set_saturation(saturation);
+            filter = image_filters::color_filter(color_filters::matrix(&cm, None), filter, None);
+        }
+        if let Some(tint) = &self.backdrop_tint {
+            let tint = color_filters::blend(tint.current_value.to_skia(), BlendMode::SrcATop);
+            if let Some(cf) = tint {
+                filter = image_filters::color_filter(cf, filter, None);
+            }
+        }
+        filter
+    }
 }
 
 impl Element for BoxNode {
@@ -65,19 +130,22 @@ impl Element for BoxNode {
         self.style.clone()
     }
 
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
     fn update(&mut self, time: f64) -> bool {
-        let mut changed = false;
-        if let Some(bg) = &mut self.bg_color {
-            bg.update(time);
-            changed = true;
-        }
-        if let Some(sc) = &mut self.shadow_color {
-            sc.update(time);
-            changed = true;
-        }
-        if let Some(bc) = &mut self.border_color {
-            bc.update(time);
-            changed = true;
+        let before = self.animated_values();
+        for color in [
+            &mut self.bg_color,
+            &mut self.shadow_color,
+            &mut self.border_color,
+            &mut self.backdrop_tint,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            color.update(time);
         }
         self.opacity.update(time);
         self.blur.update(time);
@@ -86,7 +154,9 @@ impl Element for BoxNode {
         self.shadow_offset_y.update(time);
         self.border_radius.update(time);
         self.border_width.update(time);
-        changed
+        self.backdrop_blur.update(time);
+        self.backdrop_saturation.update(time);
+        self.animated_values() != before
     }
 
     fn render(
@@ -102,6 +172,21 @@ impl Element for BoxNode {
 
         canvas.save();
 
+        // Frosted glass: replace the area behind the box with its filtered copy
+        if let Some(backdrop) = self.backdrop_filter() {
+            let mut layer_paint = Paint::default();
+            layer_paint.set_alpha_f(local_opacity);
+            canvas.save();
+            canvas.clip_rrect(rrect, ClipOp::Intersect, true);
+            canvas.save_layer(
+                &SaveLayerRec::default()
+                    .paint(&layer_paint)
+                    .backdrop(&backdrop),
+            );
+            canvas.restore();
+            canvas.restore();
+        }
+
         if self.overflow == "hidden" {
             canvas.clip_rrect(rrect, ClipOp::Intersect, true);
         }
@@ -192,10 +277,71 @@ impl Element for BoxNode {
             "border_width" => self
                 .border_width
                 .add_segment(start, target, duration, ease_fn),
+            "backdrop_blur" => self
+                .backdrop_blur
+                .add_segment(start, target, duration, ease_fn),
+            "backdrop_saturation" => self
+                .backdrop_saturation
+                .add_segment(start, target, duration, ease_fn),
             _ => {}
         }
     }
 
+    fn outline_path(&self, layout_rect: Rect) -> Option<skia_safe::Path> {
+        let radius = self.border_radius.current_value;
+        let mut path = skia_safe::Path::new();
+        path.add_rrect(RRect::new_rect_xy(layout_rect, radius, radius), None);
+        Some(path)
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            "blur" => Some(self.blur.current_value),
+            "shadow_blur" => Some(self.shadow_blur.current_value),
+            "shadow_x" => Some(self.shadow_offset_x.current_value),
+            "shadow_y" => Some(self.shadow_offset_y.current_value),
+            "border_radius" => Some(self.border_radius.current_value),
+            "border_width" => Some(self.border_width.current_value),
+            "backdrop_blur" => Some(self.backdrop_blur.current_value),
+            "backdrop_saturation" => Some(self.backdrop_saturation.current_value),
+            _ => None,
+        }
+    }
+
+    fn property_at(&self, property: &str, time: f64) -> Option<f32> {
+        let anim = match property {
+            "opacity" => &self.opacity,
+            "blur" => &self.blur,
+            "shadow_blur" => &self.shadow_blur,
+            "shadow_x" => &self.shadow_offset_x,
+            "shadow_y" => &self.shadow_offset_y,
+            "border_radius" => &self.border_radius,
+            "border_width" => &self.border_width,
+            "backdrop_blur" => &self.backdrop_blur,
+            "backdrop_saturation" => &self.backdrop_saturation,
+            _ => return None,
+        };
+        Some(anim.value_at(time))
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        let anim = match property {
+            "opacity" => &mut self.opacity,
+            "blur" => &mut self.blur,
+            "shadow_blur" => &mut self.shadow_blur,
+            "shadow_x" => &mut self.shadow_offset_x,
+            "shadow_y" => &mut self.shadow_offset_y,
+            "border_radius" => &mut self.border_radius,
+            "border_width" => &mut self.border_width,
+            "backdrop_blur" => &mut self.backdrop_blur,
+            "backdrop_saturation" => &mut self.backdrop_saturation,
+            _ => return false,
+        };
+        anim.current_value = value;
+        true
+    }
+
     fn animate_property_spring(
         &mut self,
         property: &str,
@@ -219,6 +365,8 @@ impl Element for BoxNode {
             "shadow_y" => apply(&mut self.shadow_offset_y),
             "border_radius" => apply(&mut self.border_radius),
             "border_width" => apply(&mut self.border_width),
+            "backdrop_blur" => apply(&mut self.backdrop_blur),
+            "backdrop_saturation" => apply(&mut self.backdrop_saturation),
             _ => {}
         }
     }
@@ -0,0 +1,671 @@
+use crate::animation::{Animated, EasingType, TweenableVector};
+use crate::element::{Element, TextSpan};
+use crate::node::text::{line_paragraph, SpanDefaults};
+use crate::node::{parse_easing, trim_path};
+use crate::types::{ChartKind, Color};
+use skia_safe::textlayout::{FontCollection, Paragraph};
+use skia_safe::{Canvas, Paint, PaintCap, PaintJoin, PaintStyle, Path, Point, Rect};
+use std::any::Any;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use taffy::style::Style;
+
+/// Colours of series (or pie slices) when no palette is given.
+pub const DEFAULT_CHART_COLORS: [Color; 6] = [
+    Color::new(0.306, 0.475, 0.655, 1.0),
+    Color::new(0.949, 0.557, 0.169, 1.0),
+    Color::new(0.882, 0.341, 0.349, 1.0),
+    Color::new(0.463, 0.718, 0.698, 1.0),
+    Color::new(0.349, 0.631, 0.310, 1.0),
+    Color::new(0.929, 0.788, 0.282, 1.0),
+];
+
+/// A named set of values, one per chart label.
+#[derive(Debug, Clone)]
+pub struct ChartSeries {
+    pub name: String,
+    pub values: Animated<TweenableVector>,
+}
+
+// --- Chart Node ---
+/// Draws a bar, line, area or pie chart with value axis, labels and legend.
+///
+/// `build` (`0.0 - 1.0`) animates the chart in: bars grow, lines draw on and pies sweep
+/// round. [`ChartNode::animate_data`] moves the values to another dataset. The value axis
+/// spans every dataset, so it stays put while the values change.
+#[derive(Clone)]
+pub struct ChartNode {
+    pub style: Style,
+    pub kind: ChartKind,
+    pub labels: Vec<String>,
+    pub series: Vec<ChartSeries>,
+    /// Series colours (slice colours for pies), repeated as needed.
+    pub colors: Vec<Color>,
+    pub font_collection: Arc<Mutex<FontCollection>>,
+    pub font_family: Option<String>,
+    pub font_size: f32,
+    pub text_color: Color,
+    pub axis_color: Color,
+    /// Horizontal lines at each value tick; `None` hides them.
+    pub grid_color: Option<Color>,
+    pub show_axes: bool,
+    pub show_legend: bool,
+    /// Writes each value by its bar, point or slice.
+    pub show_values: bool,
+    /// Value axis bounds; by default they fit the data and include zero.
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    /// Text around formatted values, e.g. `"$"` or `"%"`.
+    pub prefix: String,
+    pub suffix: String,
+    /// Decimal places of values; by default 0 for whole numbers, otherwise 1.
+    pub decimals: Option<usize>,
+    /// Space between bar groups, as a fraction of each label's width.
+    pub bar_gap: f32,
+    pub line_width: f32,
+    /// Hole in the middle of a pie, as a fraction of its radius.
+    pub inner_radius: f32,
+    /// How far through the build the last label starts, so labels build one after another.
+    pub stagger: f32,
+    pub build: Animated<f32>,
+    pub opacity: Animated<f32>,
+}
+
+impl fmt::Debug for ChartNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChartNode")
+            .field("kind", &self.kind)
+            .field("labels", &self.labels)
+            .field("series", &self.series)
+            .finish()
+    }
+}
+
+impl ChartNode {
+    /// Creates a chart with one series per `(name, values)` pair.
+    pub fn new(
+        kind: ChartKind,
+        labels: Vec<String>,
+        series: Vec<(String, Vec<f32>)>,
+        font_collection: Arc<Mutex<FontCollection>>,
+    ) -> Self {
+        let count = labels.len();
+        Self {
+            style: Style::DEFAULT,
+            kind,
+            labels,
+            series: series
+                .into_iter()
+                .map(|(name, values)| ChartSeries {
+                    name,
+                    values: Animated::new(fit_values(values, count)),
+                })
+                .collect(),
+            colors: DEFAULT_CHART_COLORS.to_vec(),
+            font_collection,
+            font_family: None,
+            font_size: 16.0,
+            text_color: Color::WHITE,
+            axis_color: Color::new(1.0, 1.0, 1.0, 0.6),
+            grid_color: Some(Color::new(1.0, 1.0, 1.0, 0.15)),
+            show_axes: true,
+            show_legend: true,
+            show_values: false,
+            min: None,
+            max: None,
+            prefix: String::new(),
+            suffix: String::new(),
+            decimals: None,
+            bar_gap: 0.3,
+            line_width: 3.0,
+            inner_radius: 0.0,
+            stagger: 0.3,
+            build: Animated::new(1.0),
+            opacity: Animated::new(1.0),
+        }
+    }
+
+    /// Animates each series to its new values after holding the current ones for `delay`
+    /// seconds, following any earlier transitions.
+    ///
+    /// Series without new values keep their current ones; missing values are zero.
+    pub fn animate_data(
+        &mut self,
+        data: Vec<Vec<f32>>,
+        delay: f64,
+        duration: f64,
+        easing: EasingType,
+    ) {
+        let count = self.labels.len();
+        for (series, values) in self.series.iter_mut().zip(data) {
+            if delay > 0.0 {
+                let held = series.values.raw_keyframes.last().map(|k| k.0.clone());
+                series
+                    .values
+                    .add_keyframe(held.unwrap_or_default(), delay, EasingType::Linear);
+            }
+            series
+                .values
+                .add_keyframe(fit_values(values, count), duration, easing);
+        }
+    }
+
+    fn color(&self, index: usize) -> Color {
+        if self.colors.is_empty() {
+            DEFAULT_CHART_COLORS[index % DEFAULT_CHART_COLORS.len()]
+        } else {
+            self.colors[index % self.colors.len()]
+        }
+    }
+
+    fn all_values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.series.iter().flat_map(|s| {
+            s.values
+                .raw_keyframes
+                .iter()
+                .flat_map(|(v, _, _)| v.0.iter().copied())
+        })
+    }
+
+    /// Value axis as `(low, high, tick step)`.
+    fn axis(&self) -> (f32, f32, f32) {
+        let (lo, hi) = self
+            .all_values()
+            .fold((0.0f32, 0.0f32), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let (lo, hi) = (self.min.unwrap_or(lo), self.max.unwrap_or(hi));
+        let hi = if hi > lo { hi } else { lo + 1.0 };
+
+        let raw_step = (hi - lo) / 5.0;
+        let magnitude = 10f32.powf(raw_step.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|m| m * magnitude)
+            .find(|s| *s >= raw_step)
+            .unwrap_or(10.0 * magnitude);
+        let lo = self.min.unwrap_or((lo / step).floor() * step);
+        let hi = self.max.unwrap_or((hi / step).ceil() * step);
+        (lo, hi, step)
+    }
+
+    fn value_decimals(&self) -> usize {
+        self.decimals.unwrap_or_else(|| {
+            if self.all_values().all(|v| v.fract() == 0.0) {
+                0
+            } else {
+                1
+            }
+        })
+    }
+
+    fn format(&self, value: f32, decimals: usize) -> String {
+        format!("{}{:.*}{}", self.prefix, decimals, value, self.suffix)
+    }
+
+    /// Build progress of the label at `index`, staggered across labels.
+    fn progress(&self, index: usize, count: usize) -> f32 {
+        let build = self.build.current_value;
+        let stagger = self.stagger.clamp(0.0, 0.95);
+        let start = if count > 1 {
+            index as f32 / (count - 1) as f32 * stagger
+        } else {
+            0.0
+        };
+        // Overshooting easings push every label past its full size together
+        ((build.min(1.0) - start) / (1.0 - stagger)).clamp(0.0, 1.0) + (build - 1.0).max(0.0)
+    }
+
+    fn paragraph(&self, text: &str, color: Color) -> Paragraph {
+        let families = match &self.font_family {
+            Some(family) => vec![family.as_str()],
+            None => vec!["Sans Serif", "Arial"],
+        };
+        let defaults = SpanDefaults {
+            font_size: self.font_size,
+            color,
+            font_families: &families,
+            tabular_figures: false,
+        };
+        let span = TextSpan {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        line_paragraph(&[span], &defaults, &self.font_collection)
+    }
+
+    /// Paints `paragraph` so the point at fractions `align` of its size lands on `anchor`.
+    fn paint_text(canvas: &Canvas, paragraph: &Paragraph, anchor: Point, align: (f32, f32)) {
+        let origin = Point::new(
+            anchor.x - paragraph.max_width() * align.0,
+            anchor.y - paragraph.height() * align.1,
+        );
+        paragraph.paint(canvas, origin);
+    }
+
+    fn fill_paint(color: Color) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color4f(color.to_color4f(), None);
+        paint
+    }
+
+    fn line_paint(color: Color, width: f32) -> Paint {
+        let mut paint = Self::fill_paint(color);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(width);
+        paint.set_stroke_cap(PaintCap::Round);
+        paint.set_stroke_join(PaintJoin::Round);
+        paint
+    }
+
+    /// Draws the legend centred along the top of `rect`, wrapping onto more rows as
+    /// needed, and returns its height.
+    fn draw_legend(&self, canvas: &Canvas, rect: Rect) -> f32 {
+        let names: Vec<&str> = match self.kind {
+            ChartKind::Pie => self.labels.iter().map(String::as_str).collect(),
+            _ => self.series.iter().map(|s| s.name.as_str()).collect(),
+        };
+        let entries: Vec<(usize, Paragraph)> = names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(i, name)| (i, self.paragraph(name, self.text_color)))
+            .collect();
+        let Some(line_height) = entries.iter().map(|(_, p)| p.height()).reduce(f32::max) else {
+            return 0.0;
+        };
+
+        let swatch = self.font_size * 0.7;
+        let gap = self.font_size;
+        let entry_width = |p: &Paragraph| swatch + gap * 0.4 + p.max_width();
+        let mut rows: Vec<Vec<&(usize, Paragraph)>> = vec![Vec::new()];
+        let mut row_width = 0.0;
+        for entry in &entries {
+            let width = entry_width(&entry.1);
+            let row = rows.last_mut().unwrap();
+            if !row.is_empty() && row_width + gap + width > rect.width() {
+                rows.push(vec![entry]);
+                row_width = width;
+            } else {
+                row_width += if row.is_empty() { width } else { gap + width };
+                row.push(entry);
+            }
+        }
+
+        let mut y = rect.top;
+        for row in &rows {
+            let width: f32 =
+                row.iter().map(|(_, p)| entry_width(p)).sum::<f32>() + gap * (row.len() - 1) as f32;
+            let mut x = rect.center_x() - width / 2.0;
+            for (index, paragraph) in row {
+                let top = y + (line_height - swatch) / 2.0;
+                canvas.draw_rect(
+                    Rect::from_xywh(x, top, swatch, swatch),
+                    &Self::fill_paint(self.color(*index)),
+                );
+                x += swatch + gap * 0.4;
+                paragraph.paint(canvas, Point::new(x, y));
+                x += paragraph.max_width() + gap;
+            }
+            y += line_height;
+        }
+        y - rect.top
+    }
+
+    fn draw_cartesian(&self, canvas: &Canvas, area: Rect) {
+        let pad = self.font_size * 0.5;
+        let (lo, hi, step) = self.axis();
+        let tick_decimals = if step >= 1.0 {
+            0
+        } else {
+            (-step.log10().floor()) as usize
+        };
+        let tick_count = ((hi - lo) / step + 0.5).floor() as usize;
+        let ticks: Vec<f32> = (0..=tick_count)
+            .map(|i| lo + step * i as f32)
+            .filter(|t| *t <= hi + step * 0.01)
+            .collect();
+
+        let tick_labels: Vec<Paragraph> = if self.show_axes {
+            ticks
+                .iter()
+                .map(|t| self.paragraph(&self.format(*t, tick_decimals), self.text_color))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let category_labels: Vec<Paragraph> = if self.show_axes {
+            self.labels
+                .iter()
+                .map(|l| self.paragraph(l, self.text_color))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let label_width = tick_labels
+            .iter()
+            .map(|p| p.max_width())
+            .fold(0.0, f32::max);
+        let label_height = tick_labels
+            .iter()
+            .chain(&category_labels)
+            .map(|p| p.height())
+            .fold(0.0, f32::max);
+
+        let plot = Rect::new(
+            area.left
+                + if self.show_axes {
+                    label_width + pad
+                } else {
+                    0.0
+                },
+            area.top + label_height / 2.0,
+            area.right,
+            area.bottom
+                - if category_labels.is_empty() {
+                    label_height / 2.0
+                } else {
+                    label_height + pad
+                },
+        );
+        if plot.width() <= 0.0 || plot.height() <= 0.0 {
+            return;
+        }
+        let y_of = |v: f32| plot.bottom - (v - lo) / (hi - lo) * plot.height();
+        let base = y_of(0.0f32.clamp(lo, hi));
+
+        if let Some(grid) = self.grid_color {
+            let paint = Self::line_paint(grid, 1.0);
+            for t in &ticks {
+                let y = y_of(*t);
+                canvas.draw_line((plot.left, y), (plot.right, y), &paint);
+            }
+        }
+        if self.show_axes {
+            for (t, paragraph) in ticks.iter().zip(&tick_labels) {
+                Self::paint_text(
+                    canvas,
+                    paragraph,
+                    Point::new(plot.left - pad, y_of(*t)),
+                    (1.0, 0.5),
+                );
+            }
+            let paint = Self::line_paint(self.axis_color, 1.0);
+            canvas.draw_line((plot.left, plot.top), (plot.left, plot.bottom), &paint);
+            canvas.draw_line((plot.left, base), (plot.right, base), &paint);
+        }
+
+        let count = self
+            .series
+            .iter()
+            .map(|s| s.values.current_value.0.len())
+            .fold(self.labels.len(), usize::max)
+            .max(1);
+        let slot = plot.width() / count as f32;
+        for (i, paragraph) in category_labels.iter().enumerate() {
+            let x = plot.left + slot * (i as f32 + 0.5);
+            Self::paint_text(
+                canvas,
+                paragraph,
+                Point::new(x, plot.bottom + pad),
+                (0.5, 0.0),
+            );
+        }
+
+        let decimals = self.value_decimals();
+        let value_label = |value: f32, anchor: Point, alpha: f32| {
+            let mut color = self.text_color;
+            color.a *= alpha.clamp(0.0, 1.0);
+            let paragraph = self.paragraph(&self.format(value, decimals), color);
+            let align = if value < 0.0 { (0.5, 0.0) } else { (0.5, 1.0) };
+            let offset = if value < 0.0 { pad * 0.5 } else { -pad * 0.5 };
+            Self::paint_text(canvas, &paragraph, anchor + Point::new(0.0, offset), align);
+        };
+
+        match self.kind {
+            ChartKind::Bar => {
+                let groups = self.series.len().max(1);
+                let group_width = slot * (1.0 - self.bar_gap.clamp(0.0, 0.95));
+                let bar_width = group_width / groups as f32;
+                for (s, series) in self.series.iter().enumerate() {
+                    let paint = Self::fill_paint(self.color(s));
+                    for (i, value) in series.values.current_value.0.iter().enumerate() {
+                        let progress = self.progress(i, count);
+                        if progress <= 0.0 {
+                            continue;
+                        }
+                        let x = plot.left
+                            + slot * i as f32
+                            + (slot - group_width) / 2.0
+                            + bar_width * s as f32;
+                        let top = base + (y_of(*value) - base) * progress;
+                        canvas.draw_rect(
+                            Rect::new(x, top.min(base), x + bar_width, top.max(base)),
+                            &paint,
+                        );
+                        if self.show_values {
+                            value_label(*value, Point::new(x + bar_width / 2.0, top), progress);
+                        }
+                    }
+                }
+            }
+            ChartKind::Line | ChartKind::Area => {
+                let build = self.build.current_value.clamp(0.0, 1.0);
+                for (s, series) in self.series.iter().enumerate() {
+                    let points: Vec<Point> = series
+                        .values
+                        .current_value
+                        .0
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| Point::new(plot.left + slot * (i as f32 + 0.5), y_of(*v)))
+                        .collect();
+                    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                        continue;
+                    };
+                    let reveal = first.x + (last.x - first.x) * build;
+                    let mut line = Path::new();
+                    line.move_to(*first);
+                    for p in &points[1..] {
+                        line.line_to(*p);
+                    }
+
+                    let color = self.color(s);
+                    if self.kind == ChartKind::Area {
+                        let mut fill = line.clone();
+                        fill.line_to((last.x, base))
+                            .line_to((first.x, base))
+                            .close();
+                        let mut area_color = color;
+                        area_color.a *= 0.35;
+                        canvas.save();
+                        canvas.clip_rect(
+                            Rect::new(first.x, plot.top, reveal.max(first.x), plot.bottom),
+                            None,
+                            true,
+                        );
+                        canvas.draw_path(&fill, &Self::fill_paint(area_color));
+                        canvas.restore();
+                    }
+                    canvas.draw_path(
+                        &trim_path(&line, 0.0, build, 0.0),
+                        &Self::line_paint(color, self.line_width),
+                    );
+                    if self.show_values {
+                        for (p, value) in points.iter().zip(&series.values.current_value.0) {
+                            if p.x <= reveal + 0.5 {
+                                value_label(
+                                    *value,
+                                    *p + Point::new(0.0, -self.line_width / 2.0),
+                                    1.0,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            ChartKind::Pie => {}
+        }
+    }
+
+    fn draw_pie(&self, canvas: &Canvas, area: Rect) {
+        let Some(series) = self.series.first() else {
+            return;
+        };
+        let values = &series.values.current_value.0;
+        let total: f32 = values.iter().map(|v| v.max(0.0)).sum();
+        let radius = area.width().min(area.height()) / 2.0;
+        if total <= 0.0 || radius <= 0.0 {
+            return;
+        }
+        let center = area.center();
+        let oval = Rect::from_xywh(
+            center.x - radius,
+            center.y - radius,
+            radius * 2.0,
+            radius * 2.0,
+        );
+        let inner = radius * self.inner_radius.clamp(0.0, 0.95);
+        let inner_oval =
+            Rect::from_xywh(center.x - inner, center.y - inner, inner * 2.0, inner * 2.0);
+        let end = -90.0 + 360.0 * self.build.current_value.clamp(0.0, 1.0);
+        let decimals = self.value_decimals();
+
+        let mut angle = -90.0;
+        for (i, value) in values.iter().enumerate() {
+            let sweep = value.max(0.0) / total * 360.0;
+            let visible = sweep.min(end - angle);
+            if visible > 0.0 {
+                let mut slice = Path::new();
+                if inner > 0.0 {
+                    slice.arc_to(oval, angle, visible, true);
+                    slice.arc_to(inner_oval, angle + visible, -visible, false);
+                } else {
+                    slice.move_to(center);
+                    slice.arc_to(oval, angle, visible, false);
+                }
+                slice.close();
+                canvas.draw_path(&slice, &Self::fill_paint(self.color(i)));
+
+                if self.show_values && visible >= sweep {
+                    let mid = (angle + sweep / 2.0).to_radians();
+                    let distance = (radius + inner) / 2.0;
+                    let anchor = center + Point::new(mid.cos(), mid.sin()) * distance;
+                    let paragraph = self.paragraph(&self.format(*value, decimals), self.text_color);
+                    Self::paint_text(canvas, &paragraph, anchor, (0.5, 0.5));
+                }
+            }
+            angle += sweep;
+        }
+    }
+
+    fn animated_values(&self) -> (f32, f32, Vec<f32>) {
+        (
+            self.build.current_value,
+            self.opacity.current_value,
+            self.series
+                .iter()
+                .flat_map(|s| s.values.current_value.0.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+/// Pads or truncates `values` to one per label, if there are labels.
+fn fit_values(mut values: Vec<f32>, count: usize) -> TweenableVector {
+    if count > 0 {
+        values.resize(count, 0.0);
+    }
+    TweenableVector(values)
+}
+
+impl Element for ChartNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = self.animated_values();
+        self.build.update(time);
+        self.opacity.update(time);
+        for series in &mut self.series {
+            series.values.update(time);
+        }
+        self.animated_values() != before
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 {
+            let layered = opacity < 1.0;
+            if layered {
+                canvas.save_layer_alpha_f(rect, opacity);
+            }
+            let mut area = rect;
+            if self.show_legend {
+                let height = self.draw_legend(canvas, rect);
+                if height > 0.0 {
+                    area.top += height + self.font_size;
+                }
+            }
+            match self.kind {
+                ChartKind::Pie => self.draw_pie(canvas, area),
+                _ => self.draw_cartesian(canvas, area),
+            }
+            if layered {
+                canvas.restore();
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        let ease = parse_easing(easing);
+        match property {
+            "build" => self.build.add_segment(start, target, duration, ease),
+            "opacity" => self.opacity.add_segment(start, target, duration, ease),
+            _ => {}
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "build" => Some(self.build.current_value),
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "build" => self.build.current_value = value,
+            "opacity" => self.opacity.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,634 @@
+use crate::animation::{Animated, EasingType, TweenableVector};
+use crate::element::{Element, TextSpan};
+use crate::node::parse_easing;
+use crate::node::syntax::{tokenize, CodeTheme, Token};
+use crate::node::text::{line_paragraph, SpanDefaults};
+use crate::types::{CodeLanguage, Color};
+use skia_safe::textlayout::{FontCollection, Paragraph};
+use skia_safe::{Canvas, Paint, RRect, Rect};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use taffy::geometry::Size;
+use taffy::style::{AvailableSpace, Style};
+
+/// Monospace fonts tried in order when no family is set.
+const MONOSPACE_FAMILIES: [&str; 7] = [
+    "JetBrains Mono",
+    "Fira Code",
+    "SF Mono",
+    "Menlo",
+    "Consolas",
+    "DejaVu Sans Mono",
+    "monospace",
+];
+
+/// A line of highlighted code.
+#[derive(Debug, Clone)]
+pub struct CodeLine {
+    pub tokens: Vec<Token>,
+    /// Plain text of the line, which identifies it when diffing versions of the code.
+    pub text: String,
+}
+
+/// A line of one version of the code matched to a line of the next; `None` on either side
+/// marks a line that was added or removed.
+type LineMatch = (Option<usize>, Option<usize>);
+
+/// A line as drawn in one frame.
+struct LineDraw<'a> {
+    tokens: &'a [Token],
+    /// Snapshot and index of the line, which key its cached paragraph.
+    source: (usize, usize),
+    /// Characters shown, for typing.
+    chars: usize,
+    number: usize,
+    row: f32,
+    offset_x: f32,
+    alpha: f32,
+    focus: f32,
+}
+
+/// A cached paragraph: a whole line of a snapshot, or a line number.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ParagraphKey {
+    Line(usize, usize),
+    Number(usize),
+}
+
+/// Laid-out paragraphs reused across frames. Clones start empty.
+#[derive(Default)]
+struct ParagraphCache(Mutex<CachedParagraphs>);
+
+#[derive(Default)]
+struct CachedParagraphs {
+    /// Font size, family and theme the paragraphs were built with
+    style: Option<(f32, Option<String>, CodeTheme)>,
+    paragraphs: HashMap<ParagraphKey, Paragraph>,
+}
+
+impl Clone for ParagraphCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+// --- Code Node ---
+/// Draws highlighted source code with line numbers, focus dimming and animated edits.
+///
+/// `typed` (`0.0 - 1.0`) reveals the code character by character. [`CodeNode::animate_code`]
+/// moves to a new version of the code: unchanged lines slide to their new place while
+/// removed lines slide out and added lines slide in. [`CodeNode::focus_lines`] highlights
+/// lines and dims the rest.
+#[derive(Clone)]
+pub struct CodeNode {
+    pub style: Style,
+    pub language: CodeLanguage,
+    pub theme: CodeTheme,
+    pub font_collection: Arc<Mutex<FontCollection>>,
+    pub font_family: Option<String>,
+    pub font_size: f32,
+    /// Line spacing as a multiple of the font size.
+    pub line_height: f32,
+    pub show_line_numbers: bool,
+    /// Space between the edge of the background and the code.
+    pub padding: f32,
+    pub corner_radius: f32,
+    /// Fills the node with the theme's background.
+    pub show_background: bool,
+    /// Opacity of the other lines while some are focused.
+    pub dim_opacity: f32,
+    /// Every version of the code, in order.
+    pub snapshots: Vec<Vec<CodeLine>>,
+    /// Line matches between each version and the next.
+    matches: Vec<Vec<LineMatch>>,
+    /// Index into `snapshots`; fractions are transitions between two versions.
+    pub step: Animated<f32>,
+    pub typed: Animated<f32>,
+    /// Focus (`0.0 - 1.0`) of each line, by index.
+    pub focus: Animated<TweenableVector>,
+    pub opacity: Animated<f32>,
+    paragraphs: ParagraphCache,
+}
+
+impl fmt::Debug for CodeNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeNode")
+            .field("language", &self.language)
+            .field("snapshots", &self.snapshots.len())
+            .finish()
+    }
+}
+
+impl CodeNode {
+    pub fn new(
+        source: &str,
+        language: CodeLanguage,
+        font_collection: Arc<Mutex<FontCollection>>,
+    ) -> Self {
+        let lines = highlight(source, language);
+        let focus = TweenableVector(vec![0.0; lines.len()]);
+        Self {
+            style: Style::DEFAULT,
+            language,
+            theme: CodeTheme::default(),
+            font_collection,
+            font_family: None,
+            font_size: 20.0,
+            line_height: 1.5,
+            show_line_numbers: true,
+            padding: 24.0,
+            corner_radius: 12.0,
+            show_background: true,
+            dim_opacity: 0.35,
+            snapshots: vec![lines],
+            matches: Vec::new(),
+            step: Animated::new(0.0),
+            typed: Animated::new(1.0),
+            focus: Animated::new(focus),
+            opacity: Animated::new(1.0),
+            paragraphs: ParagraphCache::default(),
+        }
+    }
+
+    /// Queues a transition to a new version of the code after the previous ones.
+    ///
+    /// Holds the current version for `delay` seconds first.
+    pub fn animate_code(&mut self, source: &str, delay: f64, duration: f64, easing: EasingType) {
+        let lines = highlight(source, self.language);
+        let previous = self.snapshots.last().map(Vec::as_slice).unwrap_or_default();
+        self.matches.push(match_lines(previous, &lines));
+        self.snapshots.push(lines);
+        self.fit_focus();
+
+        let held = self.step.raw_keyframes.last().map_or(0.0, |k| k.0);
+        if delay > 0.0 {
+            self.step.add_keyframe(held, delay, EasingType::Linear);
+        }
+        self.step
+            .add_keyframe((self.snapshots.len() - 1) as f32, duration, easing);
+    }
+
+    /// Queues a focus change: `lines` (numbered from 1) are highlighted and the rest dimmed.
+    /// No lines clears the focus.
+    pub fn focus_lines(&mut self, lines: &[usize], delay: f64, duration: f64, easing: EasingType) {
+        let target = self.focus_target(lines);
+        if delay > 0.0 {
+            let held = self.focus.raw_keyframes.last().map(|k| k.0.clone());
+            self.focus
+                .add_keyframe(held.unwrap_or_default(), delay, EasingType::Linear);
+        }
+        self.focus.add_keyframe(target, duration, easing);
+    }
+
+    /// Focuses `lines` (numbered from 1) from the start, without animating.
+    pub fn set_focus(&mut self, lines: &[usize]) {
+        self.focus = Animated::new(self.focus_target(lines));
+    }
+
+    fn focus_target(&self, lines: &[usize]) -> TweenableVector {
+        let mut target = vec![0.0; self.max_lines()];
+        for &n in lines {
+            if let Some(focus) = n.checked_sub(1).and_then(|i| target.get_mut(i)) {
+                *focus = 1.0;
+            }
+        }
+        TweenableVector(target)
+    }
+
+    fn max_lines(&self) -> usize {
+        self.snapshots.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Pads every focus keyframe to the longest version of the code.
+    fn fit_focus(&mut self) {
+        let len = self.max_lines();
+        if self.focus.current_value.0.len() >= len {
+            return;
+        }
+        let pad = |mut v: TweenableVector| {
+            v.0.resize(len, 0.0);
+            v
+        };
+        let mut keyframes = std::mem::take(&mut self.focus.raw_keyframes).into_iter();
+        let Some((first, _, _)) = keyframes.next() else {
+            return;
+        };
+        let mut focus = Animated::new(pad(first));
+        let mut previous = 0.0;
+        for (value, time, easing) in keyframes {
+            focus.add_keyframe(pad(value), time - previous, easing);
+            previous = time;
+        }
+        focus.current_value = pad(self.focus.current_value.clone());
+        self.focus = focus;
+    }
+
+    /// Lays out `spans` on one line in the code font.
+    fn paragraph(&self, spans: &[TextSpan]) -> Paragraph {
+        let families: Vec<&str> = match &self.font_family {
+            Some(family) => vec![family.as_str()],
+            None => MONOSPACE_FAMILIES.to_vec(),
+        };
+        let defaults = SpanDefaults {
+            font_size: self.font_size,
+            color: self.theme.text,
+            font_families: &families,
+            tabular_figures: false,
+        };
+        line_paragraph(spans, &defaults, &self.font_collection)
+    }
+
+    /// Calls `f` with the paragraph for `key`, laying it out on first use. The cache is
+    /// cleared whenever the font or theme changes.
+    fn with_paragraph<R>(&self, key: ParagraphKey, f: impl FnOnce(&Paragraph) -> R) -> R {
+        let mut cache = self.paragraphs.0.lock().unwrap();
+        let style = (self.font_size, self.font_family.clone(), self.theme);
+        if cache.style.as_ref() != Some(&style) {
+            cache.paragraphs.clear();
+            cache.style = Some(style);
+        }
+        let paragraph = cache.paragraphs.entry(key).or_insert_with(|| match key {
+            ParagraphKey::Line(snapshot, index) => {
+                let tokens = &self.snapshots[snapshot][index].tokens;
+                self.paragraph(&token_spans(tokens, usize::MAX, &self.theme))
+            }
+            ParagraphKey::Number(number) => self.paragraph(&[TextSpan {
+                text: number.to_string(),
+                color: Some(self.theme.line_number),
+                ..Default::default()
+            }]),
+        });
+        f(paragraph)
+    }
+
+    /// Width of the line number column.
+    fn gutter_width(&self) -> f32 {
+        if !self.show_line_numbers {
+            return 0.0;
+        }
+        let digits = self.max_lines().max(1).to_string().len();
+        let digit = self.with_paragraph(ParagraphKey::Number(0), |p| p.max_intrinsic_width());
+        digits as f32 * digit + self.font_size
+    }
+
+    /// Lines of the current frame: one version of the code while typing, or two while
+    /// moving from one to the next.
+    fn frame_lines(&self) -> (Vec<LineDraw<'_>>, Option<(usize, usize)>) {
+        let last = self.snapshots.len().saturating_sub(1);
+        let step = self.step.current_value.clamp(0.0, last as f32);
+        let from = (step.floor() as usize).min(last);
+        let t = step - from as f32;
+        let focus = |i: usize| self.focus.current_value.0.get(i).copied().unwrap_or(0.0);
+        let mut draws = Vec::new();
+
+        if t <= 0.0 || from == last {
+            let lines = &self.snapshots[from];
+            let total: usize = lines.iter().map(|l| l.text.chars().count() + 1).sum();
+            let mut remaining =
+                (self.typed.current_value.clamp(0.0, 1.0) * total as f32).round() as usize;
+            let typing = self.typed.current_value < 1.0;
+            let mut cursor = None;
+            for (i, line) in lines.iter().enumerate() {
+                let len = line.text.chars().count();
+                let chars = len.min(remaining);
+                if typing && (remaining <= len || i == lines.len() - 1) {
+                    cursor = Some((draws.len(), chars));
+                }
+                draws.push(LineDraw {
+                    tokens: &line.tokens,
+                    source: (from, i),
+                    chars,
+                    number: i + 1,
+                    row: i as f32,
+                    offset_x: 0.0,
+                    alpha: 1.0,
+                    focus: focus(i),
+                });
+                if remaining <= len {
+                    break;
+                }
+                remaining -= len + 1;
+            }
+            return (draws, cursor);
+        }
+
+        let slide = self.font_size * 2.0;
+        let leaving = (t * 2.0).min(1.0);
+        let entering = (t * 2.0 - 1.0).clamp(0.0, 1.0);
+        for &(a, b) in &self.matches[from] {
+            let draw = match (a, b) {
+                (Some(a), Some(b)) => LineDraw {
+                    tokens: &self.snapshots[from + 1][b].tokens,
+                    source: (from + 1, b),
+                    chars: usize::MAX,
+                    number: if t < 0.5 { a + 1 } else { b + 1 },
+                    row: a as f32 + (b as f32 - a as f32) * t,
+                    offset_x: 0.0,
+                    alpha: 1.0,
+                    focus: focus(b),
+                },
+                (Some(a), None) => LineDraw {
+                    tokens: &self.snapshots[from][a].tokens,
+                    source: (from, a),
+                    chars: usize::MAX,
+                    number: a + 1,
+                    row: a as f32,
+                    offset_x: -slide * leaving,
+                    alpha: 1.0 - leaving,
+                    focus: focus(a),
+                },
+                (None, Some(b)) => LineDraw {
+                    tokens: &self.snapshots[from + 1][b].tokens,
+                    source: (from + 1, b),
+                    chars: usize::MAX,
+                    number: b + 1,
+                    row: b as f32,
+                    offset_x: slide * (1.0 - entering),
+                    alpha: entering,
+                    focus: focus(b),
+                },
+                (None, None) => continue,
+            };
+            draws.push(draw);
+        }
+        (draws, None)
+    }
+}
+
+/// Tokenizes `source` into highlighted lines.
+fn highlight(source: &str, language: CodeLanguage) -> Vec<CodeLine> {
+    tokenize(source, language)
+        .into_iter()
+        .map(|tokens| CodeLine {
+            text: tokens.iter().map(|t| t.text.as_str()).collect(),
+            tokens,
+        })
+        .collect()
+}
+
+/// Spans of the first `chars` characters of `tokens`, coloured by `theme`.
+fn token_spans(tokens: &[Token], chars: usize, theme: &CodeTheme) -> Vec<TextSpan> {
+    let mut remaining = chars;
+    tokens
+        .iter()
+        .map_while(|token| {
+            if remaining == 0 {
+                return None;
+            }
+            let end = token
+                .text
+                .char_indices()
+                .nth(remaining)
+                .map_or(token.text.len(), |(i, _)| i);
+            remaining -= token.text[..end].chars().count();
+            Some(TextSpan {
+                text: token.text[..end].to_string(),
+                color: Some(theme.color(token.kind)),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Matches the lines of two versions of the code by their longest common subsequence.
+fn match_lines(from: &[CodeLine], to: &[CodeLine]) -> Vec<LineMatch> {
+    let (n, m) = (from.len(), to.len());
+    // lengths[i][j]: common lines of from[i..] and to[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if from[i].text == to[j].text {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && from[i].text == to[j].text {
+            matches.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            matches.push((Some(i), None));
+            i += 1;
+        } else {
+            matches.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    matches
+}
+
+fn fade(color: Color, alpha: f32) -> Color {
+    Color {
+        a: color.a * alpha,
+        ..color
+    }
+}
+
+impl Element for CodeNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn needs_measure(&self) -> bool {
+        true
+    }
+
+    /// Fits the longest line and the most lines of every version, so the box doesn't change
+    /// size between versions.
+    fn measure(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        let longest = self
+            .snapshots
+            .iter()
+            .enumerate()
+            .flat_map(|(snapshot, lines)| (0..lines.len()).map(move |index| (snapshot, index)))
+            .map(|(snapshot, index)| {
+                self.with_paragraph(ParagraphKey::Line(snapshot, index), |p| {
+                    p.max_intrinsic_width()
+                })
+            })
+            .fold(0.0, f32::max);
+        let width = longest.ceil() + self.gutter_width() + self.padding * 2.0;
+        let height =
+            self.max_lines() as f32 * self.font_size * self.line_height + self.padding * 2.0;
+        Size {
+            width: known_dimensions.width.unwrap_or(width),
+            height: known_dimensions.height.unwrap_or(height),
+        }
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = (
+            self.step.current_value,
+            self.typed.current_value,
+            self.focus.current_value.0.clone(),
+            self.opacity.current_value,
+        );
+        self.step.update(time);
+        self.typed.update(time);
+        self.focus.update(time);
+        self.opacity.update(time);
+        before
+            != (
+                self.step.current_value,
+                self.typed.current_value,
+                self.focus.current_value.0.clone(),
+                self.opacity.current_value,
+            )
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 {
+            let layered = opacity < 1.0;
+            if layered {
+                canvas.save_layer_alpha_f(rect, opacity);
+            }
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            if self.show_background {
+                paint.set_color4f(self.theme.background.to_color4f(), None);
+                let rrect = RRect::new_rect_xy(&rect, self.corner_radius, self.corner_radius);
+                canvas.draw_rrect(rrect, &paint);
+            }
+
+            canvas.save();
+            canvas.clip_rect(rect, None, true);
+            let line_height = self.font_size * self.line_height;
+            let gutter = self.gutter_width();
+            let left = rect.left + self.padding;
+            let top = rect.top + self.padding;
+            let (lines, cursor) = self.frame_lines();
+            let focused = self
+                .focus
+                .current_value
+                .0
+                .iter()
+                .copied()
+                .fold(0.0, f32::max);
+
+            for (index, line) in lines.iter().enumerate() {
+                let y = top + line.row * line_height;
+                let dim = 1.0 - (1.0 - self.dim_opacity) * (focused - line.focus).max(0.0);
+                let alpha = line.alpha * dim;
+                if line.focus > 0.0 {
+                    let color = fade(self.theme.highlight, line.focus * line.alpha);
+                    paint.set_color4f(color.to_color4f(), None);
+                    canvas.draw_rect(
+                        Rect::from_xywh(rect.left, y, rect.width(), line_height),
+                        &paint,
+                    );
+                }
+
+                // Paragraphs are cached at full opacity; fading lines go through a layer
+                if alpha < 1.0 {
+                    canvas.save_layer_alpha_f(None, alpha);
+                }
+                let centre = |p: &Paragraph| y + (line_height - p.height()) / 2.0;
+
+                if self.show_line_numbers {
+                    self.with_paragraph(ParagraphKey::Number(line.number), |p| {
+                        let x = left + gutter - self.font_size - p.max_intrinsic_width();
+                        p.paint(canvas, (x, centre(p)));
+                    });
+                }
+
+                let x = left + gutter + line.offset_x;
+                let draw_code = |p: &Paragraph| {
+                    p.paint(canvas, (x, centre(p)));
+                    p.max_intrinsic_width()
+                };
+                let length: usize = line.tokens.iter().map(|t| t.text.chars().count()).sum();
+                let width = if line.chars >= length {
+                    let (snapshot, index) = line.source;
+                    self.with_paragraph(ParagraphKey::Line(snapshot, index), draw_code)
+                } else {
+                    // Cut the tokens down to the typed characters
+                    draw_code(&self.paragraph(&token_spans(line.tokens, line.chars, &self.theme)))
+                };
+                if alpha < 1.0 {
+                    canvas.restore();
+                }
+
+                if cursor.map(|(i, _)| i) == Some(index) {
+                    let caret = Rect::from_xywh(
+                        x + width,
+                        y + line_height * 0.1,
+                        (self.font_size * 0.1).max(1.0),
+                        line_height * 0.8,
+                    );
+                    paint.set_color4f(self.theme.cursor.to_color4f(), None);
+                    canvas.draw_rect(caret, &paint);
+                }
+            }
+            canvas.restore();
+
+            if layered {
+                canvas.restore();
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        let ease = parse_easing(easing);
+        match property {
+            "typed" => self.typed.add_segment(start, target, duration, ease),
+            "opacity" => self.opacity.add_segment(start, target, duration, ease),
+            _ => {}
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "typed" => Some(self.typed.current_value),
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "typed" => self.typed.current_value = value,
+            "opacity" => self.opacity.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,205 @@
+use crate::animation::Animated;
+use serde::{Deserialize, Serialize};
+
+/// How a number is written out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    /// Digits after the decimal point; by default 0, or the currency's usual digits.
+    pub decimals: Option<usize>,
+    /// Separates thousands (or lakhs and crores in Indian locales).
+    pub grouping: bool,
+    pub prefix: String,
+    pub suffix: String,
+    /// ISO 4217 code such as `"USD"` or `"EUR"`, written as its symbol.
+    pub currency: Option<String>,
+    /// Writes `0.25` as `25%`.
+    pub percent: bool,
+    /// Language tag such as `"en-US"`, `"de"` or `"fr-FR"`, which picks the separators and
+    /// where the currency symbol goes.
+    pub locale: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimals: None,
+            grouping: true,
+            prefix: String::new(),
+            suffix: String::new(),
+            currency: None,
+            percent: false,
+            locale: "en".to_string(),
+        }
+    }
+}
+
+/// Separators and symbol placement of a locale.
+struct LocaleRules {
+    group: &'static str,
+    decimal: &'static str,
+    /// Currency symbol before the number (otherwise after it, with a space).
+    symbol_first: bool,
+    /// Space between a leading currency symbol and the number.
+    symbol_space: bool,
+    /// Space between the number and `%`.
+    percent_space: bool,
+    /// Groups above the thousands in twos (1,00,00,000).
+    indian: bool,
+}
+
+const NBSP: &str = "\u{00A0}";
+
+impl LocaleRules {
+    fn for_tag(tag: &str) -> Self {
+        let tag = tag.to_ascii_lowercase().replace('_', "-");
+        let language = tag.split('-').next().unwrap_or_default();
+        let rules = |group, decimal, symbol_first, symbol_space, percent_space| Self {
+            group,
+            decimal,
+            symbol_first,
+            symbol_space,
+            percent_space,
+            indian: false,
+        };
+        match (tag.as_str(), language) {
+            ("de-ch", _) => rules("’", ".", true, true, false),
+            ("pt-br", _) => rules(".", ",", true, true, false),
+            (_, "en" | "hi") if tag.ends_with("-in") || language == "hi" => Self {
+                indian: true,
+                ..rules(",", ".", true, false, false)
+            },
+            (_, "de" | "es" | "da" | "id") => rules(".", ",", false, false, true),
+            (_, "it" | "pt" | "tr") => rules(".", ",", false, false, false),
+            (_, "nl") => rules(".", ",", true, true, false),
+            (_, "fr") => rules("\u{202F}", ",", false, false, true),
+            (_, "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no") => {
+                rules(NBSP, ",", false, false, true)
+            }
+            _ => rules(",", ".", true, false, false),
+        }
+    }
+}
+
+/// Symbol of a currency code, or the code itself for unknown currencies.
+fn currency_symbol(code: &str) -> &str {
+    match code {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" | "CNY" => "¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        "BRL" => "R$",
+        "RUB" => "₽",
+        "TRY" => "₺",
+        "PLN" => "zł",
+        "CAD" => "CA$",
+        "AUD" => "A$",
+        "NZD" => "NZ$",
+        "HKD" => "HK$",
+        "SGD" => "S$",
+        "MXN" => "MX$",
+        "SEK" | "NOK" | "DKK" => "kr",
+        "ZAR" => "R",
+        _ => code,
+    }
+}
+
+/// Usual number of decimal places of a currency.
+fn currency_decimals(code: &str) -> usize {
+    match code {
+        "JPY" | "KRW" | "VND" | "CLP" | "ISK" | "HUF" => 0,
+        _ => 2,
+    }
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: f64) -> String {
+        let rules = LocaleRules::for_tag(&self.locale);
+        let currency = self.currency.as_deref().map(str::to_ascii_uppercase);
+        let value = if self.percent { value * 100.0 } else { value };
+        let decimals = self
+            .decimals
+            .unwrap_or_else(|| currency.as_deref().map_or(0, currency_decimals));
+
+        let fixed = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        // Values that round to zero are written without a sign
+        let negative = value < 0.0 && fixed.bytes().any(|b| b.is_ascii_digit() && b != b'0');
+
+        let mut number = if self.grouping {
+            group_digits(integer, &rules)
+        } else {
+            integer.to_string()
+        };
+        if !fraction.is_empty() {
+            number.push_str(rules.decimal);
+            number.push_str(fraction);
+        }
+        if self.percent {
+            if rules.percent_space {
+                number.push_str(NBSP);
+            }
+            number.push('%');
+        }
+        if let Some(code) = &currency {
+            let symbol = currency_symbol(code);
+            number = if rules.symbol_first {
+                // Codes and letter symbols ("CHF", "kr") need a space before the digits
+                let spaced = rules.symbol_space || symbol.ends_with(|c: char| c.is_alphabetic());
+                format!("{}{}{}", symbol, if spaced { NBSP } else { "" }, number)
+            } else {
+                format!("{}{}{}", number, NBSP, symbol)
+            };
+        }
+
+        format!(
+            "{}{}{}{}",
+            self.prefix,
+            if negative { "-" } else { "" },
+            number,
+            self.suffix
+        )
+    }
+}
+
+/// Inserts the locale's group separator into a string of digits.
+fn group_digits(digits: &str, rules: &LocaleRules) -> String {
+    let len = digits.len();
+    let mut out = String::with_capacity(len + len / 2);
+    for (i, digit) in digits.chars().enumerate() {
+        let remaining = len - i;
+        let boundary = if rules.indian {
+            remaining >= 3 && (remaining - 3) % 2 == 0
+        } else {
+            remaining % 3 == 0
+        };
+        if i > 0 && boundary {
+            out.push_str(rules.group);
+        }
+        out.push(digit);
+    }
+    out
+}
+
+/// An animated number written as a text node's content.
+#[derive(Debug, Clone)]
+pub struct TextCounter {
+    pub value: Animated<f64>,
+    pub format: NumberFormat,
+}
+
+impl TextCounter {
+    pub fn new(value: f64, format: NumberFormat) -> Self {
+        Self {
+            value: Animated::new(value),
+            format,
+        }
+    }
+
+    /// The current value, formatted.
+    pub fn text(&self) -> String {
+        self.format.format(self.value.current_value)
+    }
+}
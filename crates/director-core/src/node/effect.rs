@@ -108,6 +108,7 @@ pub fn build_effect_filter(
     resolution: (f32, f32),
     time: f32,
 ) -> Option<skia_safe::ImageFilter> {
+    let _span = tracing::trace_span!("build_effect_filter", effects = effects.len()).entered();
     let mut current_filter = None;
     for effect in effects {
         match effect {
@@ -297,11 +298,15 @@ half4 main(float2 pos) {
 /// A node that applies visual effects (filters) to its children.
 ///
 /// It does not render content itself but wraps its children in a layer with applied image filters.
+/// As an adjustment layer, it instead filters whatever its parent has drawn beneath it,
+/// within its own bounds (see `systems::renderer::render_recursive`).
 pub struct EffectNode {
     pub effects: Vec<EffectType>,
     pub style: Style,
     pub shader_cache: Arc<Mutex<HashMap<String, RuntimeEffect>>>,
     pub current_time: f32,
+    /// Apply the effects to the backdrop instead of the children.
+    pub adjustment: bool,
 }
 
 impl Clone for EffectNode {
@@ -311,6 +316,7 @@ impl Clone for EffectNode {
             style: self.style.clone(),
             shader_cache: self.shader_cache.clone(),
             current_time: self.current_time,
+            adjustment: self.adjustment,
         }
     }
 }
@@ -319,10 +325,23 @@ impl fmt::Debug for EffectNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("EffectNode")
             .field("effects", &self.effects)
+            .field("adjustment", &self.adjustment)
             .finish()
     }
 }
 
+impl EffectNode {
+    /// Builds the image filter for the effect chain at the current time.
+    pub fn image_filter(&self, rect: Rect) -> Option<skia_safe::ImageFilter> {
+        build_effect_filter(
+            &self.effects,
+            Some(&self.shader_cache),
+            (rect.width(), rect.height()),
+            self.current_time,
+        )
+    }
+}
+
 impl Element for EffectNode {
     fn as_any(&self) -> &dyn Any {
         self
@@ -350,13 +369,13 @@ impl Element for EffectNode {
         opacity: f32,
         draw_children: &mut dyn FnMut(&Canvas),
     ) -> Result<(), RenderError> {
-        let resolution = (rect.width(), rect.height());
-        let filter = build_effect_filter(
-            &self.effects,
-            Some(&self.shader_cache),
-            resolution,
-            self.current_time,
-        );
+        // The renderer applies adjustment layers to the backdrop itself
+        if self.adjustment {
+            draw_children(canvas);
+            return Ok(());
+        }
+
+        let filter = self.image_filter(rect);
 
         let mut paint = Paint::default();
         paint.set_alpha_f(opacity);
@@ -391,6 +410,29 @@ impl Element for EffectNode {
         }
     }
 
+    fn get_property(&self, property: &str) -> Option<f32> {
+        self.effects.iter().find_map(|effect| match effect {
+            EffectType::RuntimeShader { uniforms, .. } => match uniforms.get(property) {
+                Some(ShaderUniform::Float(a)) => Some(a.current_value),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        let mut found = false;
+        for effect in &mut self.effects {
+            if let EffectType::RuntimeShader { uniforms, .. } = effect {
+                if let Some(ShaderUniform::Float(a)) = uniforms.get_mut(property) {
+                    a.current_value = value;
+                    found = true;
+                }
+            }
+        }
+        found
+    }
+
     fn animate_property_spring(
         &mut self,
         property: &str,
@@ -46,8 +46,9 @@ impl Element for ImageNode {
     }
 
     fn update(&mut self, time: f64) -> bool {
+        let before = self.opacity.current_value;
         self.opacity.update(time);
-        true
+        self.opacity.current_value != before
     }
 
     fn render(
@@ -101,6 +102,22 @@ impl Element for ImageNode {
         }
     }
 
+    fn get_property(&self, property: &str) -> Option<f32> {
+        (property == "opacity").then_some(self.opacity.current_value)
+    }
+
+    fn property_at(&self, property: &str, time: f64) -> Option<f32> {
+        (property == "opacity").then(|| self.opacity.value_at(time))
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        if property == "opacity" {
+            self.opacity.current_value = value;
+            return true;
+        }
+        false
+    }
+
     fn animate_property_spring(
         &mut self,
         property: &str,
@@ -294,4 +294,21 @@ impl Element for LottieNode {
             self.frame.add_segment(start, target, duration, ease);
         }
     }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            "frame" => Some(self.frame.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "opacity" => self.opacity.current_value = value,
+            "frame" => self.frame.current_value = value,
+            _ => return false,
+        }
+        true
+    }
 }
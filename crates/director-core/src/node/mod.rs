@@ -5,6 +5,9 @@ use skia_safe::Rect;
 pub mod text;
 pub use text::TextNode;
 
+pub mod counter;
+pub use counter::{NumberFormat, TextCounter};
+
 pub mod text_animator;
 pub use text_animator::{parse_text_anim_property, GlyphAnimState, TextAnimProperty, TextAnimator};
 
@@ -23,6 +26,30 @@ pub use image_node::ImageNode;
 pub mod video_node;
 pub use video_node::{VideoNode, VideoSource};
 
+pub mod shape;
+pub use shape::{trim_path, PathBoolean, PathMorph, ShapeFill, ShapeGeometry, ShapeNode};
+
+pub mod morph;
+pub use morph::morph_paths;
+
+pub mod code;
+pub use code::{CodeLine, CodeNode};
+
+pub mod syntax;
+pub use syntax::{tokenize, CodeTheme, Token, TokenKind};
+
+pub mod chart;
+pub use chart::{ChartNode, ChartSeries, DEFAULT_CHART_COLORS};
+
+pub mod progress;
+pub use progress::{ProgressNode, ProgressSource};
+
+pub mod visualizer;
+pub use visualizer::{SpectrumNode, WaveformNode};
+
+pub mod particles;
+pub use particles::{unit_sprite_path, LifeCurve, ParticleEmitter, ParticleNode, ParticleSprite};
+
 pub mod composition;
 pub use composition::CompositionNode;
 
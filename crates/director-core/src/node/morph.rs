@@ -0,0 +1,119 @@
+use skia_safe::{ContourMeasure, ContourMeasureIter, Path, Point};
+
+/// Resampled points per contour are one every few pixels, within these bounds.
+const MIN_SAMPLES: usize = 16;
+const MAX_SAMPLES: usize = 256;
+const SAMPLE_SPACING: f32 = 4.0;
+
+/// Blends two paths at `t` (`0.0` is `from`, `1.0` is `to`).
+///
+/// Contours are paired in order and resampled to the same number of points. Closed pairs
+/// are given the same winding and their start points rotated to line up; open pairs are
+/// reversed if that brings their ends closer. A contour without a partner shrinks into (or
+/// grows from) its own centre. Values of `t` outside `0.0 - 1.0` extrapolate, so
+/// overshooting easings work.
+pub fn morph_paths(from: &Path, to: &Path, t: f32) -> Path {
+    let from_contours: Vec<ContourMeasure> = ContourMeasureIter::new(from, false, None).collect();
+    let to_contours: Vec<ContourMeasure> = ContourMeasureIter::new(to, false, None).collect();
+
+    let mut out = Path::new();
+    for i in 0..from_contours.len().max(to_contours.len()) {
+        let (a, b, closed) = match (from_contours.get(i), to_contours.get(i)) {
+            (Some(a), Some(b)) => {
+                let closed = a.is_closed() && b.is_closed();
+                let count = sample_count(a.length().max(b.length()));
+                let a = sample(a, count, closed);
+                let mut b = sample(b, count, closed);
+                align(&a, &mut b, closed);
+                (a, b, closed)
+            }
+            (Some(a), None) => {
+                let points = sample(a, sample_count(a.length()), a.is_closed());
+                let center = vec![centroid(&points); points.len()];
+                (points, center, a.is_closed())
+            }
+            (None, Some(b)) => {
+                let points = sample(b, sample_count(b.length()), b.is_closed());
+                let center = vec![centroid(&points); points.len()];
+                (center, points, b.is_closed())
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let mut points = a.iter().zip(&b).map(|(a, b)| *a + (*b - *a) * t);
+        if let Some(first) = points.next() {
+            out.move_to(first);
+            for p in points {
+                out.line_to(p);
+            }
+            if closed {
+                out.close();
+            }
+        }
+    }
+    out
+}
+
+fn sample_count(length: f32) -> usize {
+    ((length / SAMPLE_SPACING).ceil() as usize).clamp(MIN_SAMPLES, MAX_SAMPLES)
+}
+
+/// Points at equal distances along a contour. Closed contours skip the end point, which
+/// repeats the start.
+fn sample(contour: &ContourMeasure, count: usize, closed: bool) -> Vec<Point> {
+    let length = contour.length();
+    let step = if closed {
+        length / count as f32
+    } else {
+        length / (count - 1) as f32
+    };
+    (0..count)
+        .map(|i| {
+            contour
+                .pos_tan(i as f32 * step)
+                .map(|(p, _)| p)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Reorders `b` to follow `a` as closely as possible.
+fn align(a: &[Point], b: &mut [Point], closed: bool) {
+    let dist = |p: Point, q: Point| {
+        let d = p - q;
+        d.x * d.x + d.y * d.y
+    };
+
+    if !closed {
+        let (a0, a1) = (a[0], a[a.len() - 1]);
+        let (b0, b1) = (b[0], b[b.len() - 1]);
+        if dist(a0, b1) + dist(a1, b0) < dist(a0, b0) + dist(a1, b1) {
+            b.reverse();
+        }
+        return;
+    }
+
+    if signed_area(a).signum() != signed_area(b).signum() {
+        b.reverse();
+    }
+    let n = b.len();
+    let cost = |r: usize| (0..n).map(|i| dist(a[i], b[(i + r) % n])).sum::<f32>();
+    let best = (0..n)
+        .map(|r| (r, cost(r)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .map_or(0, |(r, _)| r);
+    b.rotate_left(best);
+}
+
+fn signed_area(points: &[Point]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].cross(points[(i + 1) % n]))
+        .sum::<f32>()
+        / 2.0
+}
+
+fn centroid(points: &[Point]) -> Point {
+    let sum = points.iter().fold(Point::default(), |acc, p| acc + *p);
+    sum / points.len().max(1) as f32
+}
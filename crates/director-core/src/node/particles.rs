@@ -0,0 +1,465 @@
+use crate::animation::Animated;
+use crate::element::Element;
+use crate::node::parse_easing;
+use crate::scripting::rng::Noise;
+use crate::types::Color;
+use keyframe::CanTween;
+use skia_safe::{Canvas, ContourMeasure, ContourMeasureIter, Image, Paint, Path, Point, Rect};
+use std::any::Any;
+use taffy::style::Style;
+
+/// Upper bound on particles drawn per frame, whatever the rate and lifetime.
+const MAX_PARTICLES: usize = 20_000;
+
+/// Where new particles appear, in node-local pixels.
+#[derive(Debug, Clone)]
+pub enum ParticleEmitter {
+    /// A single point; `None` is the centre of the layout box.
+    Point(Option<Point>),
+    /// Anywhere on the segment between two points.
+    Line(Point, Point),
+    /// Anywhere inside the rectangle; `None` is the layout box.
+    Rect(Option<Rect>),
+    /// Anywhere along the outline of a path.
+    Shape(Path),
+}
+
+/// What each particle looks like.
+#[derive(Debug, Clone)]
+pub enum ParticleSprite {
+    Circle,
+    Square,
+    /// Outlines fitted to a unit box around the origin; each particle picks one at random
+    /// (e.g. the glyphs of a string).
+    Paths(Vec<Path>),
+    /// A bitmap drawn `size` pixels wide. Only the opacity curve applies, not `color`.
+    Image(Image),
+}
+
+/// Piecewise-linear value over a particle's life (`0.0` at birth, `1.0` at death).
+#[derive(Debug, Clone)]
+pub struct LifeCurve<T> {
+    /// `(life, value)` keys sorted by life.
+    pub keys: Vec<(f32, T)>,
+}
+
+impl<T: CanTween + Clone + Default> LifeCurve<T> {
+    /// A curve that stays at `value`.
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    /// Spreads `values` evenly over the particle's life.
+    pub fn even(values: Vec<T>) -> Self {
+        let step = 1.0 / (values.len().max(2) - 1) as f32;
+        Self {
+            keys: values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (i as f32 * step, v))
+                .collect(),
+        }
+    }
+
+    pub fn sample(&self, life: f32) -> T {
+        let Some(first) = self.keys.first() else {
+            return T::default();
+        };
+        if life <= first.0 {
+            return first.1.clone();
+        }
+        for pair in self.keys.windows(2) {
+            let ((l0, v0), (l1, v1)) = (&pair[0], &pair[1]);
+            if life <= *l1 {
+                let t = if l1 > l0 {
+                    (life - l0) / (l1 - l0)
+                } else {
+                    1.0
+                };
+                return T::ease(v0.clone(), v1.clone(), t);
+            }
+        }
+        self.keys[self.keys.len() - 1].1.clone()
+    }
+}
+
+/// A particle at one point in time.
+struct Particle {
+    position: Point,
+    rotation: f32,
+    size: f32,
+    color: Color,
+    alpha: f32,
+    sprite: usize,
+}
+
+// --- Particle Node ---
+/// Emits and draws particles (confetti, sparks, snow, ...).
+///
+/// Nothing is simulated incrementally: every particle's state is computed from the seed,
+/// its birth time and the current time, so any frame renders identically on its own.
+/// Motion is ballistic with linear drag, plus a noise displacement for turbulence.
+/// `(min, max)` pairs are ranges each particle picks a random value from.
+#[derive(Debug, Clone)]
+pub struct ParticleNode {
+    pub style: Style,
+    pub seed: u64,
+    pub emitter: ParticleEmitter,
+    /// Particles emitted per second.
+    pub rate: f32,
+    /// Extra particles emitted at once, as `(time, count)`.
+    pub bursts: Vec<(f64, u32)>,
+    /// How long the steady `rate` emits for; `None` emits for the node's whole life.
+    pub emit_duration: Option<f64>,
+    /// Seconds each particle lives.
+    pub lifetime: (f32, f32),
+    /// Initial speed in pixels per second.
+    pub speed: (f32, f32),
+    /// Emission direction in degrees (0 = right, -90 = up).
+    pub direction: f32,
+    /// Width of the emission cone in degrees (360 = all directions).
+    pub spread: f32,
+    /// Acceleration in pixels per second squared.
+    pub gravity: (f32, f32),
+    /// Fraction of velocity lost per second.
+    pub drag: f32,
+    /// Maximum turbulence displacement in pixels.
+    pub turbulence: Animated<f32>,
+    /// Size in pixels of the turbulence swirls.
+    pub turbulence_scale: f32,
+    /// Rotation speed in degrees per second.
+    pub spin: (f32, f32),
+    pub sprite: ParticleSprite,
+    /// Particle size in pixels over its life.
+    pub size: LifeCurve<f32>,
+    /// Random factor applied to each particle's size.
+    pub size_scale: (f32, f32),
+    pub color: LifeCurve<Color>,
+    /// Particle opacity over its life.
+    pub alpha: LifeCurve<f32>,
+    pub opacity: Animated<f32>,
+    noise: Noise,
+    time: f64,
+}
+
+impl ParticleNode {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            style: Style::DEFAULT,
+            seed,
+            emitter: ParticleEmitter::Point(None),
+            rate: 20.0,
+            bursts: Vec::new(),
+            emit_duration: None,
+            lifetime: (2.0, 2.0),
+            speed: (50.0, 150.0),
+            direction: -90.0,
+            spread: 60.0,
+            gravity: (0.0, 0.0),
+            drag: 0.0,
+            turbulence: Animated::new(0.0),
+            turbulence_scale: 200.0,
+            spin: (0.0, 0.0),
+            sprite: ParticleSprite::Circle,
+            size: LifeCurve::constant(8.0),
+            size_scale: (1.0, 1.0),
+            color: LifeCurve::constant(Color::WHITE),
+            alpha: LifeCurve::even(vec![1.0, 0.0]),
+            opacity: Animated::new(1.0),
+            noise: Noise::new(seed),
+            time: 0.0,
+        }
+    }
+
+    /// Changes the seed, which reshuffles every particle.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.noise = Noise::new(seed);
+    }
+
+    /// Whether particles can be alive at `time`.
+    fn is_active(&self, time: f64) -> bool {
+        let last_birth = self
+            .bursts
+            .iter()
+            .map(|(t, _)| *t)
+            .chain(self.emit_duration.filter(|_| self.rate > 0.0))
+            .fold(0.0, f64::max);
+        let lifetime = self.lifetime.0.max(self.lifetime.1) as f64;
+        (self.rate > 0.0 && self.emit_duration.is_none()) || time < last_birth + lifetime
+    }
+
+    /// Every particle alive at `time`, in a fixed order.
+    fn particles(&self, bounds: Rect, time: f64) -> Vec<Particle> {
+        let mut particles = Vec::new();
+        let contours: Vec<ContourMeasure> = match &self.emitter {
+            ParticleEmitter::Shape(path) => ContourMeasureIter::new(path, false, None).collect(),
+            _ => Vec::new(),
+        };
+        let emit = |u, v| self.emit_point(bounds, &contours, u, v);
+        let max_lifetime = self.lifetime.0.max(self.lifetime.1) as f64;
+
+        if self.rate > 0.0 {
+            let rate = self.rate as f64;
+            let first = ((time - max_lifetime) * rate).ceil().max(0.0) as u64;
+            let mut last = (time * rate).floor();
+            if let Some(duration) = self.emit_duration {
+                last = last.min((duration * rate).ceil() - 1.0);
+            }
+            if last >= 0.0 {
+                let last = (last as u64).min(first + MAX_PARTICLES as u64);
+                for index in first..=last {
+                    let birth = index as f64 / rate;
+                    self.push_particle(&mut particles, &emit, index, birth, time);
+                }
+            }
+        }
+
+        for (burst, &(birth, count)) in self.bursts.iter().enumerate() {
+            if time < birth || time >= birth + max_lifetime {
+                continue;
+            }
+            for i in 0..count as u64 {
+                // Bursts use ids far above any steady-stream index
+                let id = (1 << 48) | ((burst as u64) << 24) | i;
+                self.push_particle(&mut particles, &emit, id, birth, time);
+            }
+        }
+
+        particles.truncate(MAX_PARTICLES);
+        particles
+    }
+
+    fn push_particle(
+        &self,
+        out: &mut Vec<Particle>,
+        emit: &dyn Fn(f32, f32) -> Point,
+        id: u64,
+        birth: f64,
+        time: f64,
+    ) {
+        let random = |channel: u64| unit_random(self.seed, id, channel);
+        let range = |(min, max): (f32, f32), channel: u64| min + (max - min) * random(channel);
+
+        let lifetime = range(self.lifetime, 0);
+        let age = (time - birth) as f32;
+        if lifetime <= 0.0 || age < 0.0 || age >= lifetime {
+            return;
+        }
+        let life = age / lifetime;
+
+        let origin = emit(random(1), random(2));
+        let angle = (self.direction + (random(3) - 0.5) * self.spread).to_radians();
+        let speed = range(self.speed, 4);
+        let (vx, vy) = (angle.cos() * speed, angle.sin() * speed);
+        let (gx, gy) = self.gravity;
+
+        // Closed form of dv/dt = g - drag * v
+        let (x, y) = if self.drag > 0.0 {
+            let k = self.drag;
+            let decay = (1.0 - (-k * age).exp()) / k;
+            (
+                origin.x + vx * decay + gx / k * (age - decay),
+                origin.y + vy * decay + gy / k * (age - decay),
+            )
+        } else {
+            (
+                origin.x + vx * age + 0.5 * gx * age * age,
+                origin.y + vy * age + 0.5 * gy * age * age,
+            )
+        };
+
+        let mut position = Point::new(x, y);
+        let turbulence = self.turbulence.current_value;
+        if turbulence != 0.0 {
+            let scale = self.turbulence_scale.max(1.0) as f64;
+            let (nx, ny, nt) = (x as f64 / scale, y as f64 / scale, time * 0.5);
+            let ramp = age.min(1.0) * turbulence;
+            position.x += self.noise.sample(nx, ny, nt) as f32 * ramp;
+            position.y += self.noise.sample(nx + 31.4, ny + 47.2, nt) as f32 * ramp;
+        }
+
+        let sprites = match &self.sprite {
+            ParticleSprite::Paths(paths) => paths.len().max(1),
+            _ => 1,
+        };
+        out.push(Particle {
+            position,
+            rotation: random(5) * 360.0 + range(self.spin, 6) * age,
+            size: self.size.sample(life) * range(self.size_scale, 7),
+            color: self.color.sample(life),
+            alpha: self.alpha.sample(life).clamp(0.0, 1.0),
+            sprite: ((random(8) * sprites as f32) as usize).min(sprites - 1),
+        });
+    }
+
+    fn emit_point(&self, bounds: Rect, contours: &[ContourMeasure], u: f32, v: f32) -> Point {
+        match &self.emitter {
+            ParticleEmitter::Point(p) => p.unwrap_or_else(|| bounds.center()),
+            ParticleEmitter::Line(a, b) => *a + (*b - *a) * u,
+            ParticleEmitter::Rect(rect) => {
+                let r = rect.unwrap_or(bounds);
+                Point::new(r.left + r.width() * u, r.top + r.height() * v)
+            }
+            ParticleEmitter::Shape(path) => {
+                let total: f32 = contours.iter().map(|c| c.length()).sum();
+                let mut distance = u * total;
+                for contour in contours {
+                    if distance <= contour.length() {
+                        if let Some((p, _)) = contour.pos_tan(distance) {
+                            return p;
+                        }
+                    }
+                    distance -= contour.length();
+                }
+                path.bounds().center()
+            }
+        }
+    }
+
+    fn draw_particle(&self, canvas: &Canvas, particle: &Particle, opacity: f32) {
+        let alpha = particle.alpha * opacity;
+        if alpha <= 0.0 || particle.size <= 0.0 {
+            return;
+        }
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        let mut color = particle.color;
+        color.a *= alpha;
+        paint.set_color4f(color.to_color4f(), None);
+
+        let size = particle.size;
+        canvas.save();
+        canvas.translate(particle.position);
+        canvas.rotate(particle.rotation, None);
+        match &self.sprite {
+            ParticleSprite::Circle => {
+                canvas.draw_circle((0.0, 0.0), size / 2.0, &paint);
+            }
+            ParticleSprite::Square => {
+                canvas.draw_rect(
+                    Rect::from_xywh(-size / 2.0, -size / 2.0, size, size),
+                    &paint,
+                );
+            }
+            ParticleSprite::Paths(paths) => {
+                if let Some(path) = paths.get(particle.sprite) {
+                    canvas.scale((size, size));
+                    canvas.draw_path(path, &paint);
+                }
+            }
+            ParticleSprite::Image(image) => {
+                let height = size * image.height() as f32 / image.width().max(1) as f32;
+                let mut paint = Paint::default();
+                paint.set_alpha_f(alpha);
+                canvas.draw_image_rect(
+                    image,
+                    None,
+                    Rect::from_xywh(-size / 2.0, -height / 2.0, size, height),
+                    &paint,
+                );
+            }
+        }
+        canvas.restore();
+    }
+}
+
+/// Scales and centres `path` so its larger side spans `-0.5..0.5` around the origin.
+pub fn unit_sprite_path(path: &Path) -> Path {
+    let bounds = path.compute_tight_bounds();
+    let extent = bounds.width().max(bounds.height());
+    if extent <= 0.0 {
+        return path.clone();
+    }
+    let mut matrix = skia_safe::Matrix::scale((1.0 / extent, 1.0 / extent));
+    matrix.pre_translate((-bounds.center_x(), -bounds.center_y()));
+    path.with_transform(&matrix)
+}
+
+/// Uniform value in `[0, 1)` fixed by seed, particle and channel (SplitMix64 finaliser).
+fn unit_random(seed: u64, id: u64, channel: u64) -> f32 {
+    let mut z =
+        seed ^ id.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ channel.wrapping_mul(0xD1B5_4A32_D192_ED03);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 24) as f32
+}
+
+impl Element for ParticleNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = (self.opacity.current_value, self.turbulence.current_value);
+        self.opacity.update(time);
+        self.turbulence.update(time);
+        let moving = time != self.time && (self.is_active(self.time) || self.is_active(time));
+        self.time = time;
+        moving || (self.opacity.current_value, self.turbulence.current_value) != before
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 {
+            for particle in self.particles(rect, self.time) {
+                self.draw_particle(canvas, &particle, opacity);
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        let ease = parse_easing(easing);
+        match property {
+            "opacity" => self.opacity.add_segment(start, target, duration, ease),
+            "turbulence" => self.turbulence.add_segment(start, target, duration, ease),
+            _ => {}
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            "turbulence" => Some(self.turbulence.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "opacity" => self.opacity.current_value = value,
+            "turbulence" => self.turbulence.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
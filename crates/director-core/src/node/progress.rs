@@ -0,0 +1,279 @@
+use crate::animation::Animated;
+use crate::element::{Element, FrameInfo, FrameStage};
+use crate::node::parse_easing;
+use crate::types::{Color, GradientConfig};
+use skia_safe::{Canvas, ClipOp, Paint, Point, RRect, Rect, Shader, TileMode};
+use std::any::Any;
+use taffy::style::Style;
+
+/// Clock a progress node follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressSource {
+    /// Time since the start of the node's scene, over the scene's duration.
+    #[default]
+    Scene,
+    /// Time on the movie's timeline, over the end of its last scene.
+    Movie,
+    /// Playback position of a mixer track, over the length that plays.
+    Audio(usize),
+}
+
+// --- Progress Node ---
+/// Draws a progress bar that fills as its clock runs, with optional chapter segments and
+/// a playhead knob.
+///
+/// Its frame hook sets the clock every frame with [`ProgressNode::set_clock`], so the bar
+/// tracks playback without any animation. `start` and `end` pick the part of the clock the
+/// bar spans, and `markers` split it into chapters at times on the same clock.
+#[derive(Debug, Clone)]
+pub struct ProgressNode {
+    pub style: Style,
+    pub source: ProgressSource,
+    /// Clock time (seconds) at which the bar starts filling.
+    pub start: f64,
+    /// Clock time at which the bar is full; by default the clock's length.
+    pub end: Option<f64>,
+    /// Clock times splitting the bar into chapter segments.
+    pub markers: Vec<f64>,
+    /// Space between chapter segments in pixels.
+    pub segment_gap: f32,
+    pub background: Color,
+    pub fill: Color,
+    /// Replaces `fill`; spans the whole bar, so it's revealed as the bar fills.
+    pub fill_gradient: Option<GradientConfig>,
+    /// Corner radius of the bar (and each segment); `None` rounds the ends fully.
+    pub corner_radius: Option<f32>,
+    /// Radius of the playhead knob; 0 hides it.
+    pub knob_radius: f32,
+    pub knob_color: Color,
+    /// Clock reading and length at the current frame.
+    pub time: f64,
+    pub duration: f64,
+    pub opacity: Animated<f32>,
+}
+
+impl ProgressNode {
+    pub fn new(source: ProgressSource) -> Self {
+        Self {
+            style: Style::DEFAULT,
+            source,
+            start: 0.0,
+            end: None,
+            markers: Vec::new(),
+            segment_gap: 4.0,
+            background: Color::new(1.0, 1.0, 1.0, 0.25),
+            fill: Color::WHITE,
+            fill_gradient: None,
+            corner_radius: None,
+            knob_radius: 0.0,
+            knob_color: Color::WHITE,
+            time: 0.0,
+            duration: 0.0,
+            opacity: Animated::new(1.0),
+        }
+    }
+
+    /// Sets the clock reading and length for this frame. Returns whether they changed.
+    pub fn set_clock(&mut self, time: f64, duration: f64) -> bool {
+        let changed = time != self.time || duration != self.duration;
+        self.time = time;
+        self.duration = duration;
+        changed
+    }
+
+    /// Position of clock time `time` along the bar (`0.0 - 1.0`).
+    pub fn fraction(&self, time: f64) -> f32 {
+        let span = self.end.unwrap_or(self.duration) - self.start;
+        if span > 0.0 {
+            ((time - self.start) / span).clamp(0.0, 1.0) as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// How full the bar is (`0.0 - 1.0`).
+    pub fn progress(&self) -> f32 {
+        self.fraction(self.time)
+    }
+
+    /// Chapter segments as `(from, to)` fractions of the bar.
+    pub fn segments(&self) -> Vec<(f32, f32)> {
+        let mut cuts: Vec<f32> = self
+            .markers
+            .iter()
+            .map(|&m| self.fraction(m))
+            .filter(|&f| f > 0.0 && f < 1.0)
+            .collect();
+        cuts.sort_by(f32::total_cmp);
+        cuts.dedup();
+        let mut edges = vec![0.0];
+        edges.extend(cuts);
+        edges.push(1.0);
+        edges.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    fn fill_paint(&self, bar: Rect) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color4f(self.fill.to_color4f(), None);
+        if let Some(gradient) = &self.fill_gradient {
+            let at = |(x, y): (f32, f32)| {
+                Point::new(bar.left + x * bar.width(), bar.top + y * bar.height())
+            };
+            let colors: Vec<skia_safe::Color> =
+                gradient.colors.iter().map(|c| c.to_skia()).collect();
+            if let Some(shader) = Shader::linear_gradient(
+                (at(gradient.start), at(gradient.end)),
+                colors.as_slice(),
+                gradient.positions.as_deref(),
+                TileMode::Clamp,
+                None,
+                None,
+            ) {
+                paint.set_color4f(Color::WHITE.to_color4f(), None);
+                paint.set_shader(shader);
+            }
+        }
+        paint
+    }
+}
+
+impl Element for ProgressNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = self.opacity.current_value;
+        self.opacity.update(time);
+        before != self.opacity.current_value
+    }
+
+    fn frame(&mut self, frame: &FrameInfo) -> bool {
+        if frame.stage != FrameStage::Update {
+            return false;
+        }
+        let (time, duration) = match self.source {
+            ProgressSource::Scene => (frame.local_time, frame.scene_duration),
+            ProgressSource::Movie => (frame.global_time, frame.movie_duration),
+            ProgressSource::Audio(track) => frame
+                .mixer
+                .tracks
+                .get(track)
+                .and_then(Option::as_ref)
+                .map(|t| t.playback_position(frame.global_time, frame.mixer.sample_rate))
+                .unwrap_or_default(),
+        };
+        self.set_clock(time, duration)
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 {
+            let layered = opacity < 1.0;
+            if layered {
+                let knob = self.knob_radius.max(0.0);
+                canvas.save_layer_alpha_f(rect.with_outset((knob, knob)), opacity);
+            }
+            let radius = self
+                .corner_radius
+                .unwrap_or(f32::INFINITY)
+                .clamp(0.0, rect.height() / 2.0);
+            let progress = self.progress();
+            let filled = rect.left + rect.width() * progress;
+            let mut background = Paint::default();
+            background.set_anti_alias(true);
+            background.set_color4f(self.background.to_color4f(), None);
+            let fill = self.fill_paint(rect);
+
+            let segments = self.segments();
+            let last = segments.len() - 1;
+            for (i, &(from, to)) in segments.iter().enumerate() {
+                // Gaps only between segments, not at the ends of the bar
+                let left = rect.left
+                    + rect.width() * from
+                    + if i > 0 { self.segment_gap / 2.0 } else { 0.0 };
+                let right = rect.left + rect.width() * to
+                    - if i < last {
+                        self.segment_gap / 2.0
+                    } else {
+                        0.0
+                    };
+                if right <= left {
+                    continue;
+                }
+                let segment = Rect::new(left, rect.top, right, rect.bottom);
+                let rrect = RRect::new_rect_xy(segment, radius, radius);
+                canvas.draw_rrect(rrect, &background);
+                if filled > left {
+                    canvas.save();
+                    canvas.clip_rrect(rrect, ClipOp::Intersect, true);
+                    canvas.draw_rect(
+                        Rect::new(left, rect.top, filled.min(right), rect.bottom),
+                        &fill,
+                    );
+                    canvas.restore();
+                }
+            }
+
+            if self.knob_radius > 0.0 {
+                let mut knob = Paint::default();
+                knob.set_anti_alias(true);
+                knob.set_color4f(self.knob_color.to_color4f(), None);
+                canvas.draw_circle((filled, rect.center_y()), self.knob_radius, &knob);
+            }
+            if layered {
+                canvas.restore();
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        if property == "opacity" {
+            self.opacity
+                .add_segment(start, target, duration, parse_easing(easing));
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "progress" => Some(self.progress()),
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "opacity" => self.opacity.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
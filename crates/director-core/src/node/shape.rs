@@ -0,0 +1,573 @@
+use crate::animation::{Animated, EasingType};
+use crate::element::{Element, FrameInfo, FrameStage};
+use crate::node::{morph_paths, parse_easing};
+use crate::types::{Color, GradientConfig, StrokeCap, StrokeJoin};
+use skia_safe::{
+    Canvas, ContourMeasureIter, Paint, PaintStyle, Path, PathEffect, PathOp, Point, RRect, Rect,
+    Shader, StrokeRec, TileMode,
+};
+use std::any::Any;
+use std::f32::consts::PI;
+use taffy::geometry::Size;
+use taffy::style::{AvailableSpace, Style};
+
+/// Geometry drawn by a `ShapeNode`.
+///
+/// All kinds except `Path` are fitted to the node's layout box.
+#[derive(Debug, Clone)]
+pub enum ShapeGeometry {
+    /// Rectangle with corners rounded by `corner_radius`.
+    Rect,
+    /// Ellipse inscribed in the box.
+    Ellipse,
+    /// Regular polygon with `points` corners, first corner at the top.
+    Polygon,
+    /// Star with `points` tips; inner corners sit at `inner_radius` of the outer radius.
+    Star,
+    /// Arbitrary path in node-local pixels (e.g. parsed from SVG path data).
+    Path(Path),
+}
+
+/// How the inside of a shape is painted.
+#[derive(Debug, Clone)]
+pub enum ShapeFill {
+    Solid(Animated<Color>),
+    /// Gradient from `start` to `end`, both relative to the layout box (0.0 - 1.0).
+    LinearGradient(GradientConfig),
+    /// Gradient centred on `start`, reaching its last colour at `end`.
+    RadialGradient(GradientConfig),
+}
+
+/// Keyframed morph of a shape through a sequence of outlines.
+#[derive(Debug, Clone)]
+pub struct PathMorph {
+    /// Outlines to morph through; the first is the geometry the shape had before morphing.
+    pub shapes: Vec<ShapeGeometry>,
+    /// Position along `shapes`, e.g. `1.5` is halfway from the second to the third.
+    pub progress: Animated<f32>,
+}
+
+/// Boolean combination of a shape's children, recomputed from their outlines every frame.
+#[derive(Debug, Clone)]
+pub struct PathBoolean {
+    /// Applied in child order: `(first op second) op third ...`
+    pub op: PathOp,
+    /// Combined outline in node-local pixels. The children themselves are not drawn.
+    pub result: Path,
+}
+
+// --- Shape Node ---
+/// A vector shape drawn directly as a Skia path, with fill and stroke.
+#[derive(Debug, Clone)]
+pub struct ShapeNode {
+    pub style: Style,
+    pub geometry: ShapeGeometry,
+    pub fill: Option<ShapeFill>,
+    pub stroke_color: Option<Animated<Color>>,
+    pub stroke_width: Animated<f32>,
+    pub stroke_cap: StrokeCap,
+    pub stroke_join: StrokeJoin,
+    /// Alternating dash and gap lengths in pixels; empty for a solid stroke.
+    pub dash: Vec<f32>,
+    /// Shifts the dash pattern along the path, in pixels.
+    pub dash_offset: Animated<f32>,
+    /// Rounds every corner of the outline by this many pixels.
+    pub corner_radius: Animated<f32>,
+    /// Corner count of polygons and tip count of stars (rounded when drawn).
+    pub points: Animated<f32>,
+    /// Inner radius of stars as a fraction of the outer radius.
+    pub inner_radius: Animated<f32>,
+    /// Start of the visible stroke as a fraction of the outline length.
+    pub trim_start: Animated<f32>,
+    /// End of the visible stroke as a fraction of the outline length.
+    pub trim_end: Animated<f32>,
+    /// Shifts the trimmed range along the outline, wrapping past the end.
+    pub trim_offset: Animated<f32>,
+    pub opacity: Animated<f32>,
+    /// Replaces `geometry` while set; see `morph_to`.
+    pub morph: Option<PathMorph>,
+    /// Draws the combined outlines of the children instead of `geometry`.
+    pub boolean: Option<PathBoolean>,
+}
+
+impl ShapeNode {
+    pub fn new(geometry: ShapeGeometry) -> Self {
+        Self {
+            style: Style::DEFAULT,
+            geometry,
+            fill: None,
+            stroke_color: None,
+            stroke_width: Animated::new(0.0),
+            stroke_cap: StrokeCap::default(),
+            stroke_join: StrokeJoin::default(),
+            dash: Vec::new(),
+            dash_offset: Animated::new(0.0),
+            corner_radius: Animated::new(0.0),
+            points: Animated::new(5.0),
+            inner_radius: Animated::new(0.5),
+            trim_start: Animated::new(0.0),
+            trim_end: Animated::new(1.0),
+            trim_offset: Animated::new(0.0),
+            opacity: Animated::new(1.0),
+            morph: None,
+            boolean: None,
+        }
+    }
+
+    /// Creates a shape drawing its children's outlines combined with `op`.
+    pub fn boolean(op: PathOp) -> Self {
+        Self {
+            boolean: Some(PathBoolean {
+                op,
+                result: Path::new(),
+            }),
+            ..Self::new(ShapeGeometry::Rect)
+        }
+    }
+
+    /// Queues a morph to `path` (in node-local pixels) after any earlier morphs.
+    pub fn morph_to(&mut self, path: Path, duration: f64, easing: EasingType) {
+        let morph = self.morph.get_or_insert_with(|| PathMorph {
+            shapes: vec![self.geometry.clone()],
+            progress: Animated::new(0.0),
+        });
+        morph.shapes.push(ShapeGeometry::Path(path));
+        let target = (morph.shapes.len() - 1) as f32;
+        morph.progress.add_keyframe(target, duration, easing);
+    }
+
+    /// Builds the outline for a layout box, with corner rounding applied.
+    pub fn outline(&self, bounds: Rect) -> Path {
+        if let Some(boolean) = &self.boolean {
+            return boolean.result.clone();
+        }
+        let Some(morph) = self.morph.as_ref().filter(|m| m.shapes.len() > 1) else {
+            return self.geometry_outline(&self.geometry, bounds);
+        };
+        let last = morph.shapes.len() - 2;
+        let progress = morph.progress.current_value;
+        let segment = (progress.floor().max(0.0) as usize).min(last);
+        let t = progress - segment as f32;
+        if t == 0.0 {
+            return self.geometry_outline(&morph.shapes[segment], bounds);
+        }
+        if t == 1.0 {
+            return self.geometry_outline(&morph.shapes[segment + 1], bounds);
+        }
+        morph_paths(
+            &self.geometry_outline(&morph.shapes[segment], bounds),
+            &self.geometry_outline(&morph.shapes[segment + 1], bounds),
+            t,
+        )
+    }
+
+    fn geometry_outline(&self, geometry: &ShapeGeometry, bounds: Rect) -> Path {
+        let radius = self.corner_radius.current_value.max(0.0);
+        let mut path = Path::new();
+        match geometry {
+            ShapeGeometry::Rect => {
+                path.add_rrect(RRect::new_rect_xy(bounds, radius, radius), None);
+                return path;
+            }
+            ShapeGeometry::Ellipse => {
+                path.add_oval(bounds, None);
+                return path;
+            }
+            ShapeGeometry::Polygon => {
+                let sides = self.points.current_value.round().max(3.0) as usize;
+                path.add_poly(&radial_points(bounds, sides, |_| 1.0), true);
+            }
+            ShapeGeometry::Star => {
+                let tips = self.points.current_value.round().max(3.0) as usize;
+                let inner = self.inner_radius.current_value.max(0.0);
+                let corners =
+                    radial_points(bounds, tips * 2, |i| if i % 2 == 0 { 1.0 } else { inner });
+                path.add_poly(&corners, true);
+            }
+            ShapeGeometry::Path(p) => path = p.clone(),
+        }
+
+        if radius > 0.0 {
+            if let Some((rounded, _)) = PathEffect::corner_path(radius)
+                .and_then(|e| e.filter_path(&path, &StrokeRec::new_fill(), bounds))
+            {
+                return rounded;
+            }
+        }
+        path
+    }
+
+    fn fill_paint(&self, fill: &ShapeFill, bounds: Rect, opacity: f32) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+
+        let at = |(x, y): (f32, f32)| {
+            Point::new(
+                bounds.left + x * bounds.width(),
+                bounds.top + y * bounds.height(),
+            )
+        };
+        let shader = |gradient: &GradientConfig, radial: bool| {
+            let colors: Vec<skia_safe::Color> =
+                gradient.colors.iter().map(|c| c.to_skia()).collect();
+            let positions = gradient.positions.as_deref();
+            let (start, end) = (at(gradient.start), at(gradient.end));
+            if radial {
+                Shader::radial_gradient(
+                    start,
+                    (end - start).length().max(0.001),
+                    colors.as_slice(),
+                    positions,
+                    TileMode::Clamp,
+                    None,
+                    None,
+                )
+            } else {
+                Shader::linear_gradient(
+                    (start, end),
+                    colors.as_slice(),
+                    positions,
+                    TileMode::Clamp,
+                    None,
+                    None,
+                )
+            }
+        };
+
+        match fill {
+            ShapeFill::Solid(color) => {
+                let mut c = color.current_value;
+                c.a *= opacity;
+                paint.set_color4f(c.to_color4f(), None);
+            }
+            ShapeFill::LinearGradient(g) | ShapeFill::RadialGradient(g) => {
+                let radial = matches!(fill, ShapeFill::RadialGradient(_));
+                if let Some(s) = shader(g, radial) {
+                    paint.set_shader(s);
+                }
+                paint.set_alpha_f(opacity);
+            }
+        }
+        paint
+    }
+
+    fn stroke_paint(&self, color: Color, opacity: f32) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(self.stroke_width.current_value);
+        paint.set_stroke_cap(self.stroke_cap.to_skia());
+        paint.set_stroke_join(self.stroke_join.to_skia());
+
+        let mut c = color;
+        c.a *= opacity;
+        paint.set_color4f(c.to_color4f(), None);
+
+        // An odd-length pattern repeats, as in SVG's stroke-dasharray
+        if self.dash.iter().any(|d| *d > 0.0) {
+            let mut intervals = self.dash.clone();
+            if intervals.len() % 2 == 1 {
+                intervals.extend_from_within(..);
+            }
+            if let Some(effect) = PathEffect::dash(&intervals, self.dash_offset.current_value) {
+                paint.set_path_effect(effect);
+            }
+        }
+        paint
+    }
+
+    fn animated_values(&self) -> ([Option<Color>; 2], [f32; 10]) {
+        let fill = match &self.fill {
+            Some(ShapeFill::Solid(c)) => Some(c.current_value),
+            _ => None,
+        };
+        (
+            [fill, self.stroke_color.as_ref().map(|c| c.current_value)],
+            [
+                self.stroke_width.current_value,
+                self.dash_offset.current_value,
+                self.corner_radius.current_value,
+                self.points.current_value,
+                self.inner_radius.current_value,
+                self.trim_start.current_value,
+                self.trim_end.current_value,
+                self.trim_offset.current_value,
+                self.opacity.current_value,
+                self.morph
+                    .as_ref()
+                    .map_or(0.0, |m| m.progress.current_value),
+            ],
+        )
+    }
+
+    fn property_mut(&mut self, property: &str) -> Option<&mut Animated<f32>> {
+        match property {
+            "stroke_width" => Some(&mut self.stroke_width),
+            "dash_offset" => Some(&mut self.dash_offset),
+            "corner_radius" => Some(&mut self.corner_radius),
+            "points" => Some(&mut self.points),
+            "inner_radius" => Some(&mut self.inner_radius),
+            "trim_start" => Some(&mut self.trim_start),
+            "trim_end" => Some(&mut self.trim_end),
+            "trim_offset" => Some(&mut self.trim_offset),
+            "opacity" => Some(&mut self.opacity),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps the part of `path` between `start` and `end`, given as fractions of its total
+/// length across all contours, after shifting both by `offset` (wrapping past the end).
+///
+/// Follows After Effects trim paths: `start` and `end` are clamped to `0.0 - 1.0` and
+/// swapped if reversed, and a full range returns the path untouched.
+pub fn trim_path(path: &Path, start: f32, end: f32, offset: f32) -> Path {
+    let (mut start, mut end) = (start.clamp(0.0, 1.0), end.clamp(0.0, 1.0));
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+    if end - start >= 1.0 {
+        return path.clone();
+    }
+
+    let mut trimmed = Path::new();
+    let contours: Vec<_> = ContourMeasureIter::new(path, false, None).collect();
+    let total: f32 = contours.iter().map(|c| c.length()).sum();
+    if end <= start || total <= 0.0 {
+        return trimmed;
+    }
+
+    let offset = offset.rem_euclid(1.0);
+    let (from, to) = (start + offset, end + offset);
+    let ranges = if to <= 1.0 {
+        vec![(from, to)]
+    } else if from >= 1.0 {
+        vec![(from - 1.0, to - 1.0)]
+    } else {
+        vec![(from, 1.0), (0.0, to - 1.0)]
+    };
+
+    for (from, to) in ranges {
+        let (from, to) = (from * total, to * total);
+        let mut distance = 0.0;
+        for contour in &contours {
+            let length = contour.length();
+            if to > distance && from < distance + length {
+                contour.segment(from - distance, to - distance, &mut trimmed, true);
+            }
+            distance += length;
+        }
+    }
+    trimmed
+}
+
+/// Corners spread evenly around the box centre, starting at the top, each at
+/// `radius(i)` times the box's half extents.
+fn radial_points(bounds: Rect, count: usize, radius: impl Fn(usize) -> f32) -> Vec<Point> {
+    let center = bounds.center();
+    let (rx, ry) = (bounds.width() / 2.0, bounds.height() / 2.0);
+    (0..count)
+        .map(|i| {
+            let angle = -PI / 2.0 + i as f32 * 2.0 * PI / count as f32;
+            let r = radius(i);
+            Point::new(
+                center.x + angle.cos() * rx * r,
+                center.y + angle.sin() * ry * r,
+            )
+        })
+        .collect()
+}
+
+impl Element for ShapeNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// Path shapes size themselves to their path when the style leaves the size open.
+    fn needs_measure(&self) -> bool {
+        self.boolean.is_none() && matches!(self.geometry, ShapeGeometry::Path(_))
+    }
+
+    fn measure(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        let bounds = match &self.geometry {
+            ShapeGeometry::Path(p) => *p.bounds(),
+            _ => Rect::default(),
+        };
+        Size {
+            width: known_dimensions.width.unwrap_or(bounds.right.max(0.0)),
+            height: known_dimensions.height.unwrap_or(bounds.bottom.max(0.0)),
+        }
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = self.animated_values();
+        if let Some(ShapeFill::Solid(c)) = &mut self.fill {
+            c.update(time);
+        }
+        if let Some(c) = &mut self.stroke_color {
+            c.update(time);
+        }
+        self.stroke_width.update(time);
+        self.dash_offset.update(time);
+        self.corner_radius.update(time);
+        self.points.update(time);
+        self.inner_radius.update(time);
+        self.trim_start.update(time);
+        self.trim_end.update(time);
+        self.trim_offset.update(time);
+        self.opacity.update(time);
+        if let Some(morph) = &mut self.morph {
+            morph.progress.update(time);
+        }
+        self.animated_values() != before
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let local_opacity = self.opacity.current_value * opacity;
+        let path = self.outline(rect);
+
+        if let Some(fill) = &self.fill {
+            canvas.draw_path(&path, &self.fill_paint(fill, rect, local_opacity));
+        }
+        if let Some(color) = &self.stroke_color {
+            if self.stroke_width.current_value > 0.0 {
+                let stroke = trim_path(
+                    &path,
+                    self.trim_start.current_value,
+                    self.trim_end.current_value,
+                    self.trim_offset.current_value,
+                );
+                canvas.draw_path(
+                    &stroke,
+                    &self.stroke_paint(color.current_value, local_opacity),
+                );
+            }
+        }
+
+        // Children of boolean shapes are operands, already part of the outline
+        if self.boolean.is_none() {
+            draw_children(canvas);
+        }
+        Ok(())
+    }
+
+    fn outline_path(&self, layout_rect: Rect) -> Option<Path> {
+        Some(self.outline(layout_rect))
+    }
+
+    fn needs_child_outlines(&self) -> bool {
+        self.boolean.is_some()
+    }
+
+    fn frame(&mut self, frame: &FrameInfo) -> bool {
+        let Some(boolean) = &mut self.boolean else {
+            return false;
+        };
+        if frame.stage != FrameStage::Layout {
+            return false;
+        }
+        let mut outlines = frame.child_outlines.iter();
+        let result = match outlines.next() {
+            Some(first) => outlines.fold(first.clone(), |acc, outline| {
+                acc.op(outline, boolean.op).unwrap_or(acc)
+            }),
+            None => Path::default(),
+        };
+        let changed = result != boolean.result;
+        boolean.result = result;
+        changed
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        let ease_fn = parse_easing(easing);
+        if let Some(anim) = self.property_mut(property) {
+            anim.add_segment(start, target, duration, ease_fn);
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "stroke_width" => Some(self.stroke_width.current_value),
+            "dash_offset" => Some(self.dash_offset.current_value),
+            "corner_radius" => Some(self.corner_radius.current_value),
+            "points" => Some(self.points.current_value),
+            "inner_radius" => Some(self.inner_radius.current_value),
+            "trim_start" => Some(self.trim_start.current_value),
+            "trim_end" => Some(self.trim_end.current_value),
+            "trim_offset" => Some(self.trim_offset.current_value),
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn property_at(&self, property: &str, time: f64) -> Option<f32> {
+        let anim = match property {
+            "stroke_width" => &self.stroke_width,
+            "dash_offset" => &self.dash_offset,
+            "corner_radius" => &self.corner_radius,
+            "points" => &self.points,
+            "inner_radius" => &self.inner_radius,
+            "trim_start" => &self.trim_start,
+            "trim_end" => &self.trim_end,
+            "trim_offset" => &self.trim_offset,
+            "opacity" => &self.opacity,
+            _ => return None,
+        };
+        Some(anim.value_at(time))
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match self.property_mut(property) {
+            Some(anim) => {
+                anim.current_value = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn animate_property_spring(
+        &mut self,
+        property: &str,
+        start: Option<f32>,
+        target: f32,
+        config: crate::animation::SpringConfig,
+    ) {
+        if let Some(anim) = self.property_mut(property) {
+            if let Some(s) = start {
+                anim.add_spring_with_start(s, target, config);
+            } else {
+                anim.add_spring(target, config);
+            }
+        }
+    }
+}
@@ -0,0 +1,506 @@
+//! Syntax highlighting for code nodes: a small tokenizer for common languages and colour
+//! themes for its tokens.
+
+use crate::types::{CodeLanguage, Color};
+
+/// What a piece of source code is, for highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    /// Type names, and keys in JSON.
+    Type,
+    /// Function and macro names where they are called or defined.
+    Function,
+    String,
+    Number,
+    /// `true`, `null`, `None` and similar literals.
+    Constant,
+    Comment,
+    Punctuation,
+}
+
+/// A run of source text of one kind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+/// Lexical rules of a language.
+struct Rules {
+    /// Space-separated words.
+    keywords: &'static str,
+    types: &'static str,
+    constants: &'static str,
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Quotes whose strings may span lines.
+    multiline_quotes: &'static [char],
+    /// Capitalised identifiers are types.
+    capitalized_types: bool,
+    /// Keywords and types match regardless of case.
+    ignore_case: bool,
+}
+
+const RUST: Rules = Rules {
+    keywords: "as async await break const continue crate dyn else enum extern fn for if impl in \
+        let loop match mod move mut pub ref return self Self static struct super trait \
+        type unsafe use where while yield",
+    types: "i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64 bool char str",
+    constants: "true false None Some Ok Err",
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    multiline_quotes: &['"'],
+    capitalized_types: true,
+    ignore_case: false,
+};
+
+const JAVASCRIPT: Rules = Rules {
+    keywords: "as async await break case catch class const continue debugger default delete do \
+        else enum export extends finally for from function if implements import in \
+        instanceof interface let new of private protected public readonly return static \
+        super switch this throw try type typeof var void while with yield",
+    types: "any bigint boolean never number object string symbol unknown",
+    constants: "true false null undefined NaN Infinity",
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    multiline_quotes: &['`'],
+    capitalized_types: true,
+    ignore_case: false,
+};
+
+const PYTHON: Rules = Rules {
+    keywords: "and as assert async await break case class continue def del elif else except \
+        finally for from global if import in is lambda match nonlocal not or pass raise \
+        return try while with yield",
+    types: "bool bytes dict float int list object set str tuple",
+    constants: "True False None self",
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    multiline_quotes: &[],
+    capitalized_types: true,
+    ignore_case: false,
+};
+
+const GO: Rules = Rules {
+    keywords: "break case chan const continue default defer else fallthrough for func go goto \
+        if import interface map package range return select struct switch type var",
+    types: "any bool byte complex64 complex128 error float32 float64 int int8 int16 int32 \
+        int64 rune string uint uint8 uint16 uint32 uint64 uintptr",
+    constants: "true false nil iota",
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    multiline_quotes: &['`'],
+    capitalized_types: true,
+    ignore_case: false,
+};
+
+const C: Rules = Rules {
+    keywords: "abstract auto break case catch class const constexpr continue default delete do \
+        else enum extends extern final for friend fun goto if implements import inline \
+        interface namespace new operator override package private protected public \
+        return sizeof static struct super switch template this throw throws try typedef \
+        typename union using val var virtual volatile while",
+    types: "bool boolean byte char double float int long short signed size_t string unsigned \
+        void",
+    constants: "true false null nullptr NULL nil",
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    multiline_quotes: &[],
+    capitalized_types: true,
+    ignore_case: false,
+};
+
+const JSON: Rules = Rules {
+    keywords: "",
+    types: "",
+    constants: "true false null",
+    line_comments: &[],
+    block_comment: None,
+    quotes: &['"'],
+    multiline_quotes: &[],
+    capitalized_types: false,
+    ignore_case: false,
+};
+
+const SHELL: Rules = Rules {
+    keywords: "alias break case continue do done elif else esac exit export fi for function if \
+        in local readonly return select source then until while",
+    types: "",
+    constants: "true false",
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    multiline_quotes: &['"', '\''],
+    capitalized_types: false,
+    ignore_case: false,
+};
+
+const SQL: Rules = Rules {
+    keywords: "add all alter and as asc between by case create default delete desc distinct \
+        drop else end exists foreign from full group having in index inner insert into \
+        is join key left like limit not offset on or order outer primary references \
+        returning right select set table then union update values when where with",
+    types: "bigint boolean char date decimal float int integer json jsonb numeric real \
+        serial smallint text timestamp uuid varchar",
+    constants: "true false null",
+    line_comments: &["--"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['\'', '"'],
+    multiline_quotes: &['\''],
+    capitalized_types: false,
+    ignore_case: true,
+};
+
+fn rules(language: CodeLanguage) -> Option<&'static Rules> {
+    match language {
+        CodeLanguage::Plain => None,
+        CodeLanguage::Rust => Some(&RUST),
+        CodeLanguage::JavaScript => Some(&JAVASCRIPT),
+        CodeLanguage::Python => Some(&PYTHON),
+        CodeLanguage::Go => Some(&GO),
+        CodeLanguage::C => Some(&C),
+        CodeLanguage::Json => Some(&JSON),
+        CodeLanguage::Shell => Some(&SHELL),
+        CodeLanguage::Sql => Some(&SQL),
+    }
+}
+
+/// Splits `source` into highlighted tokens, one list per line.
+///
+/// Comments and strings spanning several lines are split at each line break, so every line
+/// can be drawn on its own.
+pub fn tokenize(source: &str, language: CodeLanguage) -> Vec<Vec<Token>> {
+    let mut lines = vec![Vec::new()];
+    let Some(rules) = rules(language) else {
+        push(&mut lines, TokenKind::Plain, source);
+        return lines;
+    };
+
+    let chars: Vec<char> = source.chars().collect();
+    let at = |i: usize| chars.get(i).copied().unwrap_or('\0');
+    let starts_with = |i: usize, s: &str| s.chars().enumerate().all(|(k, c)| at(i + k) == c);
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let next_significant = |mut i: usize| {
+        while at(i) == ' ' || at(i) == '\t' {
+            i += 1;
+        }
+        at(i)
+    };
+
+    let mut i = 0;
+    let mut line_start = true;
+    let mut previous_keyword = String::new();
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let kind = if c.is_whitespace() {
+            i += 1;
+            if c == '\n' {
+                line_start = true;
+            } else {
+                while at(i).is_whitespace() && at(i) != '\n' {
+                    i += 1;
+                }
+            }
+            push(&mut lines, TokenKind::Plain, &collect(&chars[start..i]));
+            continue;
+        } else if rules.line_comments.iter().any(|p| starts_with(i, p)) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if let Some((open, close)) = rules.block_comment.filter(|(o, _)| starts_with(i, o)) {
+            i += open.len();
+            while i < chars.len() && !starts_with(i, close) {
+                i += 1;
+            }
+            i = (i + close.len()).min(chars.len());
+            TokenKind::Comment
+        } else if language == CodeLanguage::Python
+            && (starts_with(i, "\"\"\"") || starts_with(i, "'''"))
+        {
+            let fence: String = chars[i..i + 3].iter().collect();
+            i += 3;
+            while i < chars.len() && !starts_with(i, &fence) {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 3).min(chars.len());
+            TokenKind::String
+        } else if rules.quotes.contains(&c) {
+            i = string_end(&chars, i, rules.multiline_quotes.contains(&c));
+            if language == CodeLanguage::Json && next_significant(i) == ':' {
+                TokenKind::Type
+            } else {
+                TokenKind::String
+            }
+        } else if language == CodeLanguage::Rust && c == '\'' {
+            // A char literal ('a', '\n') or a lifetime ('a)
+            if at(i + 1) == '\\' {
+                i = string_end(&chars, i, false);
+                TokenKind::String
+            } else if at(i + 2) == '\'' {
+                i += 3;
+                TokenKind::String
+            } else {
+                i += 1;
+                while is_ident(at(i)) {
+                    i += 1;
+                }
+                TokenKind::Keyword
+            }
+        } else if c.is_ascii_digit() || (c == '.' && at(i + 1).is_ascii_digit()) {
+            i += 1;
+            while at(i).is_ascii_alphanumeric()
+                || at(i) == '_'
+                || (at(i) == '.' && at(i + 1).is_ascii_digit())
+            {
+                i += 1;
+            }
+            TokenKind::Number
+        } else if (c == '#' && language == CodeLanguage::C && line_start)
+            || (c == '$' && language == CodeLanguage::Shell)
+        {
+            // Preprocessor directives and shell variables
+            i += 1;
+            if at(i) == '{' {
+                while i < chars.len() && chars[i] != '}' && chars[i] != '\n' {
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+            } else {
+                while is_ident(at(i)) {
+                    i += 1;
+                }
+            }
+            if c == '#' {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Type
+            }
+        } else if c == '@'
+            && matches!(
+                language,
+                CodeLanguage::Python | CodeLanguage::JavaScript | CodeLanguage::C
+            )
+            && (at(i + 1).is_alphabetic() || at(i + 1) == '_')
+        {
+            // Decorators and annotations
+            i += 1;
+            while is_ident(at(i)) || (at(i) == '.' && is_ident(at(i + 1))) {
+                i += 1;
+            }
+            TokenKind::Function
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            while is_ident(at(i)) {
+                i += 1;
+            }
+            let word = collect(&chars[start..i]);
+            let lookup = if rules.ignore_case {
+                word.to_ascii_lowercase()
+            } else {
+                word.clone()
+            };
+            let is_in = |words: &str| words.split_whitespace().any(|w| w == lookup);
+            let kind = if is_in(rules.keywords) {
+                TokenKind::Keyword
+            } else if is_in(rules.constants) {
+                TokenKind::Constant
+            } else if is_in(rules.types) {
+                TokenKind::Type
+            } else if language == CodeLanguage::Rust && at(i) == '!' && at(i + 1) != '=' {
+                // Macro invocation
+                i += 1;
+                TokenKind::Function
+            } else if next_significant(i) == '('
+                || matches!(
+                    previous_keyword.as_str(),
+                    "fn" | "def" | "func" | "function"
+                )
+            {
+                TokenKind::Function
+            } else if rules.capitalized_types && word.starts_with(char::is_uppercase) {
+                TokenKind::Type
+            } else {
+                TokenKind::Plain
+            };
+            previous_keyword = if kind == TokenKind::Keyword {
+                lookup
+            } else {
+                String::new()
+            };
+            push(&mut lines, kind, &collect(&chars[start..i]));
+            line_start = false;
+            continue;
+        } else {
+            i += 1;
+            TokenKind::Punctuation
+        };
+        previous_keyword.clear();
+        line_start = false;
+        push(&mut lines, kind, &collect(&chars[start..i]));
+    }
+    lines
+}
+
+fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+/// Index after the string starting at `start`; escapes skip the next character.
+fn string_end(chars: &[char], start: usize, multiline: bool) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '\n' if !multiline => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// Appends `text` to the last line, starting a new line at each line break. Adjacent
+/// tokens of the same kind are merged.
+fn push(lines: &mut Vec<Vec<Token>>, kind: TokenKind, text: &str) {
+    for (n, part) in text.split('\n').enumerate() {
+        if n > 0 {
+            lines.push(Vec::new());
+        }
+        if part.is_empty() {
+            continue;
+        }
+        let line = lines.last_mut().expect("tokenize starts with a line");
+        match line.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(part),
+            _ => line.push(Token {
+                kind,
+                text: part.to_string(),
+            }),
+        }
+    }
+}
+
+/// Colours of a code block and its tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodeTheme {
+    pub background: Color,
+    pub text: Color,
+    pub keyword: Color,
+    pub type_name: Color,
+    pub function: Color,
+    pub string: Color,
+    pub number: Color,
+    pub constant: Color,
+    pub comment: Color,
+    pub punctuation: Color,
+    pub line_number: Color,
+    /// Background of focused lines.
+    pub highlight: Color,
+    /// Caret shown while typing.
+    pub cursor: Color,
+}
+
+fn hex(rgb: u32) -> Color {
+    let channel = |shift: u32| ((rgb >> shift) & 0xFF) as f32 / 255.0;
+    Color::new(channel(16), channel(8), channel(0), 1.0)
+}
+
+impl CodeTheme {
+    /// One Dark colours.
+    pub fn dark() -> Self {
+        Self {
+            background: hex(0x282C34),
+            text: hex(0xABB2BF),
+            keyword: hex(0xC678DD),
+            type_name: hex(0xE5C07B),
+            function: hex(0x61AFEF),
+            string: hex(0x98C379),
+            number: hex(0xD19A66),
+            constant: hex(0xD19A66),
+            comment: hex(0x7F848E),
+            punctuation: hex(0xABB2BF),
+            line_number: hex(0x636D83),
+            highlight: Color::new(1.0, 1.0, 1.0, 0.08),
+            cursor: hex(0x528BFF),
+        }
+    }
+
+    /// GitHub light colours.
+    pub fn light() -> Self {
+        Self {
+            background: hex(0xFFFFFF),
+            text: hex(0x24292F),
+            keyword: hex(0xCF222E),
+            type_name: hex(0x953800),
+            function: hex(0x8250DF),
+            string: hex(0x0A3069),
+            number: hex(0x0550AE),
+            constant: hex(0x0550AE),
+            comment: hex(0x6E7781),
+            punctuation: hex(0x24292F),
+            line_number: hex(0x8C959F),
+            highlight: Color::new(0.98, 0.83, 0.25, 0.25),
+            cursor: hex(0x0969DA),
+        }
+    }
+
+    /// Monokai colours.
+    pub fn monokai() -> Self {
+        Self {
+            background: hex(0x272822),
+            text: hex(0xF8F8F2),
+            keyword: hex(0xF92672),
+            type_name: hex(0x66D9EF),
+            function: hex(0xA6E22E),
+            string: hex(0xE6DB74),
+            number: hex(0xAE81FF),
+            constant: hex(0xAE81FF),
+            comment: hex(0x75715E),
+            punctuation: hex(0xF8F8F2),
+            line_number: hex(0x90908A),
+            highlight: Color::new(1.0, 1.0, 1.0, 0.1),
+            cursor: hex(0xF8F8F0),
+        }
+    }
+
+    /// A built-in theme by name: `"dark"`, `"light"` or `"monokai"`.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" | "one_dark" => Some(Self::dark()),
+            "light" | "github" => Some(Self::light()),
+            "monokai" => Some(Self::monokai()),
+            _ => None,
+        }
+    }
+
+    /// Colour of a token kind.
+    pub fn color(&self, kind: TokenKind) -> Color {
+        match kind {
+            TokenKind::Plain => self.text,
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_name,
+            TokenKind::Function => self.function,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Constant => self.constant,
+            TokenKind::Comment => self.comment,
+            TokenKind::Punctuation => self.punctuation,
+        }
+    }
+}
+
+impl Default for CodeTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
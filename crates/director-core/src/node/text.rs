@@ -1,5 +1,6 @@
-use crate::animation::Animated;
+use crate::animation::{Animated, SpringConfig};
 use crate::element::{Element, TextFit, TextShadow, TextSpan};
+use crate::node::counter::TextCounter;
 use crate::node::text_animator::{
     parse_text_anim_property, GlyphAnimState, TextAnimProperty, TextAnimator,
 };
@@ -54,6 +55,12 @@ pub struct TextNode {
     pub animators: Vec<TextAnimator>,
     /// Current animation time (set by update(), used by render())
     pub current_time: f64,
+
+    // Number Counters
+    /// Animated number written into the first span on every update
+    pub counter: Option<TextCounter>,
+    /// Uses equal-width digits so changing numbers don't shift
+    pub tabular_figures: bool,
 }
 
 impl Clone for TextNode {
@@ -73,6 +80,8 @@ impl Clone for TextNode {
             style: self.style.clone(),
             animators: self.animators.clone(),
             current_time: self.current_time,
+            counter: self.counter.clone(),
+            tabular_figures: self.tabular_figures,
         }
     }
 }
@@ -86,6 +95,111 @@ impl fmt::Debug for TextNode {
     }
 }
 
+/// Fallbacks for the fields a `TextSpan` leaves unset.
+pub(crate) struct SpanDefaults<'a> {
+    pub font_size: f32,
+    pub color: Color,
+    /// Families tried in order when a span has no `font_family`
+    pub font_families: &'a [&'a str],
+    /// Uses equal-width digits
+    pub tabular_figures: bool,
+}
+
+/// Builds a paragraph from `spans`, styling each span over `defaults`. The paragraph
+/// still needs laying out.
+///
+/// Shared by every node that draws text, so spans look the same everywhere.
+pub(crate) fn build_span_paragraph(
+    spans: &[TextSpan],
+    defaults: &SpanDefaults,
+    paragraph_style: &ParagraphStyle,
+    font_collection: &Mutex<FontCollection>,
+) -> Paragraph {
+    let font_collection = font_collection.lock().unwrap();
+    let mut builder = ParagraphBuilder::new(paragraph_style, &*font_collection);
+    for span in spans {
+        builder.push_style(&span_text_style(span, defaults));
+        builder.add_text(&span.text);
+        builder.pop();
+    }
+    builder.build()
+}
+
+/// Lays out `spans` on a single line, exactly as wide as their text.
+pub(crate) fn line_paragraph(
+    spans: &[TextSpan],
+    defaults: &SpanDefaults,
+    font_collection: &Mutex<FontCollection>,
+) -> Paragraph {
+    let mut paragraph_style = ParagraphStyle::new();
+    paragraph_style.set_text_height_behavior(TextHeightBehavior::DisableAll);
+    let mut paragraph = build_span_paragraph(spans, defaults, &paragraph_style, font_collection);
+    paragraph.layout(f32::INFINITY);
+    let width = paragraph.max_intrinsic_width().ceil();
+    paragraph.layout(width);
+    paragraph
+}
+
+/// Converts `span` to a Skia text style, falling back to `defaults` for unset fields.
+fn span_text_style(span: &TextSpan, defaults: &SpanDefaults) -> TextStyle {
+    let mut text_style = TextStyle::new();
+
+    // 1. Font Size
+    text_style.set_font_size(span.font_size.unwrap_or(defaults.font_size));
+
+    // 2. Font Family
+    match &span.font_family {
+        Some(family) => text_style.set_font_families(&[family.as_str()]),
+        None => text_style.set_font_families(defaults.font_families),
+    };
+    if defaults.tabular_figures {
+        text_style.add_font_feature("tnum", 1);
+    }
+
+    // 3. Color (Foreground)
+    let color = span.color.unwrap_or(defaults.color);
+    let mut paint = Paint::default();
+    paint.set_color4f(color.to_color4f(), None);
+    paint.set_anti_alias(true);
+    text_style.set_foreground_paint(&paint);
+
+    // 4. Background
+    if let Some(bg_color) = span.background_color {
+        let mut bg_paint = Paint::default();
+        bg_paint.set_color4f(bg_color.to_color4f(), None);
+        bg_paint.set_anti_alias(true);
+        text_style.set_background_paint(&bg_paint);
+    }
+
+    // 5. Weight / Slant
+    if let Some(w) = span.font_weight {
+        let weight = skia_safe::font_style::Weight::from(w as i32);
+        let slant = if span.font_style.as_deref() == Some("italic") {
+            skia_safe::font_style::Slant::Italic
+        } else {
+            skia_safe::font_style::Slant::Upright
+        };
+        text_style.set_font_style(skia_safe::FontStyle::new(
+            weight,
+            skia_safe::font_style::Width::NORMAL,
+            slant,
+        ));
+    }
+
+    // 6. Stroke (Rich Text)
+    if let Some(sw) = span.stroke_width.filter(|&sw| sw > 0.0) {
+        let mut stroke_paint = Paint::default();
+        stroke_paint.set_style(PaintStyle::Stroke);
+        stroke_paint.set_stroke_width(sw);
+        stroke_paint.set_anti_alias(true);
+        let stroke_color = span.stroke_color.unwrap_or(Color::BLACK);
+        stroke_paint.set_color4f(stroke_color.to_color4f(), None);
+        text_style.set_foreground_paint(&stroke_paint);
+    }
+
+    text_style
+}
+
 impl TextNode {
     pub fn new(spans: Vec<TextSpan>, font_collection: Arc<Mutex<FontCollection>>) -> Self {
         let mut node = Self {
@@ -103,6 +217,8 @@ impl TextNode {
             style: Style::default(),
             animators: Vec::new(),
             current_time: 0.0,
+            counter: None,
+            tabular_figures: false,
         };
         node.init_paragraph();
         node
@@ -136,80 +252,18 @@ impl TextNode {
         paragraph_style.set_strut_style(strut_style);
         // ----------------------------------
 
-        let font_collection_guard = self.font_collection.lock().unwrap();
-        let mut builder = ParagraphBuilder::new(&paragraph_style, &*font_collection_guard);
-
-        for span in &self.spans {
-            let mut text_style = TextStyle::new();
-
-            // 1. Font Size
-            let size = span
-                .font_size
-                .unwrap_or(self.default_font_size.current_value);
-            text_style.set_font_size(size);
-
-            // 2. Font Family
-            let mut families = vec![];
-            if let Some(f) = &span.font_family {
-                families.push(f.as_str());
-            } else {
-                families.push("Sans Serif");
-                families.push("Arial");
-            }
-            text_style.set_font_families(&families);
-
-            // 3. Color (Foreground)
-            let color = span.color.unwrap_or(self.default_color.current_value);
-            let mut paint = Paint::default();
-            paint.set_color(color.to_skia());
-            paint.set_anti_alias(true);
-            text_style.set_foreground_paint(&paint);
-
-            // 4. Background
-            if let Some(bg_color) = span.background_color {
-                let mut bg_paint = Paint::default();
-                bg_paint.set_color(bg_color.to_skia());
-                bg_paint.set_anti_alias(true);
-                text_style.set_background_paint(&bg_paint);
-            }
-
-            // 5. Weight / Slant
-            if let Some(w) = span.font_weight {
-                let weight = skia_safe::font_style::Weight::from(w as i32);
-                let slant = if span.font_style.as_deref() == Some("italic") {
-                    skia_safe::font_style::Slant::Italic
-                } else {
-                    skia_safe::font_style::Slant::Upright
-                };
-                text_style.set_font_style(skia_safe::FontStyle::new(
-                    weight,
-                    skia_safe::font_style::Width::NORMAL,
-                    slant,
-                ));
-            }
-
-            // 6. Stroke (Rich Text)
-            if let Some(sw) = span.stroke_width {
-                if sw > 0.0 {
-                    let mut stroke_paint = Paint::default();
-                    stroke_paint.set_style(PaintStyle::Stroke);
-                    stroke_paint.set_stroke_width(sw);
-                    stroke_paint.set_anti_alias(true);
-                    if let Some(sc) = span.stroke_color {
-                        stroke_paint.set_color(sc.to_skia());
-                    } else {
-                        stroke_paint.set_color(skia_safe::Color::BLACK);
-                    }
-                    text_style.set_foreground_paint(&stroke_paint);
-                }
-            }
-
-            builder.push_style(&text_style);
-            builder.add_text(&span.text);
-            builder.pop();
-        }
-
-        builder.build()
+        let defaults = SpanDefaults {
+            font_size,
+            color: self.default_color.current_value,
+            font_families: &["Sans Serif", "Arial"],
+            tabular_figures: self.tabular_figures,
+        };
+        build_span_paragraph(
+            &self.spans,
+            &defaults,
+            &paragraph_style,
+            &self.font_collection,
+        )
     }
 
     pub fn init_paragraph(&mut self) {
@@ -226,6 +280,35 @@ impl TextNode {
         }
     }
 
+    /// Writes the counter's formatted value into the first span.
+    ///
+    /// Returns whether the text changed; the paragraph then needs rebuilding.
+    fn write_counter_text(&mut self) -> bool {
+        let Some(counter) = &self.counter else {
+            return false;
+        };
+        let text = counter.text();
+        let stale = match self.spans.first_mut() {
+            Some(span) if span.text == text => false,
+            Some(span) => {
+                span.text = text;
+                true
+            }
+            None => {
+                self.spans.push(TextSpan {
+                    text,
+                    ..Default::default()
+                });
+                true
+            }
+        };
+        if stale {
+            // New digits can change the measured width
+            self.dirty_layout = true;
+        }
+        stale
+    }
+
     /// Returns the total number of characters across all spans.
     pub fn get_total_char_count(&self) -> usize {
         self.spans.iter().map(|s| s.text.chars().count()).sum()
@@ -451,6 +534,22 @@ impl Element for TextNode {
         self.style.clone()
     }
 
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn set_rich_text(&mut self, spans: Vec<TextSpan>) {
+        self.spans = spans;
+        self.dirty_layout = true;
+        self.init_paragraph();
+    }
+
+    fn modify_text_spans(&mut self, f: &dyn Fn(&mut Vec<TextSpan>)) {
+        f(&mut self.spans);
+        self.dirty_layout = true;
+        self.init_paragraph();
+    }
+
     fn update(&mut self, time: f64) -> bool {
         // Store time for render to use
         self.current_time = time;
@@ -478,6 +577,13 @@ impl Element for TextNode {
             changed = true;
         }
 
+        if let Some(counter) = &mut self.counter {
+            counter.value.update(time);
+            if self.write_counter_text() {
+                changed = true;
+            }
+        }
+
         if changed {
             // Rebuild paragraph
             self.init_paragraph();
@@ -601,10 +707,88 @@ impl Element for TextNode {
                     .add_segment(start, target, duration, ease_fn);
                 self.dirty_layout = true;
             }
+            "value" => {
+                if let Some(counter) = &mut self.counter {
+                    let ease_fn = crate::node::parse_easing(easing);
+                    counter
+                        .value
+                        .add_segment(start as f64, target as f64, duration, ease_fn);
+                }
+            }
             _ => {}
         }
     }
 
+    fn animate_property_spring(
+        &mut self,
+        property: &str,
+        start: Option<f32>,
+        target: f32,
+        config: SpringConfig,
+    ) {
+        if let ("value", Some(counter)) = (property, &mut self.counter) {
+            match start {
+                Some(s) => counter
+                    .value
+                    .add_spring_with_start(s as f64, target as f64, config),
+                None => counter.value.add_spring(target as f64, config),
+            }
+        }
+    }
+
+    /// Glyph outlines of the laid-out text, where `render` paints them.
+    fn outline_path(&self, layout_rect: Rect) -> Option<skia_safe::Path> {
+        self.ensure_paragraph_ready();
+        let mut p_guard = self.paragraph.lock().unwrap();
+        let paragraph = p_guard.as_mut()?;
+        paragraph.layout(layout_rect.width());
+
+        let mut outline = skia_safe::Path::new();
+        for line in 0..paragraph.line_number() {
+            let (_, glyphs) = paragraph.get_path_at(line);
+            outline.add_path(&glyphs, (layout_rect.x(), layout_rect.y()), None);
+        }
+        Some(outline)
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "font_size" | "size" => Some(self.default_font_size.current_value),
+            "value" => self.counter.as_ref().map(|c| c.value.current_value as f32),
+            _ => None,
+        }
+    }
+
+    fn property_at(&self, property: &str, time: f64) -> Option<f32> {
+        match property {
+            "font_size" | "size" => Some(self.default_font_size.value_at(time)),
+            "value" => self.counter.as_ref().map(|c| c.value.value_at(time) as f32),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "font_size" | "size" => {
+                self.default_font_size.current_value = value;
+                self.dirty_layout = true;
+                self.init_paragraph();
+                true
+            }
+            "value" => match &mut self.counter {
+                Some(counter) => {
+                    counter.value.current_value = value as f64;
+                    if self.write_counter_text() {
+                        self.init_paragraph();
+                    }
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
     fn add_text_animator(
         &mut self,
         start_idx: usize,
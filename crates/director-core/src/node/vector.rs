@@ -1,30 +1,54 @@
+use crate::animation::Animated;
 use crate::element::Element;
-use crate::node::parse_easing;
-use skia_safe::{AlphaType, Canvas, ColorSpace, ColorType, Data, Image, Paint, Rect};
+use crate::node::{parse_easing, trim_path};
+use skia_safe::{
+    AlphaType, Canvas, ColorSpace, ColorType, Data, Image, Matrix, Paint, PaintCap, PaintJoin,
+    PaintStyle, Path, PathEffect, PathFillType, Rect, Shader, TileMode,
+};
 use std::any::Any;
 use std::sync::{Arc, Mutex};
 use taffy::style::Style;
 use tiny_skia::{Pixmap, Transform};
+use usvg::tiny_skia_path::PathSegment;
 use usvg::{Options, Tree};
 
 /// A node that renders scalable vector graphics (SVG).
 ///
 /// Rasterizes the SVG to a bitmap at the exact size required by the layout, ensuring crisp rendering.
+/// While its strokes are trimmed, the SVG is instead drawn path by path with Skia so each
+/// stroke can be cut. That renderer skips clip paths, masks, filters, blend modes, patterns
+/// and images, so an SVG using any of them is drawn with it for the whole animation once
+/// its trim is animated, rather than switching renderers when the trim reaches the ends.
 #[derive(Debug)]
 pub struct VectorNode {
     tree: Arc<Tree>,
+    /// Whether the Skia renderer draws this SVG like resvg does (see `skia_drawable`).
+    skia_exact: bool,
+    /// Whether a trim property has been given an animation.
+    trim_animated: bool,
     cache: Mutex<Option<(u32, u32, Image)>>,
     pub style: Style,
-    pub opacity: crate::animation::Animated<f32>,
+    pub opacity: Animated<f32>,
+    /// Start of the visible part of every stroke, as a fraction of its length.
+    pub trim_start: Animated<f32>,
+    /// End of the visible part of every stroke, as a fraction of its length.
+    pub trim_end: Animated<f32>,
+    /// Shifts the trimmed range along each stroke, wrapping past the end.
+    pub trim_offset: Animated<f32>,
 }
 
 impl Clone for VectorNode {
     fn clone(&self) -> Self {
         Self {
             tree: self.tree.clone(),
+            skia_exact: self.skia_exact,
+            trim_animated: self.trim_animated,
             cache: Mutex::new(None), // Don't share cache across clones (or maybe we should? No, size might differ)
             style: self.style.clone(),
             opacity: self.opacity.clone(),
+            trim_start: self.trim_start.clone(),
+            trim_end: self.trim_end.clone(),
+            trim_offset: self.trim_offset.clone(),
         }
     }
 }
@@ -35,10 +59,242 @@ impl VectorNode {
         // usvg 0.44: Tree::from_data
         let tree = Tree::from_data(data, &opt).expect("Failed to parse SVG");
         Self {
+            skia_exact: skia_drawable(&tree),
+            trim_animated: false,
             tree: Arc::new(tree),
             cache: Mutex::new(None),
             style: Style::DEFAULT,
-            opacity: crate::animation::Animated::new(1.0),
+            opacity: Animated::new(1.0),
+            trim_start: Animated::new(0.0),
+            trim_end: Animated::new(1.0),
+            trim_offset: Animated::new(0.0),
+        }
+    }
+
+    /// Returns the combined outline of every shape in the SVG, in SVG user units,
+    /// together with the size of the SVG canvas.
+    pub fn outline(&self) -> (Path, (f32, f32)) {
+        let mut path = Path::new();
+        append_group_outline(self.tree.root(), &mut path);
+        let size = self.tree.size();
+        (path, (size.width(), size.height()))
+    }
+
+    fn is_trimmed(&self) -> bool {
+        self.trim_start.current_value > 0.0 || self.trim_end.current_value < 1.0
+    }
+
+    /// Whether to draw with Skia this frame: while trimmed, and throughout a trim
+    /// animation when resvg would draw the untrimmed frames differently.
+    fn draws_with_skia(&self) -> bool {
+        self.is_trimmed() || (!self.skia_exact && self.trim_animated)
+    }
+
+    fn property_mut(&mut self, property: &str) -> Option<&mut Animated<f32>> {
+        match property {
+            "opacity" => Some(&mut self.opacity),
+            "trim_start" => Some(&mut self.trim_start),
+            "trim_end" => Some(&mut self.trim_end),
+            "trim_offset" => Some(&mut self.trim_offset),
+            _ => None,
+        }
+    }
+
+    /// Draws a group with Skia, trimming every stroke.
+    fn draw_group_trimmed(&self, canvas: &Canvas, group: &usvg::Group) {
+        for node in group.children() {
+            match node {
+                usvg::Node::Group(g) => {
+                    let opacity = g.opacity().get();
+                    if opacity < 1.0 {
+                        canvas.save_layer_alpha_f(None, opacity);
+                        self.draw_group_trimmed(canvas, g);
+                        canvas.restore();
+                    } else {
+                        self.draw_group_trimmed(canvas, g);
+                    }
+                }
+                usvg::Node::Text(t) => self.draw_group_trimmed(canvas, t.flattened()),
+                usvg::Node::Path(p) if p.is_visible() => self.draw_path_trimmed(canvas, p),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw_path_trimmed(&self, canvas: &Canvas, p: &usvg::Path) {
+        // Drawn in the path's own coordinates so stroke widths and gradients scale with it
+        let mut path = Path::new();
+        append_segments(p.data(), &mut path);
+        canvas.save();
+        canvas.concat(&to_matrix(p.abs_transform()));
+
+        let fill = p.fill().and_then(|fill| {
+            let mut paint = svg_paint(fill.paint(), fill.opacity().get())?;
+            paint.set_style(PaintStyle::Fill);
+            let rule = match fill.rule() {
+                usvg::FillRule::NonZero => PathFillType::Winding,
+                usvg::FillRule::EvenOdd => PathFillType::EvenOdd,
+            };
+            let mut filled = path.clone();
+            filled.set_fill_type(rule);
+            Some((filled, paint))
+        });
+
+        let stroke = p.stroke().and_then(|stroke| {
+            let mut paint = svg_paint(stroke.paint(), stroke.opacity().get())?;
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(stroke.width().get());
+            paint.set_stroke_miter(stroke.miterlimit().get());
+            paint.set_stroke_cap(match stroke.linecap() {
+                usvg::LineCap::Butt => PaintCap::Butt,
+                usvg::LineCap::Round => PaintCap::Round,
+                usvg::LineCap::Square => PaintCap::Square,
+            });
+            paint.set_stroke_join(match stroke.linejoin() {
+                usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => PaintJoin::Miter,
+                usvg::LineJoin::Round => PaintJoin::Round,
+                usvg::LineJoin::Bevel => PaintJoin::Bevel,
+            });
+            if let Some(effect) = stroke
+                .dasharray()
+                .and_then(|d| PathEffect::dash(d, stroke.dashoffset()))
+            {
+                paint.set_path_effect(effect);
+            }
+            let trimmed = trim_path(
+                &path,
+                self.trim_start.current_value,
+                self.trim_end.current_value,
+                self.trim_offset.current_value,
+            );
+            Some((trimmed, paint))
+        });
+
+        let mut layers = [fill, stroke];
+        if p.paint_order() == usvg::PaintOrder::StrokeAndFill {
+            layers.reverse();
+        }
+        for (path, paint) in layers.iter().flatten() {
+            canvas.draw_path(path, paint);
+        }
+        canvas.restore();
+    }
+}
+
+/// Converts a usvg paint to a Skia paint; patterns are not supported.
+fn svg_paint(paint: &usvg::Paint, opacity: f32) -> Option<Paint> {
+    let mut out = Paint::default();
+    out.set_anti_alias(true);
+
+    let stops = |stops: &[usvg::Stop]| {
+        let colors: Vec<skia_safe::Color> = stops
+            .iter()
+            .map(|s| {
+                let c = s.color();
+                let a = (s.opacity().get() * 255.0).round() as u8;
+                skia_safe::Color::from_argb(a, c.red, c.green, c.blue)
+            })
+            .collect();
+        let positions: Vec<f32> = stops.iter().map(|s| s.offset().get()).collect();
+        (colors, positions)
+    };
+    let tile_mode = |spread: usvg::SpreadMethod| match spread {
+        usvg::SpreadMethod::Pad => TileMode::Clamp,
+        usvg::SpreadMethod::Reflect => TileMode::Mirror,
+        usvg::SpreadMethod::Repeat => TileMode::Repeat,
+    };
+
+    match paint {
+        usvg::Paint::Color(c) => {
+            out.set_color(skia_safe::Color::from_rgb(c.red, c.green, c.blue));
+        }
+        usvg::Paint::LinearGradient(g) => {
+            let (colors, positions) = stops(g.stops());
+            out.set_shader(Shader::linear_gradient(
+                ((g.x1(), g.y1()), (g.x2(), g.y2())),
+                colors.as_slice(),
+                positions.as_slice(),
+                tile_mode(g.spread_method()),
+                None,
+                &to_matrix(g.transform()),
+            )?);
+        }
+        usvg::Paint::RadialGradient(g) => {
+            let (colors, positions) = stops(g.stops());
+            out.set_shader(Shader::two_point_conical_gradient(
+                (g.fx(), g.fy()),
+                0.0,
+                (g.cx(), g.cy()),
+                g.r().get(),
+                colors.as_slice(),
+                positions.as_slice(),
+                tile_mode(g.spread_method()),
+                None,
+                &to_matrix(g.transform()),
+            )?);
+        }
+        usvg::Paint::Pattern(_) => return None,
+    }
+    out.set_alpha_f(out.alpha_f() * opacity);
+    Some(out)
+}
+
+/// Whether `VectorNode::draw_group_trimmed` draws `tree` like resvg does, so the two
+/// renderers can be swapped between frames without a visible change.
+fn skia_drawable(tree: &Tree) -> bool {
+    fn group_drawable(group: &usvg::Group) -> bool {
+        group.children().iter().all(|node| match node {
+            usvg::Node::Group(g) => g.blend_mode() == usvg::BlendMode::Normal && group_drawable(g),
+            usvg::Node::Text(t) => group_drawable(t.flattened()),
+            usvg::Node::Path(_) => true,
+            usvg::Node::Image(_) => false,
+        })
+    }
+
+    tree.clip_paths().is_empty()
+        && tree.masks().is_empty()
+        && tree.filters().is_empty()
+        && tree.patterns().is_empty()
+        && group_drawable(tree.root())
+}
+
+fn to_matrix(t: Transform) -> Matrix {
+    Matrix::new_all(t.sx, t.kx, t.tx, t.ky, t.sy, t.ty, 0.0, 0.0, 1.0)
+}
+
+fn append_group_outline(group: &usvg::Group, out: &mut Path) {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(g) => append_group_outline(g, out),
+            usvg::Node::Text(t) => append_group_outline(t.flattened(), out),
+            usvg::Node::Path(p) => {
+                if let Some(data) = p.data().clone().transform(p.abs_transform()) {
+                    append_segments(&data, out);
+                }
+            }
+            usvg::Node::Image(_) => {}
+        }
+    }
+}
+
+fn append_segments(data: &usvg::tiny_skia_path::Path, out: &mut Path) {
+    for segment in data.segments() {
+        match segment {
+            PathSegment::MoveTo(pt) => {
+                out.move_to((pt.x, pt.y));
+            }
+            PathSegment::LineTo(pt) => {
+                out.line_to((pt.x, pt.y));
+            }
+            PathSegment::QuadTo(c, pt) => {
+                out.quad_to((c.x, c.y), (pt.x, pt.y));
+            }
+            PathSegment::CubicTo(c1, c2, pt) => {
+                out.cubic_to((c1.x, c1.y), (c2.x, c2.y), (pt.x, pt.y));
+            }
+            PathSegment::Close => {
+                out.close();
+            }
         }
     }
 }
@@ -60,8 +316,20 @@ impl Element for VectorNode {
     }
 
     fn update(&mut self, time: f64) -> bool {
+        let values = |n: &Self| {
+            [
+                n.opacity.current_value,
+                n.trim_start.current_value,
+                n.trim_end.current_value,
+                n.trim_offset.current_value,
+            ]
+        };
+        let before = values(self);
         self.opacity.update(time);
-        true
+        self.trim_start.update(time);
+        self.trim_end.update(time);
+        self.trim_offset.update(time);
+        values(self) != before
     }
 
     fn render(
@@ -79,6 +347,20 @@ impl Element for VectorNode {
             return Ok(());
         }
 
+        if self.draws_with_skia() {
+            let tree_size = self.tree.size();
+            canvas.save_layer_alpha_f(rect, self.opacity.current_value * parent_opacity);
+            canvas.translate((rect.left, rect.top));
+            canvas.scale((
+                rect.width() / tree_size.width(),
+                rect.height() / tree_size.height(),
+            ));
+            self.draw_group_trimmed(canvas, self.tree.root());
+            canvas.restore();
+            draw_children(canvas);
+            return Ok(());
+        }
+
         let mut cache_guard = self.cache.lock().unwrap();
 
         // Check if cache is valid
@@ -129,6 +411,19 @@ impl Element for VectorNode {
         Ok(())
     }
 
+    fn outline_path(&self, layout_rect: Rect) -> Option<Path> {
+        let (path, (width, height)) = self.outline();
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let mut matrix = Matrix::translate((layout_rect.left, layout_rect.top));
+        matrix.pre_scale(
+            (layout_rect.width() / width, layout_rect.height() / height),
+            None,
+        );
+        Some(path.with_transform(&matrix))
+    }
+
     fn animate_property(
         &mut self,
         property: &str,
@@ -137,9 +432,30 @@ impl Element for VectorNode {
         duration: f64,
         easing: &str,
     ) {
-        if property == "opacity" {
-            let ease = parse_easing(easing);
-            self.opacity.add_segment(start, target, duration, ease);
+        let ease = parse_easing(easing);
+        if let Some(anim) = self.property_mut(property) {
+            anim.add_segment(start, target, duration, ease);
+            self.trim_animated |= property.starts_with("trim_");
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            "trim_start" => Some(self.trim_start.current_value),
+            "trim_end" => Some(self.trim_end.current_value),
+            "trim_offset" => Some(self.trim_offset.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match self.property_mut(property) {
+            Some(anim) => {
+                anim.current_value = value;
+                true
+            }
+            None => false,
         }
     }
 }
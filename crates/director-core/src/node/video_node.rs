@@ -218,6 +218,18 @@ impl Element for VideoNode {
         }
     }
 
+    fn get_property(&self, property: &str) -> Option<f32> {
+        (property == "opacity").then_some(self.opacity.current_value)
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        if property == "opacity" {
+            self.opacity.current_value = value;
+            return true;
+        }
+        false
+    }
+
     fn animate_property_spring(
         &mut self,
         property: &str,
@@ -0,0 +1,573 @@
+use crate::animation::Animated;
+use crate::audio::{AudioAnalyzer, AudioTrack};
+use crate::element::{Element, FrameInfo, FrameStage};
+use crate::node::parse_easing;
+use crate::types::{Color, SpectrumStyle, WaveformMode};
+use skia_safe::{Canvas, Paint, PaintCap, PaintJoin, PaintStyle, Path, Point, RRect, Rect};
+use std::any::Any;
+use taffy::style::Style;
+
+/// Seconds between the past spectra that `smoothing` blends in (one frame at 60 fps).
+const SMOOTHING_STEP: f64 = 1.0 / 60.0;
+/// Most past spectra blended in, for smoothing close to 1.
+const MAX_SMOOTHING_WINDOWS: usize = 24;
+
+/// Frames of `track` that can play: its samples, cut short by its duration.
+fn playable_frames(track: &AudioTrack, sample_rate: u32) -> usize {
+    let frames = track.samples.len() / 2;
+    match track.duration {
+        Some(duration) if !track.loop_audio => frames.min((duration * sample_rate as f64) as usize),
+        _ => frames,
+    }
+}
+
+/// Frame of `track`'s samples playing at global frame `frame` (counted from the track's
+/// start), or `None` while the track is silent.
+fn source_frame(track: &AudioTrack, frame: i64, sample_rate: u32) -> Option<usize> {
+    let frames = track.samples.len() / 2;
+    let limit = track
+        .duration
+        .map_or(i64::MAX, |d| (d * sample_rate as f64) as i64);
+    if frame < 0 || frame >= limit || frames == 0 {
+        return None;
+    }
+    let frame = frame as usize;
+    if track.loop_audio {
+        Some(frame % frames)
+    } else {
+        (frame < frames).then_some(frame)
+    }
+}
+
+/// Peak level of each of `columns` equal slices of the frames `start..end` (counted from
+/// the track's start).
+fn peaks(track: &AudioTrack, start: i64, end: i64, columns: usize, sample_rate: u32) -> Vec<f32> {
+    let span = (end - start).max(1) as f64;
+    (0..columns)
+        .map(|column| {
+            let from = start + (span * column as f64 / columns as f64) as i64;
+            let to = (start + (span * (column + 1) as f64 / columns as f64) as i64).max(from + 1);
+            (from..to)
+                .filter_map(|frame| source_frame(track, frame, sample_rate))
+                .map(|i| ((track.samples[i * 2] + track.samples[i * 2 + 1]) * 0.5).abs())
+                .fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+/// Blends each level with the average of its neighbours.
+fn blend_neighbours(levels: &[f32], amount: f32) -> Vec<f32> {
+    if amount <= 0.0 || levels.len() < 2 {
+        return levels.to_vec();
+    }
+    (0..levels.len())
+        .map(|i| {
+            let previous = levels[i.saturating_sub(1)];
+            let next = levels[(i + 1).min(levels.len() - 1)];
+            levels[i] * (1.0 - amount) + (previous + next) * 0.5 * amount
+        })
+        .collect()
+}
+
+/// Paint for bars and lines.
+fn stroke_paint(color: Color, width: f32, rounded: bool) -> Paint {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color4f(color.to_color4f(), None);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(width);
+    paint.set_stroke_cap(if rounded {
+        PaintCap::Round
+    } else {
+        PaintCap::Butt
+    });
+    paint.set_stroke_join(PaintJoin::Round);
+    paint
+}
+
+/// Fills `bar`, with round ends if `rounded`.
+fn draw_bar(canvas: &Canvas, bar: Rect, color: Color, rounded: bool) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color4f(color.to_color4f(), None);
+    let radius = if rounded {
+        bar.width().min(bar.height()) / 2.0
+    } else {
+        0.0
+    };
+    canvas.draw_rrect(RRect::new_rect_xy(bar, radius, radius), &paint);
+}
+
+// --- Waveform Node ---
+/// Draws the waveform of a mixer track as a row of bars.
+///
+/// In overview mode the bars cover the whole track and the played part is drawn in
+/// `played_color`. In scrolling mode they cover `window` seconds around the part of the
+/// track playing now, which sits in the middle. Its frame hook calls
+/// [`WaveformNode::analyze`] with the mixer every frame.
+#[derive(Debug, Clone)]
+pub struct WaveformNode {
+    pub style: Style,
+    /// Mixer track shown.
+    pub track: usize,
+    pub mode: WaveformMode,
+    /// Number of bars across the node.
+    pub bars: usize,
+    /// Seconds of audio across the node in scrolling mode.
+    pub window: f64,
+    pub color: Color,
+    /// Colour of the bars already played; `None` draws them in `color`.
+    pub played_color: Option<Color>,
+    /// Space between bars, as a fraction of each bar's slot.
+    pub gap: f32,
+    pub rounded: bool,
+    /// Multiplies the levels before they are fitted to the node's height.
+    pub gain: f32,
+    /// How much each bar is blended with its neighbours (`0.0 - 1.0`).
+    pub smoothing: f32,
+    /// Current height (`0.0 - 1.0`) of each bar.
+    pub levels: Vec<f32>,
+    /// Playhead position across the node (`0.0 - 1.0`).
+    pub progress: f32,
+    pub opacity: Animated<f32>,
+    /// Peaks of the whole track, and the track, length and bar count they were found for.
+    overview: Vec<f32>,
+    overview_source: Option<(usize, usize, usize)>,
+}
+
+impl WaveformNode {
+    pub fn new(track: usize, mode: WaveformMode) -> Self {
+        Self {
+            style: Style::DEFAULT,
+            track,
+            mode,
+            bars: 64,
+            window: 4.0,
+            color: Color::new(1.0, 1.0, 1.0, 0.35),
+            played_color: Some(Color::WHITE),
+            gap: 0.3,
+            rounded: true,
+            gain: 1.0,
+            smoothing: 0.0,
+            levels: Vec::new(),
+            progress: 0.0,
+            opacity: Animated::new(1.0),
+            overview: Vec::new(),
+            overview_source: None,
+        }
+    }
+
+    /// Reads the levels of `track` at global time `time` (`None` when the track is gone).
+    ///
+    /// Returns whether the bars changed.
+    pub fn analyze(&mut self, track: Option<&AudioTrack>, time: f64, sample_rate: u32) -> bool {
+        let bars = self.bars.max(1);
+        let (peaks, progress) = match track {
+            None => (vec![0.0; bars], 0.0),
+            Some(track) => {
+                let elapsed = time - track.start_time;
+                match self.mode {
+                    WaveformMode::Overview => {
+                        let frames = playable_frames(track, sample_rate);
+                        let source = (self.track, frames, bars);
+                        if self.overview_source != Some(source) {
+                            self.overview = peaks(track, 0, frames as i64, bars, sample_rate);
+                            self.overview_source = Some(source);
+                        }
+                        let length = frames as f64 / sample_rate as f64;
+                        let progress = if length <= 0.0 {
+                            0.0
+                        } else if track.loop_audio && elapsed > 0.0 {
+                            elapsed.rem_euclid(length) / length
+                        } else {
+                            (elapsed / length).clamp(0.0, 1.0)
+                        };
+                        (self.overview.clone(), progress as f32)
+                    }
+                    WaveformMode::Scrolling => {
+                        let centre = (elapsed * sample_rate as f64) as i64;
+                        let half = (self.window.max(0.01) * sample_rate as f64 / 2.0) as i64;
+                        let peaks = peaks(track, centre - half, centre + half, bars, sample_rate);
+                        (peaks, 0.5)
+                    }
+                }
+            }
+        };
+
+        let levels: Vec<f32> = blend_neighbours(&peaks, self.smoothing)
+            .into_iter()
+            .map(|level| (level * self.gain).clamp(0.0, 1.0))
+            .collect();
+        let changed = levels != self.levels || progress != self.progress;
+        self.levels = levels;
+        self.progress = progress;
+        changed
+    }
+}
+
+impl Element for WaveformNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = self.opacity.current_value;
+        self.opacity.update(time);
+        before != self.opacity.current_value
+    }
+
+    fn frame(&mut self, frame: &FrameInfo) -> bool {
+        if frame.stage != FrameStage::Update {
+            return false;
+        }
+        let track = frame.mixer.tracks.get(self.track).and_then(Option::as_ref);
+        self.analyze(track, frame.global_time, frame.mixer.sample_rate)
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 && !self.levels.is_empty() {
+            let layered = opacity < 1.0;
+            if layered {
+                canvas.save_layer_alpha_f(rect, opacity);
+            }
+            let slot = rect.width() / self.levels.len() as f32;
+            let width = (slot * (1.0 - self.gap.clamp(0.0, 0.95))).max(1.0);
+            let middle = rect.center_y();
+            for (i, level) in self.levels.iter().enumerate() {
+                let centre = rect.left + slot * (i as f32 + 0.5);
+                let height = (level * rect.height()).max(1.0);
+                let played = (i as f32 + 0.5) / self.levels.len() as f32 <= self.progress;
+                let color = match self.played_color {
+                    Some(played_color) if played => played_color,
+                    _ => self.color,
+                };
+                let bar =
+                    Rect::from_xywh(centre - width / 2.0, middle - height / 2.0, width, height);
+                draw_bar(canvas, bar, color, self.rounded);
+            }
+            if layered {
+                canvas.restore();
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        if property == "opacity" {
+            self.opacity
+                .add_segment(start, target, duration, parse_easing(easing));
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "opacity" => self.opacity.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
+
+// --- Spectrum Node ---
+/// Draws the frequency spectrum of a mixer track as bars, a ring of bars or a line.
+///
+/// The spectrum is split into `bands` log-spaced bands between `min_frequency` and
+/// `max_frequency`, and each band's peak is mapped from decibels to a level. `smoothing`
+/// blends in the spectra of the preceding frames, weighting each frame back by another
+/// factor of `smoothing`. They are recomputed rather than remembered, so a frame looks the
+/// same however it's reached. Its frame hook calls [`SpectrumNode::analyze`] every frame.
+#[derive(Debug, Clone)]
+pub struct SpectrumNode {
+    pub style: Style,
+    /// Mixer track shown.
+    pub track: usize,
+    pub kind: SpectrumStyle,
+    /// Number of frequency bands (bars).
+    pub bands: usize,
+    /// Frequency range in Hz.
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+    /// Decibels shown as an empty band.
+    pub min_db: f32,
+    /// Decibels shown as a full band.
+    pub max_db: f32,
+    /// Weight (`0.0 - 0.99`) of each preceding frame; 0 shows only the current spectrum.
+    pub smoothing: f32,
+    pub color: Color,
+    /// Space between bars, as a fraction of each bar's slot.
+    pub gap: f32,
+    pub rounded: bool,
+    /// Stroke width of the line style.
+    pub line_width: f32,
+    /// Radius of the ring in the circle style, as a fraction of half the node's size.
+    pub inner_radius: f32,
+    /// Current level (`0.0 - 1.0`) of each band.
+    pub levels: Vec<f32>,
+    pub opacity: Animated<f32>,
+}
+
+impl SpectrumNode {
+    pub fn new(track: usize, kind: SpectrumStyle) -> Self {
+        Self {
+            style: Style::DEFAULT,
+            track,
+            kind,
+            bands: 32,
+            min_frequency: 40.0,
+            max_frequency: 16000.0,
+            min_db: -70.0,
+            max_db: -10.0,
+            smoothing: 0.5,
+            color: Color::WHITE,
+            gap: 0.3,
+            rounded: true,
+            line_width: 3.0,
+            inner_radius: 0.5,
+            levels: Vec::new(),
+            opacity: Animated::new(1.0),
+        }
+    }
+
+    /// Reads the spectrum of `track` at global time `time` (`None` when the track is gone).
+    ///
+    /// Returns whether the levels changed.
+    pub fn analyze(
+        &mut self,
+        track: Option<&AudioTrack>,
+        time: f64,
+        analyzer: &AudioAnalyzer,
+    ) -> bool {
+        let smoothing = self.smoothing.clamp(0.0, 0.99);
+        let windows = if smoothing > 0.0 {
+            // Enough frames for their weights to fall below 1%
+            ((0.01f32.ln() / smoothing.ln()).ceil() as usize + 1).min(MAX_SMOOTHING_WINDOWS)
+        } else {
+            1
+        };
+
+        let mut levels = vec![0.0; self.bands.max(1)];
+        if let Some(track) = track {
+            let mut total = 0.0;
+            for back in 0..windows {
+                let weight = smoothing.powi(back as i32);
+                total += weight;
+                let elapsed = time - track.start_time - back as f64 * SMOOTHING_STEP;
+                let frame = (elapsed * analyzer.sample_rate as f64).floor() as i64;
+                let Some(frame) = source_frame(track, frame, analyzer.sample_rate) else {
+                    continue;
+                };
+                let position = frame as f64 / analyzer.sample_rate as f64;
+                let spectrum = analyzer.compute_spectrum(&track.samples, position);
+                for (level, band) in levels.iter_mut().zip(self.band_levels(&spectrum, analyzer)) {
+                    *level += band * weight;
+                }
+            }
+            for level in &mut levels {
+                *level /= total;
+            }
+        }
+
+        let changed = levels != self.levels;
+        self.levels = levels;
+        changed
+    }
+
+    /// Level of each band: the peak of its FFT bins, mapped from decibels.
+    fn band_levels(&self, spectrum: &[f32], analyzer: &AudioAnalyzer) -> Vec<f32> {
+        let bands = self.bands.max(1);
+        let bin_hz = analyzer.sample_rate as f32 / analyzer.fft_size as f32;
+        let low = self.min_frequency.max(1.0);
+        let high = self.max_frequency.max(low * 1.01);
+        let octaves = (high / low).ln();
+        let range = (self.max_db - self.min_db).max(1.0);
+        (0..bands)
+            .map(|band| {
+                let edge = |i: usize| low * (octaves * i as f32 / bands as f32).exp();
+                let first = (edge(band) / bin_hz).floor() as usize;
+                let last = ((edge(band + 1) / bin_hz).ceil() as usize)
+                    .max(first + 1)
+                    .min(spectrum.len());
+                let peak = spectrum
+                    .get(first..last)
+                    .unwrap_or_default()
+                    .iter()
+                    .copied()
+                    .fold(0.0, f32::max);
+                let db = 20.0 * peak.max(1e-9).log10();
+                ((db - self.min_db) / range).clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+
+    fn draw_bars(&self, canvas: &Canvas, rect: Rect) {
+        let slot = rect.width() / self.levels.len() as f32;
+        let width = (slot * (1.0 - self.gap.clamp(0.0, 0.95))).max(1.0);
+        for (i, level) in self.levels.iter().enumerate() {
+            let height = (level * rect.height()).max(1.0);
+            let left = rect.left + slot * i as f32 + (slot - width) / 2.0;
+            let bar = Rect::from_xywh(left, rect.bottom - height, width, height);
+            draw_bar(canvas, bar, self.color, self.rounded);
+        }
+    }
+
+    fn draw_circle(&self, canvas: &Canvas, rect: Rect) {
+        let centre = rect.center();
+        let outer = rect.width().min(rect.height()) / 2.0;
+        let inner = outer * self.inner_radius.clamp(0.0, 1.0);
+        let count = self.levels.len() as f32;
+        let slot = std::f32::consts::TAU * inner.max(1.0) / count;
+        let width = (slot * (1.0 - self.gap.clamp(0.0, 0.95))).max(1.0);
+        // Round caps reach past the ends of the line by half its width
+        let cap = if self.rounded { width / 2.0 } else { 0.0 };
+        let paint = stroke_paint(self.color, width, self.rounded);
+        for (i, level) in self.levels.iter().enumerate() {
+            let angle = std::f32::consts::TAU * i as f32 / count - std::f32::consts::FRAC_PI_2;
+            let direction = Point::new(angle.cos(), angle.sin());
+            let length = ((outer - inner - cap * 2.0) * level).max(0.0);
+            let start = centre + direction * (inner + cap);
+            let end = centre + direction * (inner + cap + length);
+            canvas.draw_line(start, end, &paint);
+        }
+    }
+
+    fn draw_line(&self, canvas: &Canvas, rect: Rect) {
+        let inset = self.line_width / 2.0;
+        let height = (rect.height() - self.line_width).max(0.0);
+        let step = rect.width() / (self.levels.len().max(2) - 1) as f32;
+        let mut path = Path::new();
+        for (i, level) in self.levels.iter().enumerate() {
+            let point = Point::new(
+                rect.left + step * i as f32,
+                rect.bottom - inset - level * height,
+            );
+            if i == 0 {
+                path.move_to(point);
+            } else {
+                path.line_to(point);
+            }
+        }
+        canvas.draw_path(
+            &path,
+            &stroke_paint(self.color, self.line_width, self.rounded),
+        );
+    }
+}
+
+impl Element for SpectrumNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn layout_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn set_layout_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn update(&mut self, time: f64) -> bool {
+        let before = self.opacity.current_value;
+        self.opacity.update(time);
+        before != self.opacity.current_value
+    }
+
+    fn frame(&mut self, frame: &FrameInfo) -> bool {
+        if frame.stage != FrameStage::Update {
+            return false;
+        }
+        let track = frame.mixer.tracks.get(self.track).and_then(Option::as_ref);
+        self.analyze(track, frame.global_time, frame.analyzer)
+    }
+
+    fn render(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        opacity: f32,
+        draw_children: &mut dyn FnMut(&Canvas),
+    ) -> Result<(), crate::RenderError> {
+        let opacity = self.opacity.current_value * opacity;
+        if opacity > 0.0 && !self.levels.is_empty() {
+            let layered = opacity < 1.0;
+            if layered {
+                canvas.save_layer_alpha_f(rect, opacity);
+            }
+            match self.kind {
+                SpectrumStyle::Bars => self.draw_bars(canvas, rect),
+                SpectrumStyle::Circle => self.draw_circle(canvas, rect),
+                SpectrumStyle::Line => self.draw_line(canvas, rect),
+            }
+            if layered {
+                canvas.restore();
+            }
+        }
+        draw_children(canvas);
+        Ok(())
+    }
+
+    fn animate_property(
+        &mut self,
+        property: &str,
+        start: f32,
+        target: f32,
+        duration: f64,
+        easing: &str,
+    ) {
+        if property == "opacity" {
+            self.opacity
+                .add_segment(start, target, duration, parse_easing(easing));
+        }
+    }
+
+    fn get_property(&self, property: &str) -> Option<f32> {
+        match property {
+            "opacity" => Some(self.opacity.current_value),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: f32) -> bool {
+        match property {
+            "opacity" => self.opacity.current_value = value,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -5,15 +5,20 @@
 //! ## Responsibilities
 //! - **Node Storage**: `Vec<Option<SceneNode>>` arena with `NodeId` indices.
 //! - **Hierarchy**: Parent-child relationships via `children` and `parent`.
-//! - **Node Operations**: Add, remove, reparent nodes.
+//! - **Node Operations**: Add, remove, reparent and clone nodes.
+//! - **Active Set**: Nodes visited this frame, so per-frame passes skip inactive scenes.
+//! - **Symbols**: Instances linked to a detached master subtree, rebuilt when it is edited.
 //!
 //! ## Key Types
 //! - `SceneGraph`: The arena container.
 //! - `SceneNode`: Wraps an `Element` with layout and hierarchy data.
 //! - `NodeId`: A `usize` index into the arena (defined in `types.rs`).
 
-use crate::element::Element;
-use crate::types::{NodeId, PathAnimationState, Transform};
+use crate::animation::Animated;
+use crate::element::{Element, TextSpan};
+use crate::systems::render_cache::RenderCache;
+use crate::types::{ClipPath, MatteMode, NodeId, PathAnimationState, Transform};
+use std::collections::HashMap;
 
 /// Runtime binding of an audio analysis value to a node property.
 ///
@@ -36,6 +41,125 @@ pub struct AudioBinding {
     pub prev_value: f32,
 }
 
+/// One term of a `PropertyDriver`: `weight * source.property`.
+#[derive(Clone, Debug)]
+pub struct DriverInput {
+    /// Node to read from
+    pub source: NodeId,
+    /// Property to read on the source node
+    pub property: String,
+    /// Factor applied before the terms are summed
+    pub weight: f32,
+}
+
+/// Runtime link that drives a node property from other nodes' properties.
+///
+/// The driven value is `clamp(sum(weight * source(t - delay)) * multiplier + offset, min, max)`.
+#[derive(Clone, Debug)]
+pub struct PropertyDriver {
+    /// Property to set on the driven node: "x", "y", "scale", "opacity", "content", ...
+    pub property: String,
+    /// Terms summed into the raw value
+    pub inputs: Vec<DriverInput>,
+    /// Factor applied to the summed inputs
+    pub multiplier: f32,
+    /// Constant added after the multiplier
+    pub offset: f32,
+    /// Lower clamp bound
+    pub min: Option<f32>,
+    /// Upper clamp bound
+    pub max: Option<f32>,
+    /// Lag behind the sources, in seconds. Sources are evaluated at `t - delay`, so the
+    /// value doesn't depend on which frames were rendered before.
+    pub delay: f64,
+    /// Decimal places used when driving text `content`
+    pub decimals: usize,
+    /// Last text written when driving `content` (avoids re-shaping unchanged text)
+    pub last_text: Option<String>,
+}
+
+impl PropertyDriver {
+    /// Creates a one-to-one driver (`multiplier = 1`, no offset, clamp or delay).
+    pub fn new(property: &str, source: NodeId, source_property: &str) -> Self {
+        Self::with_inputs(
+            property,
+            vec![DriverInput {
+                source,
+                property: source_property.to_string(),
+                weight: 1.0,
+            }],
+        )
+    }
+
+    /// Creates a driver summing `inputs` (`multiplier = 1`, no offset, clamp or delay).
+    pub fn with_inputs(property: &str, inputs: Vec<DriverInput>) -> Self {
+        Self {
+            property: property.to_string(),
+            inputs,
+            multiplier: 1.0,
+            offset: 0.0,
+            min: None,
+            max: None,
+            delay: 0.0,
+            decimals: 0,
+            last_text: None,
+        }
+    }
+
+    /// Maps the summed inputs to the output value.
+    pub fn map(&self, raw: f32) -> f32 {
+        let mut value = raw * self.multiplier + self.offset;
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+        value
+    }
+
+    /// Writes the output value to the driven node.
+    pub fn apply(&mut self, node: &mut SceneNode, value: f32) {
+        if self.property == "content" {
+            let text = format!("{:.*}", self.decimals, value);
+            if self.last_text.as_deref() == Some(text.as_str()) {
+                return;
+            }
+            node.element.modify_text_spans(&|spans| {
+                if spans.is_empty() {
+                    spans.push(TextSpan {
+                        text: text.clone(),
+                        ..Default::default()
+                    });
+                } else {
+                    spans.truncate(1);
+                    spans[0].text = text.clone();
+                }
+            });
+            node.dirty_style = true;
+            self.last_text = Some(text);
+        } else {
+            node.set_property(&self.property, value);
+        }
+    }
+}
+
+/// A copy of a symbol master that follows edits to it (`add_instance`).
+///
+/// When the master subtree changes, the copy is rebuilt from it in place and
+/// `overrides` are applied again (see `SceneGraph::sync_subtree`).
+#[derive(Clone, Debug)]
+pub struct SymbolInstance {
+    /// Root of the detached master subtree
+    pub master: NodeId,
+    /// Root of the copy
+    pub root: NodeId,
+    /// Per-instance overrides, as passed to `add_instance`
+    pub overrides: rhai::Map,
+    /// `SceneGraph::subtree_revision` of the master when the copy was last synced
+    pub synced_revision: u64,
+}
+
 /// A wrapper around an `Element` that adds scene graph relationships and state.
 ///
 /// `SceneNode` encapsulates the specific logic for hierarchy, layout positioning,
@@ -52,6 +176,8 @@ pub struct SceneNode {
     pub layout_rect: skia_safe::Rect,
     /// The local time for the current frame (computed during update pass).
     pub local_time: f64,
+    /// Length of the scene the node plays in for the current frame (set with `local_time`).
+    pub scene_duration: f64,
     /// The global time when this node was last visited/prepared for update.
     pub last_visit_time: f64,
 
@@ -61,6 +187,10 @@ pub struct SceneNode {
 
     // Masking & Compositing
     pub mask_node: Option<NodeId>,
+    /// How `mask_node` is applied (alpha/luma, optionally inverted).
+    pub mask_mode: MatteMode,
+    /// Geometry-based clip applied to this node and its children (`set_clip_path`).
+    pub clip_path: Option<ClipPath>,
     pub blend_mode: skia_safe::BlendMode,
 
     /// Explicit render order z-index (default: 0).
@@ -72,9 +202,34 @@ pub struct SceneNode {
 
     /// Audio-reactive bindings for this node
     pub audio_bindings: Vec<AudioBinding>,
+
+    /// Links that set this node's properties from other nodes each frame
+    pub drivers: Vec<PropertyDriver>,
+
+    /// Script callbacks invoked every frame with the node's local time (`node.on_frame`).
+    pub frame_callbacks: Vec<rhai::FnPtr>,
+
+    /// Bumped when the node's appearance changes (see `mark_content_changed`) and each
+    /// time its style is synced while dirty; part of its render cache key.
+    pub content_version: u64,
+    /// Fingerprint of this subtree for the current frame, or `None` if it must be redrawn.
+    pub cache_key: Option<u64>,
+    /// Recording of this subtree, replayed while `cache_key` is unchanged.
+    pub render_cache: RenderCache,
+    /// Bumped by every `SceneGraph::get_node_mut`; lets symbol instances notice edits
+    /// to their master.
+    pub revision: u64,
 }
 
 impl SceneNode {
+    /// Invalidates the recording of this node's subtree without touching its layout.
+    ///
+    /// Use this for changes that only affect drawing; `dirty_style` re-syncs the
+    /// layout style and should only be set when that style may have changed.
+    pub fn mark_content_changed(&mut self) {
+        self.content_version += 1;
+    }
+
     /// Creates a new SceneNode wrapping the given Element.
     pub fn new(element: Box<dyn Element>) -> Self {
         Self {
@@ -83,15 +238,140 @@ impl SceneNode {
             parent: None,
             layout_rect: skia_safe::Rect::default(),
             local_time: 0.0,
+            scene_duration: 0.0,
             last_visit_time: -1.0,
             path_animation: None,
             transform: Transform::new(),
             mask_node: None,
+            mask_mode: MatteMode::Alpha,
+            clip_path: None,
             blend_mode: skia_safe::BlendMode::SrcOver,
             z_index: 0,
             dirty_style: true,
             audio_bindings: Vec::new(),
+            drivers: Vec::new(),
+            frame_callbacks: Vec::new(),
+            content_version: 0,
+            cache_key: None,
+            render_cache: RenderCache::default(),
+            revision: 0,
+        }
+    }
+
+    /// Maps the node's local coordinates into its parent's: layout position, then the
+    /// transform (rotation, scale and skew around the pivot).
+    pub fn local_matrix(&self) -> skia_safe::Matrix {
+        let t = &self.transform;
+        let pivot_x = self.layout_rect.width() * t.pivot_x;
+        let pivot_y = self.layout_rect.height() * t.pivot_y;
+
+        let mut matrix = skia_safe::Matrix::translate((
+            self.layout_rect.left + t.translate_x.current_value + pivot_x,
+            self.layout_rect.top + t.translate_y.current_value + pivot_y,
+        ));
+        matrix.pre_rotate(t.rotation.current_value, None);
+        matrix.pre_scale((t.scale_x.current_value, t.scale_y.current_value), None);
+        matrix.pre_skew(
+            (
+                t.skew_x.current_value.to_radians().tan(),
+                t.skew_y.current_value.to_radians().tan(),
+            ),
+            None,
+        );
+        matrix.pre_translate((-pivot_x, -pivot_y));
+        matrix
+    }
+
+    /// The clip path value named `clip_feather` or `clip_expansion`, if the node is clipped.
+    pub fn clip_property_mut(&mut self, property: &str) -> Option<&mut Animated<f32>> {
+        let clip = self.clip_path.as_mut()?;
+        match property {
+            "clip_feather" => Some(&mut clip.feather),
+            "clip_expansion" => Some(&mut clip.expansion),
+            _ => None,
+        }
+    }
+
+    /// Reads a property by name: transform values, layout size, clip path, then element properties.
+    pub fn get_property(&self, property: &str) -> Option<f32> {
+        let t = &self.transform;
+        match property {
+            "x" => Some(t.translate_x.current_value),
+            "y" => Some(t.translate_y.current_value),
+            "scale" | "scale_x" => Some(t.scale_x.current_value),
+            "scale_y" => Some(t.scale_y.current_value),
+            "rotation" => Some(t.rotation.current_value),
+            "skew_x" => Some(t.skew_x.current_value),
+            "skew_y" => Some(t.skew_y.current_value),
+            "width" => Some(self.layout_rect.width()),
+            "height" => Some(self.layout_rect.height()),
+            "clip_feather" => self.clip_path.as_ref().map(|c| c.feather.current_value),
+            "clip_expansion" => self.clip_path.as_ref().map(|c| c.expansion.current_value),
+            _ => self.element.get_property(property),
+        }
+    }
+
+    /// Reads a property as it is at local `time`, replaying keyframes rather than
+    /// reading the current value.
+    ///
+    /// Layout size and element properties the element can't replay (see
+    /// `Element::property_at`) are read as they are now.
+    pub fn property_at(&self, property: &str, time: f64) -> Option<f32> {
+        let t = &self.transform;
+        let value = match property {
+            "x" => t.translate_x.value_at(time),
+            "y" => t.translate_y.value_at(time),
+            "scale" | "scale_x" => t.scale_x.value_at(time),
+            "scale_y" => t.scale_y.value_at(time),
+            "rotation" => t.rotation.value_at(time),
+            "skew_x" => t.skew_x.value_at(time),
+            "skew_y" => t.skew_y.value_at(time),
+            "clip_feather" => self.clip_path.as_ref()?.feather.value_at(time),
+            "clip_expansion" => self.clip_path.as_ref()?.expansion.value_at(time),
+            _ => {
+                return self
+                    .element
+                    .property_at(property, time)
+                    .or_else(|| self.get_property(property))
+            }
+        };
+        Some(value)
+    }
+
+    /// Overrides a transform value or element property for the current frame.
+    ///
+    /// Returns `false` if the property is unknown for this node.
+    pub fn set_property(&mut self, property: &str, value: f32) -> bool {
+        let t = &mut self.transform;
+        match property {
+            "x" => t.translate_x.current_value = value,
+            "y" => t.translate_y.current_value = value,
+            "scale" => {
+                t.scale_x.current_value = value;
+                t.scale_y.current_value = value;
+            }
+            "scale_x" => t.scale_x.current_value = value,
+            "scale_y" => t.scale_y.current_value = value,
+            "rotation" => t.rotation.current_value = value,
+            "skew_x" => t.skew_x.current_value = value,
+            "skew_y" => t.skew_y.current_value = value,
+            "clip_feather" | "clip_expansion" => {
+                let Some(clip_value) = self.clip_property_mut(property) else {
+                    return false;
+                };
+                clip_value.current_value = value;
+            }
+            _ => {
+                if !self.element.set_property(property, value) {
+                    return false;
+                }
+                // Intrinsically sized elements (text) must be re-measured.
+                if self.element.needs_measure() {
+                    self.dirty_style = true;
+                }
+            }
         }
+        true
     }
 }
 
@@ -104,6 +384,14 @@ pub struct SceneGraph {
     pub nodes: Vec<Option<SceneNode>>,
     /// Indices of nodes that have been removed and can be reused.
     pub free_indices: Vec<usize>,
+    /// Nodes visited by the last `Director::update` (scene roots, descendants and masks), in id order.
+    pub active_nodes: Vec<NodeId>,
+    /// Scene roots visited by the last `Director::update`.
+    pub active_roots: Vec<NodeId>,
+    /// Nodes destroyed since the layout engine last synced; it drops their layout nodes.
+    pub removed_nodes: Vec<NodeId>,
+    /// Symbol instances kept in sync with their masters.
+    pub symbol_instances: Vec<SymbolInstance>,
 }
 
 impl SceneGraph {
@@ -111,21 +399,197 @@ impl SceneGraph {
         Self {
             nodes: Vec::new(),
             free_indices: Vec::new(),
+            active_nodes: Vec::new(),
+            active_roots: Vec::new(),
+            removed_nodes: Vec::new(),
+            symbol_instances: Vec::new(),
         }
     }
 
     /// Adds a new element to the scene graph and returns its ID.
     pub fn add_node(&mut self, element: Box<dyn Element>) -> NodeId {
+        self.insert_node(SceneNode::new(element))
+    }
+
+    /// Stores a fully built `SceneNode` in the arena, reusing a free slot if possible.
+    fn insert_node(&mut self, node: SceneNode) -> NodeId {
         if let Some(id) = self.free_indices.pop() {
-            self.nodes[id] = Some(SceneNode::new(element));
+            self.nodes[id] = Some(node);
             id
         } else {
             let id = self.nodes.len();
-            self.nodes.push(Some(SceneNode::new(element)));
+            self.nodes.push(Some(node));
             id
         }
     }
 
+    /// Deep-copies a node and its whole subtree, returning the ID of the new root.
+    ///
+    /// Elements, transforms, animations, audio bindings and mask nodes are all
+    /// duplicated. Drivers reading from a node inside the subtree read from its copy
+    /// instead. The copy is detached (`parent` is `None`); the caller decides
+    /// where to attach it.
+    pub fn clone_subtree(&mut self, id: NodeId) -> Option<NodeId> {
+        let mut ids = HashMap::new();
+        let new_id = self.clone_nodes(id, &mut ids)?;
+        let copies: Vec<NodeId> = ids.values().copied().collect();
+        self.remap_driver_sources(&copies, &ids);
+        Some(new_id)
+    }
+
+    /// Copies a subtree for `clone_subtree`, recording each original ID's copy in `ids`.
+    fn clone_nodes(&mut self, id: NodeId, ids: &mut HashMap<NodeId, NodeId>) -> Option<NodeId> {
+        let mut copy = self.get_node(id)?.clone();
+        let children = std::mem::take(&mut copy.children);
+        let mask = copy.mask_node.take();
+        copy.parent = None;
+        copy.layout_rect = skia_safe::Rect::default();
+        copy.last_visit_time = -1.0;
+        copy.dirty_style = true;
+
+        let new_id = self.insert_node(copy);
+        ids.insert(id, new_id);
+
+        for child in children {
+            if let Some(child_copy) = self.clone_nodes(child, ids) {
+                self.add_child(new_id, child_copy);
+            }
+        }
+
+        // Masks are owned by their node but are not part of `children`.
+        if let Some(mask_copy) = mask.and_then(|m| self.clone_nodes(m, ids)) {
+            if let Some(m_node) = self.get_node_mut(mask_copy) {
+                m_node.parent = Some(new_id);
+            }
+            if let Some(n) = self.get_node_mut(new_id) {
+                n.mask_node = Some(mask_copy);
+            }
+        }
+
+        Some(new_id)
+    }
+
+    /// Points the driver inputs of `nodes` that read from a key of `ids` at the mapped node.
+    fn remap_driver_sources(&mut self, nodes: &[NodeId], ids: &HashMap<NodeId, NodeId>) {
+        for &id in nodes {
+            let Some(node) = self.nodes.get_mut(id).and_then(|n| n.as_mut()) else {
+                continue;
+            };
+            for input in node.drivers.iter_mut().flat_map(|d| d.inputs.iter_mut()) {
+                if let Some(&mapped) = ids.get(&input.source) {
+                    input.source = mapped;
+                }
+            }
+        }
+    }
+
+    /// Fingerprint of a subtree's structure and edits (masks included).
+    ///
+    /// It changes whenever a node in the subtree is accessed mutably (see
+    /// `SceneNode::revision`), or nodes are added, removed or reordered.
+    pub fn subtree_revision(&self, id: NodeId) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.get_node(id) else {
+                continue;
+            };
+            (id, node.revision, node.children.len()).hash(&mut hasher);
+            stack.extend(node.mask_node);
+            stack.extend(node.children.iter().rev());
+        }
+        hasher.finish()
+    }
+
+    /// Rebuilds `target`'s subtree as a copy of `source`'s, keeping node IDs where the
+    /// two trees line up.
+    ///
+    /// Matching children (by position) and masks are synced recursively; missing ones
+    /// are cloned from `source`, extra ones destroyed. The `target` root keeps its
+    /// parent and its placement: transform, path animation, z-index, drivers, audio
+    /// bindings and frame callbacks. Drivers copied from `source`'s descendants read
+    /// from the matching nodes under `target`.
+    pub fn sync_subtree(&mut self, source: NodeId, target: NodeId) {
+        let mut ids = HashMap::new();
+        self.sync_node(source, target, true, &mut ids);
+        let synced: Vec<NodeId> = ids.values().copied().filter(|&id| id != target).collect();
+        self.remap_driver_sources(&synced, &ids);
+    }
+
+    fn sync_node(
+        &mut self,
+        source: NodeId,
+        target: NodeId,
+        keep_placement: bool,
+        ids: &mut HashMap<NodeId, NodeId>,
+    ) {
+        let Some(mut copy) = self.get_node(source).cloned() else {
+            return;
+        };
+        let source_children = std::mem::take(&mut copy.children);
+        let source_mask = copy.mask_node.take();
+
+        let Some(old) = self.get_node_mut(target) else {
+            return;
+        };
+        let target_children = std::mem::take(&mut old.children);
+        let target_mask = old.mask_node;
+        if keep_placement {
+            copy.transform = old.transform.clone();
+            copy.path_animation = old.path_animation.take();
+            copy.z_index = old.z_index;
+            copy.drivers = std::mem::take(&mut old.drivers);
+            copy.audio_bindings = std::mem::take(&mut old.audio_bindings);
+            copy.frame_callbacks = std::mem::take(&mut old.frame_callbacks);
+        }
+        copy.parent = old.parent;
+        copy.children = target_children.clone();
+        copy.mask_node = target_mask;
+        copy.layout_rect = old.layout_rect;
+        copy.local_time = old.local_time;
+        copy.scene_duration = old.scene_duration;
+        copy.last_visit_time = old.last_visit_time;
+        copy.content_version = old.content_version + 1;
+        copy.revision = old.revision + 1;
+        copy.cache_key = None;
+        copy.render_cache = RenderCache::default();
+        copy.dirty_style = true;
+        *old = copy;
+        ids.insert(source, target);
+
+        for (i, &child) in source_children.iter().enumerate() {
+            match target_children.get(i) {
+                Some(&existing) => self.sync_node(child, existing, false, ids),
+                None => {
+                    if let Some(child_copy) = self.clone_nodes(child, ids) {
+                        self.add_child(target, child_copy);
+                    }
+                }
+            }
+        }
+        for &extra in target_children.iter().skip(source_children.len()) {
+            self.destroy_node(extra);
+        }
+
+        match (source_mask, target_mask) {
+            (Some(mask), Some(existing)) => self.sync_node(mask, existing, false, ids),
+            (Some(mask), None) => {
+                if let Some(mask_copy) = self.clone_nodes(mask, ids) {
+                    if let Some(m_node) = self.get_node_mut(mask_copy) {
+                        m_node.parent = Some(target);
+                    }
+                    if let Some(n) = self.get_node_mut(target) {
+                        n.mask_node = Some(mask_copy);
+                    }
+                }
+            }
+            (None, Some(existing)) => self.destroy_node(existing),
+            (None, None) => {}
+        }
+    }
+
     /// Recursively destroys a node and its children, freeing their indices for reuse.
     pub fn destroy_node(&mut self, id: NodeId) {
         // 1. Check if node exists (and isn't already deleted)
@@ -134,24 +598,37 @@ impl SceneGraph {
         }
 
         // 2. Collect IDs to process (to avoid holding borrows on self.nodes)
-        let (parent_id, children_ids) = {
+        let (parent_id, children_ids, mask_id) = {
             let node = self.nodes[id].as_ref().unwrap();
-            (node.parent, node.children.clone())
+            (node.parent, node.children.clone(), node.mask_node)
         };
 
         // 3. Detach from Parent
         if let Some(pid) = parent_id {
             self.remove_child(pid, id);
+            if let Some(p_node) = self.get_node_mut(pid) {
+                if p_node.mask_node == Some(id) {
+                    p_node.mask_node = None;
+                }
+            }
         }
 
-        // 4. Recursively destroy children
+        // 4. Recursively destroy children and the mask
         for child_id in children_ids {
             self.destroy_node(child_id);
         }
+        if let Some(mask_id) = mask_id {
+            self.destroy_node(mask_id);
+        }
 
-        // 5. Free the slot
+        // 5. Unlink symbol instances built from or into this node
+        self.symbol_instances
+            .retain(|s| s.master != id && s.root != id);
+
+        // 6. Free the slot
         self.nodes[id] = None;
         self.free_indices.push(id);
+        self.removed_nodes.push(id);
     }
 
     /// Establishes a parent-child relationship between two nodes.
@@ -174,9 +651,124 @@ impl SceneGraph {
         }
     }
 
-    /// Returns a mutable reference to the SceneNode.
+    /// Returns true if `id` is `root` or lies anywhere inside `root`'s subtree (masks included).
+    pub fn is_in_subtree(&self, id: NodeId, root: NodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(cid) = current {
+            if cid == root {
+                return true;
+            }
+            current = self.get_node(cid).and_then(|n| n.parent);
+        }
+        false
+    }
+
+    /// Detaches a node from its parent, whether it is a regular child or a mask.
+    ///
+    /// The node stays in the arena with `parent` set to `None`. Returns the old parent.
+    pub fn detach(&mut self, id: NodeId) -> Option<NodeId> {
+        let parent = self.get_node_mut(id)?.parent.take()?;
+        self.remove_child(parent, id);
+        if let Some(p_node) = self.get_node_mut(parent) {
+            if p_node.mask_node == Some(id) {
+                p_node.mask_node = None;
+            }
+            p_node.dirty_style = true;
+        }
+        Some(parent)
+    }
+
+    /// Moves a node (and its subtree) under `new_parent` at `index`.
+    ///
+    /// `None` or an out-of-range index appends the node. Returns `false` without
+    /// changing anything if either node is missing or `new_parent` lies inside
+    /// the moved subtree.
+    pub fn reparent(&mut self, id: NodeId, new_parent: NodeId, index: Option<usize>) -> bool {
+        if self.get_node(id).is_none()
+            || self.get_node(new_parent).is_none()
+            || self.is_in_subtree(new_parent, id)
+        {
+            return false;
+        }
+        self.detach(id);
+        self.insert_child(new_parent, id, index);
+        true
+    }
+
+    /// Moves a node so it sits directly before `sibling` in `sibling`'s parent.
+    pub fn move_before(&mut self, id: NodeId, sibling: NodeId) -> bool {
+        self.move_next_to(id, sibling, 0)
+    }
+
+    /// Moves a node so it sits directly after `sibling` in `sibling`'s parent.
+    pub fn move_after(&mut self, id: NodeId, sibling: NodeId) -> bool {
+        self.move_next_to(id, sibling, 1)
+    }
+
+    fn move_next_to(&mut self, id: NodeId, sibling: NodeId, offset: usize) -> bool {
+        let Some(parent) = self.get_node(sibling).and_then(|n| n.parent) else {
+            return false;
+        };
+        if id == sibling || self.get_node(id).is_none() || self.is_in_subtree(parent, id) {
+            return false;
+        }
+        self.detach(id);
+        let Some(pos) = self
+            .get_node(parent)
+            .and_then(|p| p.children.iter().position(|&c| c == sibling))
+        else {
+            // `sibling` is a mask, not a regular child: fall back to appending.
+            self.insert_child(parent, id, None);
+            return true;
+        };
+        self.insert_child(parent, id, Some(pos + offset));
+        true
+    }
+
+    /// Moves a node to the end of its parent's children (drawn last among equal `z_index`).
+    pub fn bring_to_front(&mut self, id: NodeId) {
+        if let Some(parent) = self.get_node(id).and_then(|n| n.parent) {
+            if self
+                .get_node(parent)
+                .is_some_and(|p| p.children.contains(&id))
+            {
+                self.detach(id);
+                self.insert_child(parent, id, None);
+            }
+        }
+    }
+
+    /// Moves a node to the start of its parent's children (drawn first among equal `z_index`).
+    pub fn send_to_back(&mut self, id: NodeId) {
+        if let Some(parent) = self.get_node(id).and_then(|n| n.parent) {
+            if self
+                .get_node(parent)
+                .is_some_and(|p| p.children.contains(&id))
+            {
+                self.detach(id);
+                self.insert_child(parent, id, Some(0));
+            }
+        }
+    }
+
+    /// Inserts an already detached node into `parent`'s children and flags both for layout sync.
+    fn insert_child(&mut self, parent: NodeId, child: NodeId, index: Option<usize>) {
+        if let Some(p_node) = self.get_node_mut(parent) {
+            let len = p_node.children.len();
+            p_node.children.insert(index.unwrap_or(len).min(len), child);
+            p_node.dirty_style = true;
+        }
+        if let Some(c_node) = self.get_node_mut(child) {
+            c_node.parent = Some(parent);
+            c_node.dirty_style = true;
+        }
+    }
+
+    /// Returns a mutable reference to the SceneNode, bumping its `revision`.
     pub fn get_node_mut(&mut self, id: NodeId) -> Option<&mut SceneNode> {
-        self.nodes.get_mut(id).and_then(|n| n.as_mut())
+        let node = self.nodes.get_mut(id).and_then(|n| n.as_mut())?;
+        node.revision += 1;
+        Some(node)
     }
 
     /// Returns a shared reference to the SceneNode.
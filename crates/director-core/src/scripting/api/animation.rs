@@ -6,17 +6,26 @@
 //! - **Keyframe Animation**: `animate` for property tweening
 //! - **Spring Animation**: `spring` for physics-based motion
 //! - **Path Animation**: `path_animate` for SVG path following
+//! - **Path Morphing**: `animate_path` to morph a shape into another outline
+//! - **Chart Data**: `animate_data` to move a chart to another dataset
+//! - **Code Edits**: `animate_code` and `focus_lines` for code nodes
+//! - **Counters**: `count_to` to tween or spring a counter to a number
 //! - **Text Animation**: `add_animator` for per-glyph animations
 //! - **Instant Setters**: `set_blur` for immediate property changes
+//! - **Property Drivers**: `drive` to link a property to other nodes' properties
 
 use crate::animation::Animated;
+use crate::node::{ChartNode, CodeNode, ShapeNode, TextNode};
+use crate::scene::{DriverInput, PropertyDriver};
 use crate::types::PathAnimationState;
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 use skia_safe::Path;
+use std::sync::Arc;
 use tracing::error;
 
 use super::super::types::NodeHandle;
 use super::super::utils::{parse_easing, parse_spring_config};
+use super::nodes::{number, parse_chart_data, parse_line_numbers};
 
 /// Register animation-related Rhai functions.
 pub fn register(engine: &mut Engine) {
@@ -72,6 +81,11 @@ pub fn register(engine: &mut Engine) {
                             .translate_y
                             .add_segment(start as f32, end as f32, dur, ease_fn)
                     }
+                    "clip_feather" | "clip_expansion" => {
+                        if let Some(value) = n.clip_property_mut(prop) {
+                            value.add_segment(start as f32, end as f32, dur, ease_fn);
+                        }
+                    }
                     _ => {
                         n.element
                             .animate_property(prop, start as f32, end as f32, dur, ease);
@@ -133,6 +147,11 @@ pub fn register(engine: &mut Engine) {
                             .translate_y
                             .add_segment(start as f32, end as f32, dur, ease_fn)
                     }
+                    "clip_feather" | "clip_expansion" => {
+                        if let Some(value) = n.clip_property_mut(prop) {
+                            value.add_segment(start as f32, end as f32, dur, ease_fn);
+                        }
+                    }
                     _ => {
                         n.element
                             .animate_property(prop, start as f32, end as f32, dur, ease);
@@ -266,6 +285,133 @@ pub fn register(engine: &mut Engine) {
         },
     );
 
+    // ========== ANIMATE_PATH (Shape Morphing) ==========
+    engine.register_fn(
+        "animate_path",
+        |node: &mut NodeHandle,
+         svg: &str,
+         dur: f64,
+         ease: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            let path =
+                Path::from_svg(svg).ok_or_else(|| format!("Invalid SVG path data '{}'", svg))?;
+            let mut d = node.director.lock().unwrap();
+            let shape = d
+                .scene
+                .get_node_mut(node.id)
+                .and_then(|n| n.element.as_any_mut().downcast_mut::<ShapeNode>())
+                .ok_or("animate_path is only supported on shapes")?;
+            shape.morph_to(path, dur, parse_easing(ease));
+            Ok(())
+        },
+    );
+
+    // ========== ANIMATE_DATA (Chart Transitions) ==========
+    engine.register_fn(
+        "animate_data",
+        |node: &mut NodeHandle,
+         data: rhai::Dynamic,
+         dur: f64,
+         ease: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            animate_chart_data(node, &data, 0.0, dur, ease)
+        },
+    );
+
+    engine.register_fn(
+        "animate_data",
+        |node: &mut NodeHandle,
+         data: rhai::Dynamic,
+         dur: f64,
+         ease: &str,
+         delay: f64|
+         -> Result<(), Box<EvalAltResult>> {
+            animate_chart_data(node, &data, delay, dur, ease)
+        },
+    );
+
+    // ========== ANIMATE_CODE / FOCUS_LINES (Code Nodes) ==========
+    engine.register_fn(
+        "animate_code",
+        |node: &mut NodeHandle,
+         code: &str,
+         dur: f64,
+         ease: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            with_code_node(node, |c| c.animate_code(code, 0.0, dur, parse_easing(ease)))
+        },
+    );
+
+    engine.register_fn(
+        "animate_code",
+        |node: &mut NodeHandle,
+         code: &str,
+         dur: f64,
+         ease: &str,
+         delay: f64|
+         -> Result<(), Box<EvalAltResult>> {
+            with_code_node(node, |c| {
+                c.animate_code(code, delay, dur, parse_easing(ease))
+            })
+        },
+    );
+
+    engine.register_fn(
+        "focus_lines",
+        |node: &mut NodeHandle,
+         lines: rhai::Dynamic,
+         dur: f64,
+         ease: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            let lines = parse_line_numbers(&lines)?;
+            with_code_node(node, |c| {
+                c.focus_lines(&lines, 0.0, dur, parse_easing(ease))
+            })
+        },
+    );
+
+    engine.register_fn(
+        "focus_lines",
+        |node: &mut NodeHandle,
+         lines: rhai::Dynamic,
+         dur: f64,
+         ease: &str,
+         delay: f64|
+         -> Result<(), Box<EvalAltResult>> {
+            let lines = parse_line_numbers(&lines)?;
+            with_code_node(node, |c| {
+                c.focus_lines(&lines, delay, dur, parse_easing(ease))
+            })
+        },
+    );
+
+    // ========== COUNT_TO (Number Counters) ==========
+    engine.register_fn(
+        "count_to",
+        |node: &mut NodeHandle,
+         target: rhai::Dynamic,
+         dur: f64,
+         ease: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            count_to(node, &target, |value, target| {
+                value.add_keyframe(target, dur, parse_easing(ease))
+            })
+        },
+    );
+
+    engine.register_fn(
+        "count_to",
+        |node: &mut NodeHandle,
+         target: rhai::Dynamic,
+         config: rhai::Map|
+         -> Result<(), Box<EvalAltResult>> {
+            let spring_conf = parse_spring_config(&config);
+            count_to(node, &target, |value, target| {
+                value.add_spring(target, spring_conf)
+            })
+        },
+    );
+
     // ========== SET_BLUR ==========
     engine.register_fn("set_blur", |node: &mut NodeHandle, val: f64| {
         let mut d = node.director.lock().unwrap();
@@ -382,4 +528,210 @@ pub fn register(engine: &mut Engine) {
             }
         },
     );
+
+    // ========== PROPERTY DRIVERS ==========
+    engine.register_fn(
+        "drive",
+        |node: &mut NodeHandle,
+         prop: &str,
+         source: NodeHandle,
+         source_prop: &str|
+         -> Result<(), Box<EvalAltResult>> {
+            let input = driver_input(node, &source, source_prop, 1.0)?;
+            add_driver(node, prop, vec![input], &rhai::Map::new())
+        },
+    );
+
+    engine.register_fn(
+        "drive",
+        |node: &mut NodeHandle,
+         prop: &str,
+         source: NodeHandle,
+         source_prop: &str,
+         options: rhai::Map|
+         -> Result<(), Box<EvalAltResult>> {
+            let input = driver_input(node, &source, source_prop, 1.0)?;
+            add_driver(node, prop, vec![input], &options)
+        },
+    );
+
+    engine.register_fn(
+        "drive",
+        |node: &mut NodeHandle,
+         prop: &str,
+         inputs: rhai::Array|
+         -> Result<(), Box<EvalAltResult>> {
+            let inputs = parse_driver_inputs(node, inputs)?;
+            add_driver(node, prop, inputs, &rhai::Map::new())
+        },
+    );
+
+    engine.register_fn(
+        "drive",
+        |node: &mut NodeHandle,
+         prop: &str,
+         inputs: rhai::Array,
+         options: rhai::Map|
+         -> Result<(), Box<EvalAltResult>> {
+            let inputs = parse_driver_inputs(node, inputs)?;
+            add_driver(node, prop, inputs, &options)
+        },
+    );
+
+    engine.register_fn("clear_drivers", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
+        if let Some(n) = d.scene.get_node_mut(node.id) {
+            n.drivers.clear();
+        }
+    });
+}
+
+/// Queues a chart's transition to `data` (see `ChartNode::animate_data`).
+fn animate_chart_data(
+    node: &NodeHandle,
+    data: &rhai::Dynamic,
+    delay: f64,
+    dur: f64,
+    ease: &str,
+) -> Result<(), Box<EvalAltResult>> {
+    let data = parse_chart_data(data)?;
+    let mut d = node.director.lock().unwrap();
+    let chart = d
+        .scene
+        .get_node_mut(node.id)
+        .and_then(|n| n.element.as_any_mut().downcast_mut::<ChartNode>())
+        .ok_or("animate_data is only supported on charts")?;
+    if data.len() != chart.series.len() {
+        return Err(format!(
+            "Chart has {} series but the data has {}",
+            chart.series.len(),
+            data.len()
+        )
+        .into());
+    }
+    chart.animate_data(data, delay, dur, parse_easing(ease));
+    Ok(())
+}
+
+/// Runs `f` on the code node behind `node`.
+fn with_code_node(
+    node: &NodeHandle,
+    f: impl FnOnce(&mut CodeNode),
+) -> Result<(), Box<EvalAltResult>> {
+    let mut d = node.director.lock().unwrap();
+    let code = d
+        .scene
+        .get_node_mut(node.id)
+        .and_then(|n| n.element.as_any_mut().downcast_mut::<CodeNode>())
+        .ok_or("animate_code and focus_lines are only supported on code nodes")?;
+    f(code);
+    Ok(())
+}
+
+/// Queues a counter animation to `target` after the counter's previous ones.
+fn count_to(
+    node: &NodeHandle,
+    target: &rhai::Dynamic,
+    animate: impl FnOnce(&mut Animated<f64>, f64),
+) -> Result<(), Box<EvalAltResult>> {
+    let target = number(target).ok_or("count_to target must be a number")?;
+    let mut d = node.director.lock().unwrap();
+    let counter = d
+        .scene
+        .get_node_mut(node.id)
+        .and_then(|n| n.element.as_any_mut().downcast_mut::<TextNode>())
+        .and_then(|text| text.counter.as_mut())
+        .ok_or("count_to is only supported on counters")?;
+    animate(&mut counter.value, target);
+    Ok(())
+}
+
+/// A driver term reading `source_prop` on `source`, which must belong to `node`'s movie.
+fn driver_input(
+    node: &NodeHandle,
+    source: &NodeHandle,
+    source_prop: &str,
+    weight: f32,
+) -> Result<DriverInput, Box<EvalAltResult>> {
+    if !Arc::ptr_eq(&node.director, &source.director) {
+        return Err("Driver source belongs to a different movie".into());
+    }
+    Ok(DriverInput {
+        source: source.id,
+        property: source_prop.to_string(),
+        weight,
+    })
+}
+
+/// Parses `[#{ node, property, weight }, ...]` into driver terms (`weight` defaults to 1).
+fn parse_driver_inputs(
+    node: &NodeHandle,
+    inputs: rhai::Array,
+) -> Result<Vec<DriverInput>, Box<EvalAltResult>> {
+    if inputs.is_empty() {
+        return Err("drive needs at least one input".into());
+    }
+    inputs
+        .into_iter()
+        .map(|input| {
+            let map = input
+                .try_cast::<rhai::Map>()
+                .ok_or("Driver inputs must be maps of node, property and weight")?;
+            let source = map
+                .get("node")
+                .and_then(|v| v.clone().try_cast::<NodeHandle>())
+                .ok_or("Driver input is missing its node")?;
+            let property = map
+                .get("property")
+                .and_then(|v| v.clone().into_string().ok())
+                .ok_or("Driver input is missing its property")?;
+            let weight = match map.get("weight") {
+                None => 1.0,
+                Some(v) => v
+                    .as_float()
+                    .ok()
+                    .or_else(|| v.as_int().ok().map(|i| i as f64))
+                    .ok_or("Driver input weight must be a number")?,
+            };
+            driver_input(node, &source, &property, weight as f32)
+        })
+        .collect()
+}
+
+/// Attaches a `PropertyDriver` summing `inputs`, configured from a Rhai options map, to `node`.
+fn add_driver(
+    node: &NodeHandle,
+    prop: &str,
+    inputs: Vec<DriverInput>,
+    options: &rhai::Map,
+) -> Result<(), Box<EvalAltResult>> {
+    let num = |key: &str| {
+        options.get(key).and_then(|v| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f64))
+        })
+    };
+
+    let mut driver = PropertyDriver::with_inputs(prop, inputs);
+    if let Some(v) = num("multiplier") {
+        driver.multiplier = v as f32;
+    }
+    if let Some(v) = num("offset") {
+        driver.offset = v as f32;
+    }
+    driver.min = num("min").map(|v| v as f32);
+    driver.max = num("max").map(|v| v as f32);
+    if let Some(v) = num("delay") {
+        driver.delay = v.max(0.0);
+    }
+    if let Some(v) = options.get("decimals").and_then(|v| v.as_int().ok()) {
+        driver.decimals = v.max(0) as usize;
+    }
+
+    let mut d = node.director.lock().unwrap();
+    if let Some(n) = d.scene.get_node_mut(node.id) {
+        n.drivers.push(driver);
+    }
+    Ok(())
 }
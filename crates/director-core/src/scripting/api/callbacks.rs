@@ -0,0 +1,40 @@
+//! # Callbacks API
+//!
+//! Per-frame script callbacks for Rhai scripts.
+//!
+//! ## Responsibilities
+//! - **Node Callbacks**: `node.on_frame(|t, node| ...)` with the node's local time
+//! - **Scene Callbacks**: `scene.on_frame(|t, node| ...)` bound to the scene root
+//! - **Movie Callbacks**: `movie.on_frame(|t, frame| ...)` with the global time
+//!
+//! Callbacks run in the sandbox engine from `scripting::callbacks`.
+
+use rhai::{Engine, FnPtr};
+
+use super::super::callbacks::ensure_callback_engine;
+use super::super::types::{MovieHandle, NodeHandle, SceneHandle};
+
+/// Register callback-related Rhai functions.
+pub fn register(engine: &mut Engine) {
+    engine.register_fn("on_frame", |node: &mut NodeHandle, callback: FnPtr| {
+        let mut d = node.director.lock().unwrap();
+        ensure_callback_engine(&mut d);
+        if let Some(n) = d.scene.get_node_mut(node.id) {
+            n.frame_callbacks.push(callback);
+        }
+    });
+
+    engine.register_fn("on_frame", |scene: &mut SceneHandle, callback: FnPtr| {
+        let mut d = scene.director.lock().unwrap();
+        ensure_callback_engine(&mut d);
+        if let Some(n) = d.scene.get_node_mut(scene.root_id) {
+            n.frame_callbacks.push(callback);
+        }
+    });
+
+    engine.register_fn("on_frame", |movie: &mut MovieHandle, callback: FnPtr| {
+        let mut d = movie.director.lock().unwrap();
+        ensure_callback_engine(&mut d);
+        d.frame_callbacks.push(callback);
+    });
+}
@@ -9,17 +9,47 @@
 //! - **Cinematic Shaders**:
 //!   - `apply_effect("directional_blur", #{ strength: 10.0, angle: 45.0, samples: 16 })` for motion blur
 //!   - `apply_effect("grain", #{ intensity: 0.1, size: 2.0 })` for film grain
+//! - **Adjustment Layers**: `add_adjustment_layer` filters everything drawn beneath it;
+//!   `apply_effect` on an adjustment layer extends its effect chain
 
 use crate::animation::{Animated, TweenableVector};
-use crate::node::{EffectType, ShaderUniform};
+use crate::director::Director;
+use crate::node::{EffectNode, EffectType, ShaderUniform};
+use crate::types::NodeId;
 use rhai::{Engine, Map};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use taffy::prelude::{Dimension, LengthPercentageAuto, Position, Rect, Size, Style};
 
-use super::super::types::NodeHandle;
-use super::super::utils::apply_effect_to_node;
+use super::super::types::{NodeHandle, SceneHandle};
+use super::super::utils::{apply_effect_to_node, parse_layout_style};
 
 /// Register effect-related Rhai functions.
 pub fn register(engine: &mut Engine) {
+    // ========== ADJUSTMENT LAYERS ==========
+    // Usage: let grade = scene.add_adjustment_layer(); grade.apply_effect("sepia");
+    engine.register_fn("add_adjustment_layer", |scene: &mut SceneHandle| {
+        add_adjustment_layer(&scene.director, scene.root_id, &Map::new())
+    });
+
+    engine.register_fn(
+        "add_adjustment_layer",
+        |scene: &mut SceneHandle, props: Map| {
+            add_adjustment_layer(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    engine.register_fn("add_adjustment_layer", |parent: &mut NodeHandle| {
+        add_adjustment_layer(&parent.director, parent.id, &Map::new())
+    });
+
+    engine.register_fn(
+        "add_adjustment_layer",
+        |parent: &mut NodeHandle, props: Map| {
+            add_adjustment_layer(&parent.director, parent.id, &props)
+        },
+    );
+
     // Named effects (no value)
     engine.register_fn(
         "apply_effect",
@@ -195,3 +225,50 @@ pub fn register(engine: &mut Engine) {
         },
     );
 }
+
+/// Creates an empty adjustment layer. Without layout props it covers its parent
+/// and stays out of the flex flow.
+fn add_adjustment_layer(
+    director: &Arc<Mutex<Director>>,
+    parent_id: NodeId,
+    props: &Map,
+) -> NodeHandle {
+    let mut d = director.lock().unwrap();
+
+    let mut style = Style {
+        position: Position::Absolute,
+        inset: Rect {
+            left: LengthPercentageAuto::length(0.0),
+            right: LengthPercentageAuto::length(0.0),
+            top: LengthPercentageAuto::length(0.0),
+            bottom: LengthPercentageAuto::length(0.0),
+        },
+        size: Size {
+            width: Dimension::percent(1.0),
+            height: Dimension::percent(1.0),
+        },
+        ..Style::DEFAULT
+    };
+    parse_layout_style(props, &mut style);
+
+    let layer = EffectNode {
+        effects: Vec::new(),
+        style,
+        shader_cache: d.assets.shader_cache.clone(),
+        current_time: 0.0,
+        adjustment: true,
+    };
+
+    let id = d.scene.add_node(Box::new(layer));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent_id, id);
+
+    NodeHandle {
+        director: director.clone(),
+        id,
+    }
+}
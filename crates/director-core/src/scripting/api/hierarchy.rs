@@ -0,0 +1,314 @@
+//! # Hierarchy API
+//!
+//! Scene graph structure operations for Rhai scripts.
+//!
+//! ## Responsibilities
+//! - **Duplication**: `duplicate` deep-copies a node and its subtree
+//! - **Symbols**: `make_symbol` turns a subtree into a reusable master
+//! - **Instances**: `add_instance` places a linked copy with per-instance overrides, rebuilt
+//!   from the master whenever it is edited (`sync_symbol_instances`)
+//! - **Reparenting**: `reparent` moves a subtree under another node or scene
+//! - **Ordering**: `move_before`, `move_after`, `bring_to_front`, `send_to_back`
+
+use crate::director::Director;
+use crate::node::{BoxNode, TextNode};
+use crate::scene::SymbolInstance;
+use crate::types::NodeId;
+use rhai::{Engine, EvalAltResult, Map};
+use std::sync::{Arc, Mutex};
+
+use super::super::types::{NodeHandle, SceneHandle, SymbolHandle};
+use super::super::utils::{parse_layout_style, parse_spans_from_dynamic};
+use super::nodes::{apply_box_props, apply_text_props};
+
+/// Register hierarchy-related Rhai functions.
+pub fn register(engine: &mut Engine) {
+    engine.register_type_with_name::<SymbolHandle>("Symbol");
+
+    // ========== DUPLICATE ==========
+    engine.register_fn("duplicate", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
+        let Some(parent) = d.scene.get_node(node.id).map(|n| n.parent) else {
+            return node.clone();
+        };
+        let Some(id) = d.scene.clone_subtree(node.id) else {
+            return node.clone();
+        };
+
+        // Masks are not in their owner's children, so a duplicated mask stays detached.
+        if let Some(pid) = parent {
+            let is_child = d
+                .scene
+                .get_node(pid)
+                .is_some_and(|p| p.children.contains(&node.id));
+            if is_child {
+                d.scene.add_child(pid, id);
+            }
+        }
+
+        NodeHandle {
+            director: node.director.clone(),
+            id,
+        }
+    });
+
+    // ========== REPARENT ==========
+    engine.register_fn(
+        "reparent",
+        |node: &mut NodeHandle, parent: NodeHandle| -> Result<(), Box<EvalAltResult>> {
+            reparent(node, &parent.director, parent.id, None)
+        },
+    );
+
+    engine.register_fn(
+        "reparent",
+        |node: &mut NodeHandle, parent: NodeHandle, index: i64| -> Result<(), Box<EvalAltResult>> {
+            reparent(
+                node,
+                &parent.director,
+                parent.id,
+                Some(index.max(0) as usize),
+            )
+        },
+    );
+
+    engine.register_fn(
+        "reparent",
+        |node: &mut NodeHandle, scene: SceneHandle| -> Result<(), Box<EvalAltResult>> {
+            reparent(node, &scene.director, scene.root_id, None)
+        },
+    );
+
+    engine.register_fn(
+        "reparent",
+        |node: &mut NodeHandle, scene: SceneHandle, index: i64| -> Result<(), Box<EvalAltResult>> {
+            reparent(
+                node,
+                &scene.director,
+                scene.root_id,
+                Some(index.max(0) as usize),
+            )
+        },
+    );
+
+    // ========== ORDERING ==========
+    engine.register_fn(
+        "move_before",
+        |node: &mut NodeHandle, sibling: NodeHandle| -> Result<(), Box<EvalAltResult>> {
+            if !Arc::ptr_eq(&node.director, &sibling.director) {
+                return Err("Cannot move a node next to a node from a different movie".into());
+            }
+            let mut d = node.director.lock().unwrap();
+            if d.scene.move_before(node.id, sibling.id) {
+                Ok(())
+            } else {
+                Err("move_before: sibling has no parent or is inside the moved subtree".into())
+            }
+        },
+    );
+
+    engine.register_fn(
+        "move_after",
+        |node: &mut NodeHandle, sibling: NodeHandle| -> Result<(), Box<EvalAltResult>> {
+            if !Arc::ptr_eq(&node.director, &sibling.director) {
+                return Err("Cannot move a node next to a node from a different movie".into());
+            }
+            let mut d = node.director.lock().unwrap();
+            if d.scene.move_after(node.id, sibling.id) {
+                Ok(())
+            } else {
+                Err("move_after: sibling has no parent or is inside the moved subtree".into())
+            }
+        },
+    );
+
+    engine.register_fn("bring_to_front", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
+        d.scene.bring_to_front(node.id);
+    });
+
+    engine.register_fn("send_to_back", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
+        d.scene.send_to_back(node.id);
+    });
+
+    // ========== SYMBOLS ==========
+    engine.register_fn("make_symbol", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
+        d.scene.detach(node.id);
+
+        SymbolHandle {
+            director: node.director.clone(),
+            root_id: node.id,
+        }
+    });
+
+    engine.register_fn(
+        "add_instance",
+        |parent: &mut NodeHandle, symbol: SymbolHandle| {
+            instantiate(&parent.director, parent.id, &symbol, &Map::new())
+        },
+    );
+
+    engine.register_fn(
+        "add_instance",
+        |parent: &mut NodeHandle, symbol: SymbolHandle, overrides: Map| {
+            instantiate(&parent.director, parent.id, &symbol, &overrides)
+        },
+    );
+
+    engine.register_fn(
+        "add_instance",
+        |scene: &mut SceneHandle, symbol: SymbolHandle| {
+            instantiate(&scene.director, scene.root_id, &symbol, &Map::new())
+        },
+    );
+
+    engine.register_fn(
+        "add_instance",
+        |scene: &mut SceneHandle, symbol: SymbolHandle, overrides: Map| {
+            instantiate(&scene.director, scene.root_id, &symbol, &overrides)
+        },
+    );
+}
+
+/// Moves `node` under `parent_id`, rejecting cross-movie moves and cycles.
+fn reparent(
+    node: &NodeHandle,
+    director: &Arc<Mutex<Director>>,
+    parent_id: NodeId,
+    index: Option<usize>,
+) -> Result<(), Box<EvalAltResult>> {
+    if !Arc::ptr_eq(&node.director, director) {
+        return Err("Cannot reparent a node into a different movie".into());
+    }
+
+    let mut d = node.director.lock().unwrap();
+    if d.scene.reparent(node.id, parent_id, index) {
+        Ok(())
+    } else {
+        Err("Cannot reparent a node into itself or one of its descendants".into())
+    }
+}
+
+/// Clones the symbol subtree under `parent_id`, applies the overrides to the copy and
+/// links it to the master.
+fn instantiate(
+    director: &Arc<Mutex<Director>>,
+    parent_id: NodeId,
+    symbol: &SymbolHandle,
+    overrides: &Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    if !Arc::ptr_eq(director, &symbol.director) {
+        return Err("Symbol belongs to a different movie".into());
+    }
+
+    let mut d = director.lock().unwrap();
+    let id = d
+        .scene
+        .clone_subtree(symbol.root_id)
+        .ok_or("Symbol has been destroyed")?;
+    apply_instance_overrides(&mut d, id, overrides);
+    d.scene.add_child(parent_id, id);
+
+    let synced_revision = d.scene.subtree_revision(symbol.root_id);
+    d.scene.symbol_instances.push(SymbolInstance {
+        master: symbol.root_id,
+        root: id,
+        overrides: overrides.clone(),
+        synced_revision,
+    });
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Rebuilds the symbol instances whose master was edited since they were last synced,
+/// then applies their overrides again.
+///
+/// Runs at the start of every `Director::update`. Instances nested in other masters
+/// change those masters in turn, so passes repeat until nothing changes.
+pub(crate) fn sync_symbol_instances(d: &mut Director) {
+    for _ in 0..8 {
+        let links: Vec<(NodeId, NodeId)> = d
+            .scene
+            .symbol_instances
+            .iter()
+            .map(|s| (s.master, s.root))
+            .collect();
+
+        let mut changed = false;
+        for (master, root) in links {
+            let revision = d.scene.subtree_revision(master);
+            // Syncing an earlier instance may have destroyed this one
+            let Some(instance) = d
+                .scene
+                .symbol_instances
+                .iter_mut()
+                .find(|s| s.master == master && s.root == root)
+            else {
+                continue;
+            };
+            if instance.synced_revision == revision {
+                continue;
+            }
+            instance.synced_revision = revision;
+            let overrides = instance.overrides.clone();
+
+            d.scene.sync_subtree(master, root);
+            apply_instance_overrides(d, root, &overrides);
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Applies per-instance overrides to a cloned node.
+///
+/// Box keys (`bg_color`, `border_color`, ...) and text keys (`content`, `color`,
+/// `size`, ...) are applied to the matching element type, layout keys to any node.
+/// A `children` array applies nested override maps to the children by position.
+fn apply_instance_overrides(d: &mut Director, id: NodeId, overrides: &Map) {
+    let children = {
+        let Some(n) = d.scene.get_node_mut(id) else {
+            return;
+        };
+
+        if let Some(box_node) = n.element.as_any_mut().downcast_mut::<BoxNode>() {
+            apply_box_props(box_node, overrides);
+        } else if let Some(text_node) = n.element.as_any_mut().downcast_mut::<TextNode>() {
+            if let Some(c) = overrides.get("content") {
+                text_node.spans = parse_spans_from_dynamic(c.clone());
+            }
+            apply_text_props(text_node, overrides);
+            text_node.dirty_layout = true;
+            text_node.init_paragraph();
+        }
+
+        let mut style = n.element.layout_style();
+        parse_layout_style(overrides, &mut style);
+        n.element.set_layout_style(style);
+        n.dirty_style = true;
+
+        if let Some(z) = overrides.get("z_index").and_then(|v| v.as_int().ok()) {
+            n.z_index = z as i32;
+        }
+
+        n.children.clone()
+    };
+
+    if let Some(child_overrides) = overrides
+        .get("children")
+        .and_then(|v| v.clone().try_cast::<rhai::Array>())
+    {
+        for (child_id, child_map) in children.into_iter().zip(child_overrides) {
+            if let Some(child_map) = child_map.try_cast::<Map>() {
+                apply_instance_overrides(d, child_id, &child_map);
+            }
+        }
+    }
+}
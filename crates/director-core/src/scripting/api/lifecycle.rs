@@ -3,35 +3,32 @@
 //! Director and scene lifecycle management for Rhai scripts.
 //!
 //! ## Responsibilities
-//! - **Director Creation**: `new_director` with various overloads
+//! - **Director Creation**: `new_director` with various overloads, including the random `seed`
 //! - **Scene Management**: `add_scene`, `add_transition`
-//! - **Configuration**: `configure_motion_blur`
+//! - **Configuration**: `configure_motion_blur`, `configure_supersampling`, `configure_tiling`, `set_color_space`, `set_color_interpolation`
 
 use crate::director::{Director, TimelineItem};
 use crate::node::BoxNode;
 use crate::systems::transitions::{Transition, TransitionType};
 use crate::video_wrapper::RenderMode;
 use crate::AssetLoader;
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 use std::sync::{Arc, Mutex};
 use taffy::prelude::*;
 
+use super::super::rng::ScriptRng;
 use super::super::types::{MovieHandle, SceneHandle};
-use super::super::utils::parse_easing;
+use super::super::utils::{parse_color_interpolation, parse_color_space, parse_easing};
+use super::random::CurrentRng;
 
 /// Register lifecycle-related Rhai functions.
-pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
-    // Randomness
-    engine.register_fn("rand_float", |min: f64, max: f64| {
-        use rand::Rng;
-        rand::thread_rng().gen_range(min..max)
-    });
-
+pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>, current: CurrentRng) {
     // 1. Director/Movie
     engine.register_type_with_name::<MovieHandle>("Movie");
 
     // Overload 1: 3 args (Default Preview)
     let l1 = loader.clone();
+    let c1 = current.clone();
     engine.register_fn("new_director", move |w: i64, h: i64, fps: i64| {
         let director = Director::new(
             w as i32,
@@ -41,13 +38,12 @@ pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
             RenderMode::Preview,
             None,
         );
-        MovieHandle {
-            director: Arc::new(Mutex::new(director)),
-        }
+        movie_with_rng(director, ScriptRng::from_entropy(), &c1)
     });
 
     // Overload 2: 4 args (Config)
     let l2 = loader.clone();
+    let c2 = current;
     engine.register_fn(
         "new_director",
         move |w: i64, h: i64, fps: i64, config: rhai::Map| {
@@ -59,10 +55,27 @@ pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
                 "export" => RenderMode::Export,
                 _ => RenderMode::Preview,
             };
-            let director = Director::new(w as i32, h as i32, fps as u32, l2.clone(), mode, None);
-            MovieHandle {
-                director: Arc::new(Mutex::new(director)),
+            let mut director =
+                Director::new(w as i32, h as i32, fps as u32, l2.clone(), mode, None);
+            if let Some(space) = config
+                .get("color_space")
+                .and_then(|v| v.clone().into_string().ok())
+                .and_then(|s| parse_color_space(&s))
+            {
+                director.color_space = space;
             }
+            if let Some(interpolation) = config
+                .get("color_interpolation")
+                .and_then(|v| v.clone().into_string().ok())
+                .and_then(|s| parse_color_interpolation(&s))
+            {
+                director.color_interpolation = interpolation;
+            }
+            let rng = match config.get("seed").and_then(|v| v.as_int().ok()) {
+                Some(seed) => ScriptRng::new(seed as u64),
+                None => ScriptRng::from_entropy(),
+            };
+            movie_with_rng(director, rng, &c2)
         },
     );
 
@@ -75,6 +88,52 @@ pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
         },
     );
 
+    engine.register_fn(
+        "configure_supersampling",
+        |movie: &mut MovieHandle, factor: i64| -> Result<(), Box<EvalAltResult>> {
+            if !matches!(factor, 1 | 2 | 4) {
+                return Err(
+                    format!("Supersampling factor must be 1, 2 or 4, got {}", factor).into(),
+                );
+            }
+            movie.director.lock().unwrap().supersampling = factor as u32;
+            Ok(())
+        },
+    );
+
+    engine.register_fn(
+        "configure_tiling",
+        |movie: &mut MovieHandle, tile_size: i64, overlap: i64| -> Result<(), Box<EvalAltResult>> {
+            if tile_size < 0 || overlap < 0 {
+                return Err("Tile size and overlap must not be negative".into());
+            }
+            let mut d = movie.director.lock().unwrap();
+            d.tile_size = (tile_size > 0).then_some(tile_size as u32);
+            d.tile_overlap = overlap as u32;
+            Ok(())
+        },
+    );
+
+    engine.register_fn(
+        "set_color_space",
+        |movie: &mut MovieHandle, space: &str| -> Result<(), Box<EvalAltResult>> {
+            let space = parse_color_space(space)
+                .ok_or_else(|| format!("Unknown color space '{}'", space))?;
+            movie.director.lock().unwrap().color_space = space;
+            Ok(())
+        },
+    );
+
+    engine.register_fn(
+        "set_color_interpolation",
+        |movie: &mut MovieHandle, interpolation: &str| -> Result<(), Box<EvalAltResult>> {
+            let interpolation = parse_color_interpolation(interpolation)
+                .ok_or_else(|| format!("Unknown color interpolation '{}'", interpolation))?;
+            movie.director.lock().unwrap().color_interpolation = interpolation;
+            Ok(())
+        },
+    );
+
     // 2. Scene Management
     engine.register_type_with_name::<SceneHandle>("Scene");
     engine.register_fn("add_scene", |movie: &mut MovieHandle, duration: f64| {
@@ -168,3 +227,15 @@ pub fn register(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
         },
     );
 }
+
+/// Wraps `director` in a handle that owns `rng`, and makes `rng` the one the free
+/// random helpers draw from.
+fn movie_with_rng(mut director: Director, rng: ScriptRng, current: &CurrentRng) -> MovieHandle {
+    director.seed = rng.seed();
+    let rng = Arc::new(Mutex::new(rng));
+    *current.lock().unwrap() = rng.clone();
+    MovieHandle {
+        director: Arc::new(Mutex::new(director)),
+        rng,
+    }
+}
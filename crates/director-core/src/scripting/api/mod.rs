@@ -5,28 +5,41 @@
 //! ## Sub-modules
 //! - **lifecycle**: Director creation, scene management, transitions
 //! - **nodes**: Node creation (box, text, image, video, lottie, svg, composition)
+//! - **hierarchy**: Subtree duplication, symbol instancing, reparenting and ordering
 //! - **animation**: Keyframe, spring, and path animations
 //! - **audio**: Audio loading, analysis, and reactivity
 //! - **effects**: Visual effects and shaders
 //! - **properties**: Node property setters
+//! - **callbacks**: Per-frame `on_frame` script callbacks
+//! - **random**: Seeded random numbers, noise and colors
 
 pub mod animation;
 pub mod audio;
+pub mod callbacks;
 pub mod effects;
+pub mod hierarchy;
 pub mod lifecycle;
 pub mod nodes;
 pub mod properties;
+pub mod random;
 
 use crate::AssetLoader;
 use rhai::Engine;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+use super::rng::ScriptRng;
 
 /// Register all API functions with the Rhai engine.
 pub fn register_all(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
-    lifecycle::register(engine, loader.clone());
+    // Each movie owns its generator; the free helpers follow the latest `new_director`
+    let current = Arc::new(Mutex::new(Arc::new(Mutex::new(ScriptRng::from_entropy()))));
+    lifecycle::register(engine, loader.clone(), current.clone());
     nodes::register(engine, loader.clone());
+    hierarchy::register(engine);
     animation::register(engine);
     audio::register(engine);
     effects::register(engine);
     properties::register(engine);
+    callbacks::register(engine);
+    random::register(engine, current);
 }
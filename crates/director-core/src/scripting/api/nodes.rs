@@ -5,29 +5,44 @@
 //! ## Responsibilities
 //! - **Box Nodes**: `add_box` for container nodes
 //! - **Text Nodes**: `add_text` for rich text
+//! - **Counters**: `add_counter` for animated, formatted numbers
 //! - **Image Nodes**: `add_image` for static images
 //! - **Video Nodes**: `add_video` for video playback
 //! - **Lottie Nodes**: `add_lottie` for Lottie animations
 //! - **SVG Nodes**: `add_svg` for vector graphics
+//! - **Shape Nodes**: `add_shape` for rects, ellipses, polygons, stars and paths
+//! - **Chart Nodes**: `add_chart` for bar, line, area and pie charts
+//! - **Code Nodes**: `add_code` for highlighted source code
+//! - **Audio Visualizers**: `add_waveform` and `add_spectrum` for mixer tracks
+//! - **Progress Bars**: `add_progress` for bars following scene, movie or audio time
+//! - **Particle Nodes**: `add_particles` for seeded particle emitters
+//! - **Path Booleans**: `path_union`, `path_difference`, `path_intersect`, `path_xor`
 //! - **Composition Nodes**: `add_composition` for nested compositions
 //! - **Node Destruction**: `destroy` to remove nodes
 
 use crate::animation::Animated;
-use crate::element::TextFit;
+use crate::director::Director;
+use crate::element::{TextFit, TextSpan};
 use crate::node::{
-    BoxNode, CompositionNode, ImageNode, LottieNode, TextNode, VectorNode, VideoNode, VideoSource,
+    unit_sprite_path, BoxNode, ChartNode, CodeNode, CodeTheme, CompositionNode, ImageNode,
+    LifeCurve, LottieNode, NumberFormat, ParticleEmitter, ParticleNode, ParticleSprite,
+    ProgressNode, ProgressSource, ShapeFill, ShapeGeometry, ShapeNode, SpectrumNode, TextCounter,
+    TextNode, VectorNode, VideoNode, VideoSource, WaveformNode,
 };
+use crate::types::{Color, NodeId};
 use crate::AssetLoader;
-use rhai::{Engine, Map};
+use rhai::{Array, Engine, EvalAltResult, Map};
+use skia_safe::{Data, Font, FontStyle, Image, PathOp, Point, Rect};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use tracing::error;
 
-use super::super::types::{MovieHandle, NodeHandle, SceneHandle};
+use super::super::types::{AudioTrackHandle, MovieHandle, NodeHandle, SceneHandle};
 use super::super::utils::{
-    parse_hex_color, parse_layout_style, parse_object_fit, parse_spans_from_dynamic,
-    parse_text_shadow,
+    parse_chart_kind, parse_code_language, parse_gradient, parse_hex_color, parse_layout_style,
+    parse_object_fit, parse_progress_source, parse_spans_from_dynamic, parse_spectrum_style,
+    parse_stroke_cap, parse_stroke_join, parse_text_shadow, parse_tint_color, parse_waveform_mode,
 };
 
 /// Register node creation Rhai functions.
@@ -339,6 +354,7 @@ pub fn register(engine: &mut Engine, _loader: Arc<dyn AssetLoader>) {
 
             let mut vec_node = VectorNode::new(&bytes);
             parse_layout_style(&props, &mut vec_node.style);
+            apply_vector_props(&mut vec_node, &props);
 
             let id = d.scene.add_node(Box::new(vec_node));
             if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
@@ -375,6 +391,7 @@ pub fn register(engine: &mut Engine, _loader: Arc<dyn AssetLoader>) {
 
             let mut vec_node = VectorNode::new(&bytes);
             parse_layout_style(&props, &mut vec_node.style);
+            apply_vector_props(&mut vec_node, &props);
 
             let id = d.scene.add_node(Box::new(vec_node));
             if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
@@ -574,6 +591,173 @@ pub fn register(engine: &mut Engine, _loader: Arc<dyn AssetLoader>) {
         }
     });
 
+    // ========== ADD_COUNTER ==========
+    engine.register_fn(
+        "add_counter",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_counter_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_counter",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_counter_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== ADD_SHAPE ==========
+    engine.register_fn(
+        "add_shape",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_shape_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_shape",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_shape_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== ADD_CHART ==========
+    engine.register_fn(
+        "add_chart",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_chart_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_chart",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_chart_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== ADD_CODE ==========
+    engine.register_fn(
+        "add_code",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_code_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_code",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_code_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== ADD_WAVEFORM / ADD_SPECTRUM ==========
+    engine.register_fn(
+        "add_waveform",
+        |parent: &mut NodeHandle,
+         track: AudioTrackHandle,
+         props: rhai::Map|
+         -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_waveform_node(&parent.director, parent.id, &track, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_waveform",
+        |scene: &mut SceneHandle,
+         track: AudioTrackHandle,
+         props: rhai::Map|
+         -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_waveform_node(&scene.director, scene.root_id, &track, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_spectrum",
+        |parent: &mut NodeHandle,
+         track: AudioTrackHandle,
+         props: rhai::Map|
+         -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_spectrum_node(&parent.director, parent.id, &track, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_spectrum",
+        |scene: &mut SceneHandle,
+         track: AudioTrackHandle,
+         props: rhai::Map|
+         -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_spectrum_node(&scene.director, scene.root_id, &track, &props)
+        },
+    );
+
+    // ========== ADD_PROGRESS ==========
+    engine.register_fn(
+        "add_progress",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_progress_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_progress",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_progress_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== ADD_PARTICLES ==========
+    engine.register_fn(
+        "add_particles",
+        |parent: &mut NodeHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_particle_node(&parent.director, parent.id, &props)
+        },
+    );
+
+    engine.register_fn(
+        "add_particles",
+        |scene: &mut SceneHandle, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+            add_particle_node(&scene.director, scene.root_id, &props)
+        },
+    );
+
+    // ========== PATH BOOLEANS ==========
+    for (name, op) in [
+        ("path_union", PathOp::Union),
+        ("path_difference", PathOp::Difference),
+        ("path_intersect", PathOp::Intersect),
+        ("path_xor", PathOp::XOR),
+    ] {
+        engine.register_fn(
+            name,
+            move |a: NodeHandle, b: NodeHandle| -> Result<NodeHandle, Box<EvalAltResult>> {
+                add_path_boolean(op, vec![a, b], &Map::new())
+            },
+        );
+        engine.register_fn(
+            name,
+            move |a: NodeHandle,
+                  b: NodeHandle,
+                  props: rhai::Map|
+                  -> Result<NodeHandle, Box<EvalAltResult>> {
+                add_path_boolean(op, vec![a, b], &props)
+            },
+        );
+        engine.register_fn(
+            name,
+            move |nodes: Array| -> Result<NodeHandle, Box<EvalAltResult>> {
+                add_path_boolean(op, operand_handles(nodes)?, &Map::new())
+            },
+        );
+        engine.register_fn(
+            name,
+            move |nodes: Array, props: rhai::Map| -> Result<NodeHandle, Box<EvalAltResult>> {
+                add_path_boolean(op, operand_handles(nodes)?, &props)
+            },
+        );
+    }
+
     // ========== ADD_COMPOSITION ==========
     engine.register_fn(
         "add_composition",
@@ -647,8 +831,1146 @@ pub fn register(engine: &mut Engine, _loader: Arc<dyn AssetLoader>) {
     );
 }
 
+/// Create a `ShapeNode` from `add_shape` props and append it to `parent`.
+fn add_shape_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let mut shape = ShapeNode::new(parse_shape_geometry(props)?);
+    apply_shape_props(&mut shape, props)?;
+    parse_layout_style(props, &mut shape.style);
+
+    let mut d = director.lock().unwrap();
+    let id = d.scene.add_node(Box::new(shape));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Creates a boolean shape in place of the first operand and moves all operands under it.
+///
+/// The operands keep animating and laying out as children, but are only drawn as part
+/// of the combined outline, which the director recomputes every frame.
+fn add_path_boolean(
+    op: PathOp,
+    operands: Vec<NodeHandle>,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let first = operands
+        .first()
+        .filter(|_| operands.len() >= 2)
+        .ok_or("Path operations need at least two nodes")?;
+    let director = first.director.clone();
+    if operands
+        .iter()
+        .any(|n| !Arc::ptr_eq(&n.director, &director))
+    {
+        return Err("Path operands belong to different movies".into());
+    }
+
+    let mut shape = ShapeNode::boolean(op);
+    apply_shape_props(&mut shape, props)?;
+    parse_layout_style(props, &mut shape.style);
+
+    let mut d = director.lock().unwrap();
+    let parent = d
+        .scene
+        .get_node(first.id)
+        .and_then(|n| n.parent)
+        .ok_or("Path operands must be attached to a scene")?;
+    let nested = operands.iter().enumerate().any(|(i, a)| {
+        operands
+            .iter()
+            .enumerate()
+            .any(|(j, b)| i != j && d.scene.is_in_subtree(a.id, b.id))
+    });
+    if nested {
+        return Err("Path operands must be distinct and not contain each other".into());
+    }
+
+    let id = d.scene.add_node(Box::new(shape));
+    d.scene.add_child(parent, id);
+    d.scene.move_before(id, first.id);
+    for operand in &operands {
+        d.scene.reparent(operand.id, id, None);
+    }
+
+    Ok(NodeHandle { director, id })
+}
+
+/// Converts an array of nodes passed to a path operation.
+fn operand_handles(nodes: Array) -> Result<Vec<NodeHandle>, Box<EvalAltResult>> {
+    nodes
+        .into_iter()
+        .map(|n| {
+            n.try_cast::<NodeHandle>()
+                .ok_or_else(|| "Path operations expect an array of nodes".into())
+        })
+        .collect()
+}
+
+/// Create a `TextNode` showing an animated number from `add_counter` props and append it
+/// to `parent`.
+fn add_counter_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let value = match props.get("value") {
+        Some(v) => number(v).ok_or("Counter value must be a number")?,
+        None => 0.0,
+    };
+    let counter = TextCounter::new(value, parse_number_format(props)?);
+    let span = TextSpan {
+        text: counter.text(),
+        font_family: props.get("font").and_then(|v| v.clone().into_string().ok()),
+        ..Default::default()
+    };
+
+    let mut d = director.lock().unwrap();
+    let mut text_node = TextNode::new(vec![span], d.assets.font_collection.clone());
+    text_node.counter = Some(counter);
+    text_node.tabular_figures = true;
+    apply_text_props(&mut text_node, props);
+    parse_layout_style(props, &mut text_node.style);
+    text_node.shadow = parse_text_shadow(props);
+    text_node.init_paragraph();
+
+    let id = d.scene.add_node(Box::new(text_node));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Parse the `add_counter` formatting props.
+fn parse_number_format(props: &rhai::Map) -> Result<NumberFormat, String> {
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let flag = |key: &str| props.get(key).and_then(|v| v.as_bool().ok());
+
+    let mut format = NumberFormat::default();
+    if let Some(v) = props.get("decimals") {
+        let decimals = v
+            .as_int()
+            .map_err(|_| "Counter decimals must be an integer")?;
+        format.decimals = Some(decimals.clamp(0, 20) as usize);
+    }
+    if let Some(v) = flag("grouping") {
+        format.grouping = v;
+    }
+    if let Some(v) = string("prefix") {
+        format.prefix = v;
+    }
+    if let Some(v) = string("suffix") {
+        format.suffix = v;
+    }
+    if let Some(code) = string("currency") {
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid currency code '{}'", code));
+        }
+        format.currency = Some(code.to_ascii_uppercase());
+    }
+    if let Some(v) = flag("percent") {
+        format.percent = v;
+    }
+    if let Some(v) = string("locale") {
+        format.locale = v;
+    }
+    Ok(format)
+}
+
+/// A Rhai float or integer as `f64`.
+pub(super) fn number(value: &rhai::Dynamic) -> Option<f64> {
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|i| i as f64))
+}
+
+/// Create a `ChartNode` from `add_chart` props and append it to `parent`.
+fn add_chart_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let kind = match string("type") {
+        Some(s) => parse_chart_kind(&s).ok_or_else(|| format!("Unknown chart type '{}'", s))?,
+        None => Default::default(),
+    };
+    let labels: Vec<String> = props
+        .get("labels")
+        .and_then(|v| v.clone().into_array().ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect();
+    let data = match props.get("data") {
+        Some(data) => parse_chart_data(data)?,
+        None => Vec::new(),
+    };
+    let names: Vec<String> = props
+        .get("series")
+        .and_then(|v| v.clone().into_array().ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect();
+    let series = data
+        .into_iter()
+        .enumerate()
+        .map(|(i, values)| (names.get(i).cloned().unwrap_or_default(), values))
+        .collect();
+
+    let mut d = director.lock().unwrap();
+    let mut chart = ChartNode::new(kind, labels, series, d.assets.font_collection.clone());
+    apply_chart_props(&mut chart, props)?;
+    parse_layout_style(props, &mut chart.style);
+
+    let id = d.scene.add_node(Box::new(chart));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Parse chart data: an array of numbers (one series) or an array of such arrays.
+pub(super) fn parse_chart_data(value: &rhai::Dynamic) -> Result<Vec<Vec<f32>>, String> {
+    let arr = value
+        .clone()
+        .into_array()
+        .map_err(|_| "Chart data must be an array")?;
+    if arr.iter().all(|v| v.is_array()) {
+        Ok(arr.iter().map(numbers).collect())
+    } else if arr.iter().any(|v| v.is_array()) {
+        Err("Chart data must be all numbers or all arrays".to_string())
+    } else {
+        Ok(vec![numbers(value)])
+    }
+}
+
+/// Create a `CodeNode` from `add_code` props and append it to `parent`.
+fn add_code_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let language = match string("language") {
+        Some(s) => parse_code_language(&s).ok_or_else(|| format!("Unknown language '{}'", s))?,
+        None => Default::default(),
+    };
+    let source = string("code").unwrap_or_default();
+
+    let mut d = director.lock().unwrap();
+    let mut code = CodeNode::new(&source, language, d.assets.font_collection.clone());
+    apply_code_props(&mut code, props)?;
+    parse_layout_style(props, &mut code.style);
+
+    let id = d.scene.add_node(Box::new(code));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Apply the styling props of `add_code` to a code node.
+pub(super) fn apply_code_props(code: &mut CodeNode, props: &rhai::Map) -> Result<(), String> {
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let num = |key: &str| props.get(key).and_then(number).map(|v| v as f32);
+    let flag = |key: &str| props.get(key).and_then(|v| v.as_bool().ok());
+
+    if let Some(name) = string("theme") {
+        code.theme = CodeTheme::named(&name).ok_or_else(|| format!("Unknown theme '{}'", name))?;
+    }
+    if let Some(family) = string("font") {
+        code.font_family = Some(family);
+    }
+    if let Some(v) = num("font_size") {
+        code.font_size = v.max(1.0);
+    }
+    if let Some(v) = num("line_height") {
+        code.line_height = v.max(0.5);
+    }
+    if let Some(v) = flag("line_numbers") {
+        code.show_line_numbers = v;
+    }
+    if let Some(v) = num("padding") {
+        code.padding = v.max(0.0);
+    }
+    if let Some(v) = num("radius") {
+        code.corner_radius = v.max(0.0);
+    }
+    match props.get("background") {
+        Some(v) if v.is_bool() => code.show_background = v.as_bool().unwrap_or(true),
+        Some(v) => {
+            let s = v.to_string();
+            code.theme.background =
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid background '{}'", s))?;
+        }
+        None => {}
+    }
+    if let Some(v) = num("dim") {
+        code.dim_opacity = v.clamp(0.0, 1.0);
+    }
+    if let Some(lines) = props.get("highlight") {
+        code.set_focus(&parse_line_numbers(lines)?);
+    }
+    if let Some(v) = num("typed") {
+        code.typed = Animated::new(v);
+    }
+    if let Some(v) = num("opacity") {
+        code.opacity = Animated::new(v);
+    }
+    Ok(())
+}
+
+/// Parse an array of line numbers (counted from 1).
+pub(super) fn parse_line_numbers(value: &rhai::Dynamic) -> Result<Vec<usize>, String> {
+    let arr = value
+        .clone()
+        .into_array()
+        .map_err(|_| "Line numbers must be an array")?;
+    arr.iter()
+        .map(|v| match v.as_int() {
+            Ok(n) if n > 0 => Ok(n as usize),
+            _ => Err(format!("Invalid line number '{}'", v)),
+        })
+        .collect()
+}
+
+/// Create a `WaveformNode` for `track` from `add_waveform` props and append it to `parent`.
+fn add_waveform_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    track: &AudioTrackHandle,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    if !Arc::ptr_eq(director, &track.director) {
+        return Err("Audio track belongs to another movie".into());
+    }
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let mode = match string("mode") {
+        Some(s) => {
+            parse_waveform_mode(&s).ok_or_else(|| format!("Unknown waveform mode '{}'", s))?
+        }
+        None => Default::default(),
+    };
+
+    let mut waveform = WaveformNode::new(track.id, mode);
+    apply_waveform_props(&mut waveform, props)?;
+    parse_layout_style(props, &mut waveform.style);
+
+    let mut d = director.lock().unwrap();
+    let id = d.scene.add_node(Box::new(waveform));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Apply the styling props of `add_waveform` to a waveform node.
+fn apply_waveform_props(waveform: &mut WaveformNode, props: &rhai::Map) -> Result<(), String> {
+    let num = |key: &str| props.get(key).and_then(number);
+    let flag = |key: &str| props.get(key).and_then(|v| v.as_bool().ok());
+    let color = |key: &str| -> Result<Option<Color>, String> {
+        props
+            .get(key)
+            .map(|v| {
+                let s = v.to_string();
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid {} '{}'", key, s))
+            })
+            .transpose()
+    };
+
+    if let Some(v) = num("bars") {
+        waveform.bars = (v as usize).max(1);
+    }
+    if let Some(v) = num("window") {
+        waveform.window = v.max(0.01);
+    }
+    if let Some(c) = color("color")? {
+        waveform.color = c;
+    }
+    match props.get("played_color") {
+        Some(v) if v.as_bool() == Ok(false) || v.to_string() == "none" => {
+            waveform.played_color = None
+        }
+        Some(_) => waveform.played_color = color("played_color")?,
+        None => {}
+    }
+    if let Some(v) = num("gap") {
+        waveform.gap = v.clamp(0.0, 0.95) as f32;
+    }
+    if let Some(v) = flag("rounded") {
+        waveform.rounded = v;
+    }
+    if let Some(v) = num("gain") {
+        waveform.gain = v.max(0.0) as f32;
+    }
+    if let Some(v) = num("smoothing") {
+        waveform.smoothing = v.clamp(0.0, 1.0) as f32;
+    }
+    if let Some(v) = num("opacity") {
+        waveform.opacity = Animated::new(v as f32);
+    }
+    Ok(())
+}
+
+/// Create a `SpectrumNode` for `track` from `add_spectrum` props and append it to `parent`.
+fn add_spectrum_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    track: &AudioTrackHandle,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    if !Arc::ptr_eq(director, &track.director) {
+        return Err("Audio track belongs to another movie".into());
+    }
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let kind = match string("type") {
+        Some(s) => {
+            parse_spectrum_style(&s).ok_or_else(|| format!("Unknown spectrum type '{}'", s))?
+        }
+        None => Default::default(),
+    };
+
+    let mut spectrum = SpectrumNode::new(track.id, kind);
+    apply_spectrum_props(&mut spectrum, props)?;
+    parse_layout_style(props, &mut spectrum.style);
+
+    let mut d = director.lock().unwrap();
+    let id = d.scene.add_node(Box::new(spectrum));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Apply the styling props of `add_spectrum` to a spectrum node.
+fn apply_spectrum_props(spectrum: &mut SpectrumNode, props: &rhai::Map) -> Result<(), String> {
+    let num = |key: &str| props.get(key).and_then(number).map(|v| v as f32);
+    let flag = |key: &str| props.get(key).and_then(|v| v.as_bool().ok());
+
+    if let Some(v) = num("bands") {
+        spectrum.bands = (v as usize).max(1);
+    }
+    if let Some(v) = num("min_frequency") {
+        spectrum.min_frequency = v.max(1.0);
+    }
+    if let Some(v) = num("max_frequency") {
+        spectrum.max_frequency = v.max(1.0);
+    }
+    if spectrum.min_frequency >= spectrum.max_frequency {
+        return Err("min_frequency must be below max_frequency".to_string());
+    }
+    if let Some(v) = num("min_db") {
+        spectrum.min_db = v;
+    }
+    if let Some(v) = num("max_db") {
+        spectrum.max_db = v;
+    }
+    if spectrum.min_db >= spectrum.max_db {
+        return Err("min_db must be below max_db".to_string());
+    }
+    if let Some(v) = num("smoothing") {
+        spectrum.smoothing = v.clamp(0.0, 0.99);
+    }
+    if let Some(v) = props.get("color") {
+        let s = v.to_string();
+        spectrum.color = parse_hex_color(&s).ok_or_else(|| format!("Invalid color '{}'", s))?;
+    }
+    if let Some(v) = num("gap") {
+        spectrum.gap = v.clamp(0.0, 0.95);
+    }
+    if let Some(v) = flag("rounded") {
+        spectrum.rounded = v;
+    }
+    if let Some(v) = num("line_width") {
+        spectrum.line_width = v.max(0.0);
+    }
+    if let Some(v) = num("inner_radius") {
+        spectrum.inner_radius = v.clamp(0.0, 1.0);
+    }
+    if let Some(v) = num("opacity") {
+        spectrum.opacity = Animated::new(v);
+    }
+    Ok(())
+}
+
+/// Create a `ProgressNode` from `add_progress` props and append it to `parent`.
+///
+/// A `track` prop makes the bar follow that audio track; otherwise `source` picks the
+/// scene's or the movie's clock.
+fn add_progress_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let source = props
+        .get("source")
+        .map(|v| v.to_string())
+        .filter(|s| s != "audio");
+    let track = match props.get("track") {
+        Some(v) => {
+            let track = v
+                .clone()
+                .try_cast::<AudioTrackHandle>()
+                .ok_or("track must be an audio track")?;
+            if !Arc::ptr_eq(director, &track.director) {
+                return Err("Audio track belongs to another movie".into());
+            }
+            Some(track.id)
+        }
+        None => None,
+    };
+    let source = match (track, source) {
+        (Some(_), Some(s)) => {
+            return Err(format!("Progress source '{}' can't take a track", s).into())
+        }
+        (Some(id), None) => ProgressSource::Audio(id),
+        (None, Some(s)) => {
+            parse_progress_source(&s).ok_or_else(|| format!("Unknown progress source '{}'", s))?
+        }
+        (None, None) if props.contains_key("source") => {
+            return Err("Audio progress needs a track".into())
+        }
+        (None, None) => ProgressSource::Scene,
+    };
+
+    let mut progress = ProgressNode::new(source);
+    apply_progress_props(&mut progress, props)?;
+    parse_layout_style(props, &mut progress.style);
+
+    let mut d = director.lock().unwrap();
+    let id = d.scene.add_node(Box::new(progress));
+    if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+        if let Some(n) = d.scene.get_node_mut(id) {
+            n.z_index = z as i32;
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Apply the styling props of `add_progress` to a progress node.
+fn apply_progress_props(progress: &mut ProgressNode, props: &rhai::Map) -> Result<(), String> {
+    let num = |key: &str| props.get(key).and_then(number);
+    let color = |key: &str| -> Result<Option<Color>, String> {
+        props
+            .get(key)
+            .map(|v| {
+                let s = v.to_string();
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid {} '{}'", key, s))
+            })
+            .transpose()
+    };
+
+    if let Some(v) = num("start") {
+        progress.start = v;
+    }
+    if let Some(v) = num("end") {
+        progress.end = Some(v);
+    }
+    if progress.end.is_some_and(|end| end <= progress.start) {
+        return Err("end must be after start".to_string());
+    }
+    if let Some(v) = props.get("markers") {
+        let markers = v
+            .clone()
+            .into_array()
+            .map_err(|_| "markers must be an array")?;
+        progress.markers = markers
+            .iter()
+            .map(|m| number(m).ok_or_else(|| format!("Invalid marker '{}'", m)))
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(v) = num("gap") {
+        progress.segment_gap = v.max(0.0) as f32;
+    }
+    if let Some(c) = color("background")? {
+        progress.background = c;
+    }
+    if let Some(fill) = props.get("fill") {
+        if let Ok(s) = fill.clone().into_string() {
+            progress.fill =
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid fill color '{}'", s))?;
+        } else {
+            let mut gradient = parse_gradient(fill).ok_or("Fill must be a color or a gradient")?;
+            if fill.is_array() {
+                // Plain colour lists run along the bar
+                gradient.end = (1.0, 0.0);
+            }
+            progress.fill_gradient = Some(gradient);
+        }
+    }
+    if let Some(v) = num("radius") {
+        progress.corner_radius = Some(v.max(0.0) as f32);
+    }
+    match props.get("knob") {
+        Some(v) if v.as_bool() == Ok(true) => progress.knob_radius = 8.0,
+        Some(v) if v.as_bool() == Ok(false) => progress.knob_radius = 0.0,
+        Some(v) => return Err(format!("Invalid knob '{}'", v)),
+        None => {}
+    }
+    if let Some(v) = num("knob_radius") {
+        progress.knob_radius = v.max(0.0) as f32;
+    }
+    if let Some(c) = color("knob_color")? {
+        progress.knob_color = c;
+    }
+    if let Some(v) = num("opacity") {
+        progress.opacity = Animated::new(v as f32);
+    }
+    Ok(())
+}
+
+/// Create a `ParticleNode` from `add_particles` props and append it to `parent`.
+///
+/// Without a `seed` prop the node derives one from the movie seed and its id, so
+/// emitters differ from each other but stay reproducible.
+fn add_particle_node(
+    director: &Arc<Mutex<Director>>,
+    parent: NodeId,
+    props: &rhai::Map,
+) -> Result<NodeHandle, Box<EvalAltResult>> {
+    let mut d = director.lock().unwrap();
+    let mut particles = ParticleNode::new(0);
+    apply_particle_props(&mut d, &mut particles, props)?;
+    parse_layout_style(props, &mut particles.style);
+    let seed = props.get("seed").and_then(|v| v.as_int().ok());
+
+    let id = d.scene.add_node(Box::new(particles));
+    let movie_seed = d.seed;
+    if let Some(n) = d.scene.get_node_mut(id) {
+        if let Some(z) = props.get("z_index").and_then(|v| v.as_int().ok()) {
+            n.z_index = z as i32;
+        }
+        if let Some(p) = n.element.as_any_mut().downcast_mut::<ParticleNode>() {
+            p.set_seed(seed.map_or_else(
+                || movie_seed ^ (id as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                |s| s as u64,
+            ));
+        }
+    }
+    d.scene.add_child(parent, id);
+
+    Ok(NodeHandle {
+        director: director.clone(),
+        id,
+    })
+}
+
+/// Parse the `shape` kind (and `d` path data) of `add_shape` props.
+fn parse_shape_geometry(props: &rhai::Map) -> Result<ShapeGeometry, String> {
+    let shape = props
+        .get("shape")
+        .and_then(|v| v.clone().into_string().ok())
+        .unwrap_or_else(|| "rect".to_string());
+    match shape.as_str() {
+        "rect" => Ok(ShapeGeometry::Rect),
+        "ellipse" | "circle" => Ok(ShapeGeometry::Ellipse),
+        "polygon" => Ok(ShapeGeometry::Polygon),
+        "star" => Ok(ShapeGeometry::Star),
+        "path" => {
+            let d = props
+                .get("d")
+                .and_then(|v| v.clone().into_string().ok())
+                .unwrap_or_default();
+            skia_safe::Path::from_svg(&d)
+                .map(ShapeGeometry::Path)
+                .ok_or_else(|| format!("Invalid SVG path: {}", d))
+        }
+        other => Err(format!("Unknown shape '{}'", other)),
+    }
+}
+
+/// Apply shape-specific properties (fill, stroke, geometry parameters) from a Rhai map
+pub(super) fn apply_shape_props(shape: &mut ShapeNode, props: &rhai::Map) -> Result<(), String> {
+    let num = |key: &str| {
+        props.get(key).and_then(|v| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f64))
+        })
+    };
+
+    if let Some(fill) = props.get("fill") {
+        if let Ok(s) = fill.clone().into_string() {
+            let color = parse_hex_color(&s).ok_or_else(|| format!("Invalid fill color '{}'", s))?;
+            shape.fill = Some(ShapeFill::Solid(Animated::new(color)));
+        } else {
+            let gradient = parse_gradient(fill).ok_or("Fill must be a color or a gradient")?;
+            let radial = fill
+                .clone()
+                .try_cast::<Map>()
+                .and_then(|m| m.get("type").and_then(|v| v.clone().into_string().ok()))
+                .is_some_and(|t| t == "radial");
+            shape.fill = Some(if radial {
+                ShapeFill::RadialGradient(gradient)
+            } else {
+                ShapeFill::LinearGradient(gradient)
+            });
+        }
+    }
+
+    if let Some(s) = props
+        .get("stroke")
+        .and_then(|v| v.clone().into_string().ok())
+    {
+        let color = parse_hex_color(&s).ok_or_else(|| format!("Invalid stroke color '{}'", s))?;
+        shape.stroke_color = Some(Animated::new(color));
+        shape.stroke_width = Animated::new(1.0);
+    }
+    if let Some(v) = num("stroke_width") {
+        shape.stroke_width = Animated::new(v as f32);
+    }
+    if let Some(s) = props
+        .get("stroke_cap")
+        .and_then(|v| v.clone().into_string().ok())
+    {
+        shape.stroke_cap =
+            parse_stroke_cap(&s).ok_or_else(|| format!("Unknown stroke cap '{}'", s))?;
+    }
+    if let Some(s) = props
+        .get("stroke_join")
+        .and_then(|v| v.clone().into_string().ok())
+    {
+        shape.stroke_join =
+            parse_stroke_join(&s).ok_or_else(|| format!("Unknown stroke join '{}'", s))?;
+    }
+    if let Some(arr) = props.get("dash").and_then(|v| v.clone().into_array().ok()) {
+        shape.dash = arr
+            .iter()
+            .filter_map(|v| {
+                v.as_float()
+                    .ok()
+                    .or_else(|| v.as_int().ok().map(|i| i as f64))
+            })
+            .map(|v| v.max(0.0) as f32)
+            .collect();
+    }
+    if let Some(v) = num("dash_offset") {
+        shape.dash_offset = Animated::new(v as f32);
+    }
+    if let Some(v) = num("corner_radius") {
+        shape.corner_radius = Animated::new(v as f32);
+    }
+    if let Some(v) = num("points") {
+        shape.points = Animated::new(v as f32);
+    }
+    if let Some(v) = num("inner_radius") {
+        shape.inner_radius = Animated::new(v as f32);
+    }
+    if let Some(v) = num("trim_start") {
+        shape.trim_start = Animated::new(v as f32);
+    }
+    if let Some(v) = num("trim_end") {
+        shape.trim_end = Animated::new(v as f32);
+    }
+    if let Some(v) = num("trim_offset") {
+        shape.trim_offset = Animated::new(v as f32);
+    }
+    if let Some(v) = num("opacity") {
+        shape.opacity = Animated::new(v as f32);
+    }
+    Ok(())
+}
+
+/// Apply SVG-specific properties from a Rhai map
+pub(super) fn apply_vector_props(vector: &mut VectorNode, props: &rhai::Map) {
+    let num = |key: &str| {
+        props.get(key).and_then(|v| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f64))
+        })
+    };
+    if let Some(v) = num("trim_start") {
+        vector.trim_start = Animated::new(v as f32);
+    }
+    if let Some(v) = num("trim_end") {
+        vector.trim_end = Animated::new(v as f32);
+    }
+    if let Some(v) = num("trim_offset") {
+        vector.trim_offset = Animated::new(v as f32);
+    }
+}
+
+/// Numbers from a single number or an array of numbers.
+fn numbers(value: &rhai::Dynamic) -> Vec<f32> {
+    let num = |v: &rhai::Dynamic| {
+        v.as_float()
+            .ok()
+            .or_else(|| v.as_int().ok().map(|i| i as f64))
+            .map(|v| v as f32)
+    };
+    match value.clone().into_array() {
+        Ok(arr) => arr.iter().filter_map(num).collect(),
+        Err(_) => num(value).into_iter().collect(),
+    }
+}
+
+/// Apply chart styling properties from a Rhai map
+pub(super) fn apply_chart_props(chart: &mut ChartNode, props: &rhai::Map) -> Result<(), String> {
+    let num = |key: &str| props.get(key).map(numbers).and_then(|v| v.first().copied());
+    let flag = |key: &str| props.get(key).and_then(|v| v.as_bool().ok());
+    let string = |key: &str| props.get(key).and_then(|v| v.clone().into_string().ok());
+    let color = |key: &str| -> Result<Option<Color>, String> {
+        string(key)
+            .map(|s| parse_hex_color(&s).ok_or_else(|| format!("Invalid {} '{}'", key, s)))
+            .transpose()
+    };
+
+    if let Some(arr) = props
+        .get("colors")
+        .and_then(|v| v.clone().into_array().ok())
+    {
+        chart.colors = arr
+            .into_iter()
+            .map(|c| {
+                let s = c.to_string();
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid chart color '{}'", s))
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(family) = string("font") {
+        chart.font_family = Some(family);
+    }
+    if let Some(v) = num("font_size") {
+        chart.font_size = v.max(1.0);
+    }
+    if let Some(c) = color("text_color")? {
+        chart.text_color = c;
+    }
+    if let Some(c) = color("axis_color")? {
+        chart.axis_color = c;
+    }
+    match props.get("grid_color") {
+        Some(v) if v.as_bool() == Ok(false) || v.to_string() == "none" => chart.grid_color = None,
+        Some(_) => chart.grid_color = color("grid_color")?,
+        None => {}
+    }
+    if let Some(v) = flag("axes") {
+        chart.show_axes = v;
+    }
+    if let Some(v) = flag("legend") {
+        chart.show_legend = v;
+    }
+    if let Some(v) = flag("values") {
+        chart.show_values = v;
+    }
+    if let Some(v) = num("min") {
+        chart.min = Some(v);
+    }
+    if let Some(v) = num("max") {
+        chart.max = Some(v);
+    }
+    if let Some(s) = string("prefix") {
+        chart.prefix = s;
+    }
+    if let Some(s) = string("suffix") {
+        chart.suffix = s;
+    }
+    if let Some(v) = num("decimals") {
+        chart.decimals = Some(v.max(0.0) as usize);
+    }
+    if let Some(v) = num("bar_gap") {
+        chart.bar_gap = v;
+    }
+    if let Some(v) = num("line_width") {
+        chart.line_width = v;
+    }
+    if let Some(v) = num("inner_radius") {
+        chart.inner_radius = v;
+    }
+    if let Some(v) = num("stagger") {
+        chart.stagger = v;
+    }
+    if let Some(v) = num("build") {
+        chart.build = Animated::new(v);
+    }
+    if let Some(v) = num("opacity") {
+        chart.opacity = Animated::new(v);
+    }
+    Ok(())
+}
+
+/// Apply particle emitter, motion and appearance properties from a Rhai map
+pub(super) fn apply_particle_props(
+    d: &mut Director,
+    particles: &mut ParticleNode,
+    props: &rhai::Map,
+) -> Result<(), String> {
+    let nums = |key: &str| props.get(key).map(numbers);
+    let num = |key: &str| nums(key).and_then(|v| v.first().copied());
+    // A number is a fixed value, `[min, max]` a random range
+    let range = |key: &str| -> Result<Option<(f32, f32)>, String> {
+        match nums(key).as_deref() {
+            None => Ok(None),
+            Some([v]) => Ok(Some((*v, *v))),
+            Some([min, max]) => Ok(Some((*min, *max))),
+            Some(_) => Err(format!("'{}' must be a number or [min, max]", key)),
+        }
+    };
+
+    if let Some(emitter) = props.get("emitter") {
+        particles.emitter = parse_particle_emitter(emitter)?;
+    }
+    if let Some(v) = num("rate") {
+        particles.rate = v.max(0.0);
+    }
+    if let Some(v) = num("duration") {
+        particles.emit_duration = Some(v.max(0.0) as f64);
+    }
+    if let Some(arr) = props
+        .get("bursts")
+        .and_then(|v| v.clone().into_array().ok())
+    {
+        particles.bursts = arr
+            .iter()
+            .map(|b| match numbers(b).as_slice() {
+                [time, count] => Ok((time.max(0.0) as f64, count.max(0.0) as u32)),
+                _ => Err("Each burst must be [time, count]".to_string()),
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(v) = range("lifetime")? {
+        particles.lifetime = v;
+    }
+    if let Some(v) = range("speed")? {
+        particles.speed = v;
+    }
+    if let Some(v) = range("spin")? {
+        particles.spin = v;
+    }
+    if let Some(v) = range("scale")? {
+        particles.size_scale = v;
+    }
+    if let Some(v) = num("direction") {
+        particles.direction = v;
+    }
+    if let Some(v) = num("spread") {
+        particles.spread = v;
+    }
+    if let Some(v) = nums("gravity") {
+        particles.gravity = match v.as_slice() {
+            [y] => (0.0, *y),
+            [x, y] => (*x, *y),
+            _ => return Err("'gravity' must be a number or [x, y]".to_string()),
+        };
+    }
+    if let Some(v) = num("drag") {
+        particles.drag = v.max(0.0);
+    }
+    if let Some(v) = num("turbulence") {
+        particles.turbulence = Animated::new(v);
+    }
+    if let Some(v) = num("turbulence_scale") {
+        particles.turbulence_scale = v;
+    }
+    if let Some(sprite) = props.get("sprite") {
+        particles.sprite = parse_particle_sprite(d, sprite)?;
+    }
+    if let Some(v) = nums("size").filter(|v| !v.is_empty()) {
+        particles.size = LifeCurve::even(v);
+    }
+    if let Some(v) = nums("alpha").filter(|v| !v.is_empty()) {
+        particles.alpha = LifeCurve::even(v);
+    }
+    if let Some(color) = props.get("color") {
+        let names = match color.clone().into_array() {
+            Ok(arr) => arr,
+            Err(_) => vec![color.clone()],
+        };
+        let colors = names
+            .into_iter()
+            .map(|c| {
+                let s = c
+                    .into_string()
+                    .map_err(|_| "'color' must be a hex string or array")?;
+                parse_hex_color(&s).ok_or_else(|| format!("Invalid particle color '{}'", s))
+            })
+            .collect::<Result<Vec<Color>, String>>()?;
+        if !colors.is_empty() {
+            particles.color = LifeCurve::even(colors);
+        }
+    }
+    if let Some(v) = num("opacity") {
+        particles.opacity = Animated::new(v);
+    }
+    Ok(())
+}
+
+/// Parse an emitter given as `"point"`, `"rect"` or a map with a `type` key.
+fn parse_particle_emitter(value: &rhai::Dynamic) -> Result<ParticleEmitter, String> {
+    let map = match value.clone().into_string() {
+        Ok(kind) => {
+            let mut map = Map::new();
+            map.insert("type".into(), kind.into());
+            map
+        }
+        Err(_) => value
+            .clone()
+            .try_cast::<Map>()
+            .ok_or("Emitter must be a string or a map")?,
+    };
+    let num = |key: &str| map.get(key).map(numbers).and_then(|v| v.first().copied());
+    let point = |key: &str| match map.get(key).map(numbers).as_deref() {
+        Some([x, y]) => Ok(Point::new(*x, *y)),
+        _ => Err(format!("Emitter '{}' must be [x, y]", key)),
+    };
+    let kind = map
+        .get("type")
+        .and_then(|v| v.clone().into_string().ok())
+        .unwrap_or_else(|| "point".to_string());
+
+    match kind.as_str() {
+        "point" => Ok(ParticleEmitter::Point(
+            num("x").zip(num("y")).map(|(x, y)| Point::new(x, y)),
+        )),
+        "line" => Ok(ParticleEmitter::Line(point("from")?, point("to")?)),
+        "rect" | "box" => Ok(ParticleEmitter::Rect(num("width").zip(num("height")).map(
+            |(w, h)| Rect::from_xywh(num("x").unwrap_or(0.0), num("y").unwrap_or(0.0), w, h),
+        ))),
+        "shape" | "path" => {
+            let d = map
+                .get("d")
+                .and_then(|v| v.clone().into_string().ok())
+                .unwrap_or_default();
+            skia_safe::Path::from_svg(&d)
+                .map(ParticleEmitter::Shape)
+                .ok_or_else(|| format!("Invalid SVG path: {}", d))
+        }
+        other => Err(format!("Unknown emitter '{}'", other)),
+    }
+}
+
+/// Parse a sprite given as `"circle"`, `"square"` or a map with one of `path`, `image`
+/// or `text` (plus an optional `font` family).
+fn parse_particle_sprite(
+    d: &mut Director,
+    value: &rhai::Dynamic,
+) -> Result<ParticleSprite, String> {
+    if let Ok(kind) = value.clone().into_string() {
+        return match kind.as_str() {
+            "circle" => Ok(ParticleSprite::Circle),
+            "square" => Ok(ParticleSprite::Square),
+            other => Err(format!("Unknown particle sprite '{}'", other)),
+        };
+    }
+    let map = value
+        .clone()
+        .try_cast::<Map>()
+        .ok_or("Sprite must be a string or a map")?;
+    let string = |key: &str| map.get(key).and_then(|v| v.clone().into_string().ok());
+
+    if let Some(paths) = map.get("path") {
+        let sources = match paths.clone().into_array() {
+            Ok(arr) => arr,
+            Err(_) => vec![paths.clone()],
+        };
+        let paths = sources
+            .into_iter()
+            .map(|p| {
+                let d = p.into_string().unwrap_or_default();
+                skia_safe::Path::from_svg(&d)
+                    .map(|p| unit_sprite_path(&p))
+                    .ok_or_else(|| format!("Invalid SVG path: {}", d))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        return Ok(ParticleSprite::Paths(paths));
+    }
+
+    if let Some(file) = string("image") {
+        let bytes = d
+            .assets
+            .loader
+            .load_bytes(&file)
+            .map_err(|e| format!("Failed to load sprite '{}': {}", file, e))?;
+        return Image::from_encoded(Data::new_copy(&bytes))
+            .map(ParticleSprite::Image)
+            .ok_or_else(|| format!("Failed to decode sprite '{}'", file));
+    }
+
+    if let Some(text) = string("text") {
+        let typeface = {
+            let mut fonts = d.assets.font_collection.lock().unwrap();
+            let family = string("font").unwrap_or_default();
+            fonts
+                .find_typefaces(&[family], FontStyle::default())
+                .into_iter()
+                .next()
+                .or_else(|| fonts.default_fallback())
+                .ok_or("No font available for text sprites")?
+        };
+        let font = Font::new(typeface, 64.0);
+        let paths: Vec<_> = font
+            .str_to_glyphs_vec(&text)
+            .into_iter()
+            .filter_map(|glyph| font.get_path(glyph))
+            .filter(|p| !p.is_empty())
+            .map(|p| unit_sprite_path(&p))
+            .collect();
+        if paths.is_empty() {
+            return Err(format!("Text sprite '{}' has no visible glyphs", text));
+        }
+        return Ok(ParticleSprite::Paths(paths));
+    }
+
+    Err("Sprite map needs a 'path', 'image' or 'text' key".to_string())
+}
+
 /// Apply box-specific properties from a Rhai map
-fn apply_box_props(box_node: &mut BoxNode, props: &rhai::Map) {
+pub(super) fn apply_box_props(box_node: &mut BoxNode, props: &rhai::Map) {
     if let Some(c) = props.get("bg_color") {
         if let Ok(s) = c.clone().into_string() {
             if let Some(color) = parse_hex_color(&s) {
@@ -691,10 +2013,26 @@ fn apply_box_props(box_node: &mut BoxNode, props: &rhai::Map) {
     {
         box_node.overflow = s;
     }
+    if let Some(v) = props.get("backdrop_blur").and_then(|v| v.as_float().ok()) {
+        box_node.backdrop_blur = Animated::new(v as f32);
+    }
+    if let Some(v) = props
+        .get("backdrop_saturation")
+        .and_then(|v| v.as_float().ok())
+    {
+        box_node.backdrop_saturation = Animated::new(v as f32);
+    }
+    if let Some(c) = props.get("backdrop_tint") {
+        if let Ok(s) = c.clone().into_string() {
+            if let Some(color) = parse_tint_color(&s) {
+                box_node.backdrop_tint = Some(Animated::new(color));
+            }
+        }
+    }
 }
 
 /// Apply text-specific properties from a Rhai map
-fn apply_text_props(text_node: &mut TextNode, props: &rhai::Map) {
+pub(super) fn apply_text_props(text_node: &mut TextNode, props: &rhai::Map) {
     if let Some(s) = props.get("size").and_then(|v| v.as_float().ok()) {
         text_node.default_font_size = Animated::new(s as f32);
     }
@@ -731,4 +2069,7 @@ fn apply_text_props(text_node: &mut TextNode, props: &rhai::Map) {
     if let Some(v) = props.get("max_size").and_then(|v| v.as_float().ok()) {
         text_node.max_size = v as f32;
     }
+    if let Some(v) = props.get("tabular").and_then(|v| v.as_bool().ok()) {
+        text_node.tabular_figures = v;
+    }
 }
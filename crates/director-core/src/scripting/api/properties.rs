@@ -7,15 +7,21 @@
 //! - **Style**: `set_style` for layout and visual style changes
 //! - **Transform**: `set_pivot` for transform origin
 //! - **Layering**: `set_z_index` for z-order
-//! - **Masking**: `set_mask` for alpha masking
+//! - **Masking**: `set_mask` for alpha and luma track mattes
+//! - **Clipping**: `set_clip_path` for shape-based clips with feather, expansion and invert
 //! - **Blending**: `set_blend_mode` for compositing modes
 
-use crate::node::{ImageNode, VideoNode};
-use rhai::Engine;
+use crate::animation::Animated;
+use crate::director::Director;
+use crate::node::{ImageNode, VectorNode, VideoNode};
+use crate::types::{ClipPath, ClipShape, MatteMode, NodeId};
+use rhai::{Dynamic, Engine, EvalAltResult, Map};
+use std::sync::Arc;
 
 use super::super::types::NodeHandle;
 use super::super::utils::{
-    parse_layout_style, parse_object_fit, parse_spans_from_dynamic, parse_text_style,
+    parse_clip_shape, parse_layout_style, parse_matte_mode, parse_object_fit,
+    parse_spans_from_dynamic, parse_text_style,
 };
 
 /// Register property-related Rhai functions.
@@ -27,6 +33,7 @@ pub fn register(engine: &mut Engine) {
             let mut d = node.director.lock().unwrap();
             if let Some(n) = d.scene.get_node_mut(node.id) {
                 n.element.set_rich_text(spans);
+                n.dirty_style = true;
             }
         },
     );
@@ -81,27 +88,38 @@ pub fn register(engine: &mut Engine) {
 
     engine.register_fn("set_mask", |node: &mut NodeHandle, mask: NodeHandle| {
         let mut d = node.director.lock().unwrap();
+        attach_mask(&mut d, node.id, mask.id, MatteMode::Alpha);
+    });
 
-        // 1. Get the mask node's current parent
-        let old_parent = if let Some(m_node) = d.scene.get_node(mask.id) {
-            m_node.parent
-        } else {
-            None
-        };
+    engine.register_fn(
+        "set_mask",
+        |node: &mut NodeHandle, mask: NodeHandle, mode: &str| -> Result<(), Box<EvalAltResult>> {
+            let mode =
+                parse_matte_mode(mode).ok_or_else(|| format!("Unknown matte mode '{}'", mode))?;
+            let mut d = node.director.lock().unwrap();
+            attach_mask(&mut d, node.id, mask.id, mode);
+            Ok(())
+        },
+    );
 
-        // 2. Remove mask from old parent's children list
-        if let Some(p_id) = old_parent {
-            d.scene.remove_child(p_id, mask.id);
-        }
+    engine.register_fn(
+        "set_clip_path",
+        |node: &mut NodeHandle, shape: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            set_clip_path(node, shape, &Map::new())
+        },
+    );
 
-        // 3. Set mask's parent to the new owner (node.id)
-        if let Some(m_node) = d.scene.get_node_mut(mask.id) {
-            m_node.parent = Some(node.id);
-        }
+    engine.register_fn(
+        "set_clip_path",
+        |node: &mut NodeHandle, shape: Dynamic, options: Map| -> Result<(), Box<EvalAltResult>> {
+            set_clip_path(node, shape, &options)
+        },
+    );
 
-        // 4. Assign mask_node to owner
+    engine.register_fn("clear_clip_path", |node: &mut NodeHandle| {
+        let mut d = node.director.lock().unwrap();
         if let Some(n) = d.scene.get_node_mut(node.id) {
-            n.mask_node = Some(mask.id);
+            n.clip_path = None;
         }
     });
 
@@ -144,3 +162,81 @@ pub fn register(engine: &mut Engine) {
         }
     });
 }
+
+/// Makes `mask_id` the mask of `owner_id`, detaching it from wherever it was.
+fn attach_mask(d: &mut Director, owner_id: NodeId, mask_id: NodeId, mode: MatteMode) {
+    // 1-2. Detach the mask from its current parent (or previous owner)
+    d.scene.detach(mask_id);
+
+    // 3. Set mask's parent to the new owner
+    if let Some(m_node) = d.scene.get_node_mut(mask_id) {
+        m_node.parent = Some(owner_id);
+    }
+
+    // 4. Assign mask_node to owner
+    if let Some(n) = d.scene.get_node_mut(owner_id) {
+        n.mask_node = Some(mask_id);
+        n.mask_mode = mode;
+    }
+}
+
+/// Builds a clip from an SVG path string, a shape map or a vector node's outline.
+///
+/// Options: `feather` and `expansion` in pixels, `invert` to keep the outside.
+fn set_clip_path(
+    node: &NodeHandle,
+    shape: Dynamic,
+    options: &Map,
+) -> Result<(), Box<EvalAltResult>> {
+    let vector = shape.clone().try_cast::<NodeHandle>();
+    if let Some(v) = &vector {
+        if !Arc::ptr_eq(&node.director, &v.director) {
+            return Err("Clip source belongs to a different movie".into());
+        }
+    }
+
+    let mut d = node.director.lock().unwrap();
+    let shape = if let Some(v) = vector {
+        let outline = d
+            .scene
+            .get_node(v.id)
+            .and_then(|n| n.element.as_any().downcast_ref::<VectorNode>())
+            .map(|vn| vn.outline())
+            .ok_or("Clip source node is not a vector node")?;
+        ClipShape::Outline {
+            path: outline.0,
+            size: outline.1,
+        }
+    } else if let Some(map) = shape.clone().try_cast::<Map>() {
+        parse_clip_shape(&map)?
+    } else if let Ok(svg) = shape.into_string() {
+        ClipShape::Path(
+            skia_safe::Path::from_svg(&svg).ok_or_else(|| format!("Invalid SVG path: {}", svg))?,
+        )
+    } else {
+        return Err("set_clip_path expects an SVG path, a shape map or a vector node".into());
+    };
+
+    let num = |key: &str| {
+        options.get(key).and_then(|v| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f64))
+        })
+    };
+
+    let mut clip = ClipPath::new(shape);
+    clip.feather = Animated::new(num("feather").unwrap_or(0.0).max(0.0) as f32);
+    clip.expansion = Animated::new(num("expansion").unwrap_or(0.0) as f32);
+    clip.invert = options
+        .get("invert")
+        .and_then(|v| v.as_bool().ok())
+        .unwrap_or(false);
+
+    if let Some(n) = d.scene.get_node_mut(node.id) {
+        n.clip_path = Some(clip);
+        // The shape itself isn't part of the render cache key
+        n.mark_content_changed();
+    }
+    Ok(())
+}
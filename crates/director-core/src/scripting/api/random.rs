@@ -0,0 +1,118 @@
+//! # Random API
+//!
+//! Seeded random helpers for Rhai scripts.
+//!
+//! ## Responsibilities
+//! - **Numbers**: `rand_float`, `rand_int`
+//! - **Collections**: `rand_choice`, `shuffle`
+//! - **Noise**: `noise(x, y, t)` Perlin noise
+//! - **Colors**: `rand_color` hex strings
+//!
+//! Every movie owns a generator seeded by the `seed` in its `new_director` config.
+//! The method forms (`movie.rand_float(...)`, `items.shuffle(movie)`, ...) draw from
+//! that movie's generator; the free helpers draw from the most recently created
+//! movie's, so a seed makes every value reproducible.
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+use std::sync::{Arc, Mutex};
+
+use super::super::rng::ScriptRng;
+use super::super::types::MovieHandle;
+
+/// The generator the free helpers draw from, replaced by each `new_director`.
+pub type CurrentRng = Arc<Mutex<Arc<Mutex<ScriptRng>>>>;
+
+fn rand_choice(rng: &Mutex<ScriptRng>, items: Array) -> Result<Dynamic, Box<EvalAltResult>> {
+    if items.is_empty() {
+        return Err("rand_choice called with an empty array".into());
+    }
+    let index = rng.lock().unwrap().range_i64(0, items.len() as i64 - 1);
+    Ok(items[index as usize].clone())
+}
+
+fn rand_color(rng: &Mutex<ScriptRng>, saturation: f64, lightness: f64) -> String {
+    let hue = rng.lock().unwrap().range_f64(0.0, 360.0);
+    hsl_to_hex(hue, saturation, lightness)
+}
+
+/// Register randomness-related Rhai functions.
+pub fn register(engine: &mut Engine, current: CurrentRng) {
+    let rng = move || current.lock().unwrap().clone();
+
+    let r = rng.clone();
+    engine.register_fn("rand_float", move |min: f64, max: f64| {
+        r().lock().unwrap().range_f64(min, max)
+    });
+    engine.register_fn(
+        "rand_float",
+        |movie: &mut MovieHandle, min: f64, max: f64| movie.rng.lock().unwrap().range_f64(min, max),
+    );
+
+    let r = rng.clone();
+    engine.register_fn("rand_int", move |min: i64, max: i64| {
+        r().lock().unwrap().range_i64(min, max)
+    });
+    engine.register_fn("rand_int", |movie: &mut MovieHandle, min: i64, max: i64| {
+        movie.rng.lock().unwrap().range_i64(min, max)
+    });
+
+    let r = rng.clone();
+    engine.register_fn("rand_choice", move |items: Array| rand_choice(&r(), items));
+    engine.register_fn("rand_choice", |movie: &mut MovieHandle, items: Array| {
+        rand_choice(&movie.rng, items)
+    });
+
+    let r = rng.clone();
+    engine.register_fn("shuffle", move |items: &mut Array| {
+        r().lock().unwrap().shuffle(items);
+    });
+    engine.register_fn("shuffle", |items: &mut Array, movie: MovieHandle| {
+        movie.rng.lock().unwrap().shuffle(items);
+    });
+
+    let r = rng.clone();
+    engine.register_fn("noise", move |x: f64, y: f64, t: f64| {
+        r().lock().unwrap().noise(x, y, t)
+    });
+    engine.register_fn(
+        "noise",
+        |movie: &mut MovieHandle, x: f64, y: f64, t: f64| movie.rng.lock().unwrap().noise(x, y, t),
+    );
+
+    let r = rng.clone();
+    engine.register_fn("rand_color", move || rand_color(&r(), 0.65, 0.55));
+    engine.register_fn("rand_color", |movie: &mut MovieHandle| {
+        rand_color(&movie.rng, 0.65, 0.55)
+    });
+
+    let r = rng;
+    engine.register_fn("rand_color", move |saturation: f64, lightness: f64| {
+        rand_color(&r(), saturation, lightness)
+    });
+    engine.register_fn(
+        "rand_color",
+        |movie: &mut MovieHandle, saturation: f64, lightness: f64| {
+            rand_color(&movie.rng, saturation, lightness)
+        },
+    );
+}
+
+/// Converts HSL (hue in degrees, saturation and lightness in 0..1) to `#RRGGBB`.
+fn hsl_to_hex(h: f64, s: f64, l: f64) -> String {
+    let s = s.clamp(0.0, 1.0);
+    let l = l.clamp(0.0, 1.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let hp = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (hp % 2.0 - 1.0).abs());
+    let (r, g, b) = match hp as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let to_byte = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    format!("#{:02X}{:02X}{:02X}", to_byte(r), to_byte(g), to_byte(b))
+}
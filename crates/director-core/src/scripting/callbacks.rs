@@ -0,0 +1,200 @@
+//! # Frame Callbacks
+//!
+//! Runtime support for `on_frame` script callbacks.
+//!
+//! ## Responsibilities
+//! - **Sandbox**: A restricted Rhai engine with operation and call-depth limits,
+//!   so a runaway callback fails instead of stalling export.
+//! - **Proxies**: `FrameNode` / `FrameContext` expose node properties to callbacks
+//!   without touching the `Director` mutex (which is held during rendering).
+//! - **Dispatch**: `run_frame_callbacks` invokes movie and node callbacks each frame.
+//!
+//! ## Callback API
+//! Callbacks (and any script functions they call) see core Rhai, the `FrameNode` /
+//! `FrameContext` proxies and `noise(x, y, t)`. Script-defined functions are
+//! available when the script was run through `scripting::run_script`, which keeps
+//! them in `Director::script_functions`. The rest of the scripting API is not.
+//!
+//! ## Key Types
+//! - `FrameNode`: Read/write view of one node for the current frame.
+//! - `FrameContext`: Passed to movie callbacks; resolves `NodeHandle`s to `FrameNode`s.
+
+use crate::director::Director;
+use crate::scene::SceneGraph;
+use crate::types::NodeId;
+use rhai::{Dynamic, Engine, EvalAltResult, FnPtr};
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::warn;
+
+use super::rng::Noise;
+use super::types::NodeHandle;
+
+/// Maximum Rhai operations a single callback invocation may perform.
+pub const MAX_CALLBACK_OPERATIONS: u64 = 100_000;
+/// Maximum function call nesting inside a callback.
+pub const MAX_CALLBACK_CALL_LEVELS: usize = 32;
+
+/// Properties exposed as `node.<name>` getters/setters on `FrameNode`.
+const FRAME_PROPERTIES: [&str; 9] = [
+    "x", "y", "scale", "scale_x", "scale_y", "rotation", "skew_x", "skew_y", "opacity",
+];
+
+/// A view of a single node, valid for the duration of one callback.
+#[derive(Clone)]
+pub struct FrameNode {
+    scene: Rc<RefCell<SceneGraph>>,
+    id: NodeId,
+}
+
+/// The argument passed to movie-level callbacks.
+#[derive(Clone)]
+pub struct FrameContext {
+    scene: Rc<RefCell<SceneGraph>>,
+}
+
+impl FrameNode {
+    /// Reads a transform value, layout size or element property.
+    pub fn get(&self, property: &str) -> Result<f64, Box<EvalAltResult>> {
+        let scene = self.scene.borrow();
+        let n = scene.get_node(self.id).ok_or("Node no longer exists")?;
+        n.get_property(property)
+            .map(|v| v as f64)
+            .ok_or_else(|| format!("Unknown property '{}'", property).into())
+    }
+
+    /// Overrides a transform value or element property for the current frame.
+    pub fn set(&mut self, property: &str, value: f64) -> Result<(), Box<EvalAltResult>> {
+        let mut scene = self.scene.borrow_mut();
+        let n = scene.get_node_mut(self.id).ok_or("Node no longer exists")?;
+        if n.set_property(property, value as f32) {
+            Ok(())
+        } else {
+            Err(format!("Unknown property '{}'", property).into())
+        }
+    }
+}
+
+/// Creates the sandboxed engine used to run callbacks.
+///
+/// Only the frame proxy API is registered: the full scripting API locks the
+/// `Director`, which is already borrowed while callbacks run.
+pub fn create_callback_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_CALLBACK_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALLBACK_CALL_LEVELS);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+
+    engine.register_type_with_name::<NodeHandle>("Node");
+    engine.register_type_with_name::<FrameNode>("FrameNode");
+    engine.register_type_with_name::<FrameContext>("FrameContext");
+
+    engine.register_fn("node", |ctx: &mut FrameContext, node: NodeHandle| {
+        FrameNode {
+            scene: ctx.scene.clone(),
+            id: node.id,
+        }
+    });
+
+    engine.register_fn("get", |n: &mut FrameNode, property: &str| n.get(property));
+    engine.register_fn("set", |n: &mut FrameNode, property: &str, value: f64| {
+        n.set(property, value)
+    });
+    engine.register_fn("set", |n: &mut FrameNode, property: &str, value: i64| {
+        n.set(property, value as f64)
+    });
+
+    for property in FRAME_PROPERTIES {
+        engine.register_get(property, move |n: &mut FrameNode| n.get(property));
+        engine.register_set(property, move |n: &mut FrameNode, v: f64| {
+            n.set(property, v)
+        });
+        engine.register_set(property, move |n: &mut FrameNode, v: i64| {
+            n.set(property, v as f64)
+        });
+    }
+    engine.register_get("width", |n: &mut FrameNode| n.get("width"));
+    engine.register_get("height", |n: &mut FrameNode| n.get("height"));
+
+    engine
+}
+
+/// Creates the callback engine for this director if it does not exist yet.
+///
+/// Callbacks get `noise(x, y, t)` for the director's seed, so they can vary smoothly
+/// over time and still render the same way on every run.
+pub(crate) fn ensure_callback_engine(director: &mut Director) {
+    if director.callback_engine.is_none() {
+        let mut engine = create_callback_engine();
+        let noise = Noise::new(director.seed);
+        engine.register_fn("noise", move |x: f64, y: f64, t: f64| noise.sample(x, y, t));
+        director.callback_engine = Some(Rc::new(engine));
+    }
+}
+
+/// Invokes all movie callbacks and the callbacks of nodes visited this frame.
+///
+/// The scene graph is moved into a shared cell for the duration of the calls so
+/// `FrameNode` proxies can mutate it, then moved back. Failing callbacks are
+/// logged and skipped.
+pub(crate) fn run_frame_callbacks(engine: &Engine, director: &mut Director, global_time: f64) {
+    let mut jobs: Vec<(Option<NodeId>, f64, FnPtr)> = director
+        .frame_callbacks
+        .iter()
+        .map(|cb| (None, global_time, cb.clone()))
+        .collect();
+
+    for &id in &director.scene.active_nodes {
+        if let Some(node) = director.scene.get_node(id) {
+            jobs.extend(
+                node.frame_callbacks
+                    .iter()
+                    .map(|cb| (Some(id), node.local_time, cb.clone())),
+            );
+        }
+    }
+
+    if jobs.is_empty() {
+        return;
+    }
+
+    let scene = Rc::new(RefCell::new(std::mem::replace(
+        &mut director.scene,
+        SceneGraph::new(),
+    )));
+    let ast = director.script_functions.clone();
+
+    for (target, time, callback) in jobs {
+        let result = match target {
+            Some(id) => callback.call::<Dynamic>(
+                engine,
+                &ast,
+                (
+                    time,
+                    FrameNode {
+                        scene: scene.clone(),
+                        id,
+                    },
+                ),
+            ),
+            None => callback.call::<Dynamic>(
+                engine,
+                &ast,
+                (
+                    time,
+                    FrameContext {
+                        scene: scene.clone(),
+                    },
+                ),
+            ),
+        };
+        if let Err(e) = result {
+            warn!("on_frame callback failed at t={:.3}: {}", time, e);
+        }
+    }
+
+    // Take the graph back even if a script kept a proxy alive.
+    director.scene = std::mem::replace(&mut *scene.borrow_mut(), SceneGraph::new());
+}
@@ -7,27 +7,35 @@
 //! - **Node Creation**: `create_box`, `create_text`, `create_image`, etc.
 //! - **Animation**: `animate_*` property setters.
 //! - **Director Control**: `set_duration`, `add_scene`, timeline manipulation.
+//! - **Script Runs**: `run_script` evaluates a movie script and keeps its functions
+//!   for `on_frame` callbacks.
 //!
 //! ## Pattern
 //! All bindings follow: `engine.register_fn("name", |ctx, ...| { ... })`
 //!
 //! ## Module Structure
-//! - `types`: Handle types (MovieHandle, SceneHandle, NodeHandle, AudioTrackHandle)
+//! - `types`: Handle types (MovieHandle, SceneHandle, NodeHandle, SymbolHandle, AudioTrackHandle)
 //! - `utils`: Parsing helpers (colors, layout, text, easing)
 //! - `theme`: Design system token API
-//! - `api/`: Sub-modules for lifecycle, nodes, animation, audio, effects, properties
+//! - `rng`: Seeded generator and Perlin noise behind the random helpers
+//! - `callbacks`: Sandbox engine and frame proxies for `on_frame` callbacks
+//! - `api/`: Sub-modules for lifecycle, nodes, hierarchy, animation, audio, effects, properties, random
 
 mod api;
+pub mod callbacks;
+pub mod rng;
 mod theme;
 pub mod types;
 pub mod utils;
 
+pub(crate) use api::hierarchy::sync_symbol_instances;
+pub use callbacks::{FrameContext, FrameNode};
 pub use theme::create_theme_api;
-pub use types::{AudioTrackHandle, MovieHandle, NodeHandle, SceneHandle};
+pub use types::{AudioTrackHandle, MovieHandle, NodeHandle, SceneHandle, SymbolHandle};
 
 use crate::tokens::DesignSystem;
 use crate::AssetLoader;
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 use std::sync::Arc;
 
 /// Registers the Director Engine API into the provided Rhai `Engine`.
@@ -42,3 +50,14 @@ pub fn register_rhai_api(engine: &mut Engine, loader: Arc<dyn AssetLoader>) {
     // Register all API functions
     api::register_all(engine, loader);
 }
+
+/// Evaluates a movie script and returns the movie it produces.
+///
+/// The script's functions are kept on the movie's `Director` so `on_frame`
+/// callbacks can call them.
+pub fn run_script(engine: &Engine, script: &str) -> Result<MovieHandle, Box<EvalAltResult>> {
+    let ast = engine.compile(script)?;
+    let movie = engine.eval_ast::<MovieHandle>(&ast)?;
+    movie.director.lock().unwrap().script_functions = ast.clone_functions_only();
+    Ok(movie)
+}
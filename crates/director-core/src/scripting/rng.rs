@@ -0,0 +1,198 @@
+//! # Script Randomness
+//!
+//! Seedable random numbers and noise for scripts.
+//!
+//! ## Responsibilities
+//! - **ScriptRng**: xoshiro256** generator behind `rand_float`, `rand_int`, `shuffle`, ...
+//! - **Noise**: Improved Perlin noise with a seed-derived permutation table.
+//!
+//! Both are implemented here rather than taken from `rand`, whose generators may change
+//! between releases. Only integer ops and basic float arithmetic are used, so a seed
+//! gives the same values on every platform and build.
+
+/// Deterministic random generator used by scripts.
+#[derive(Clone, Debug)]
+pub struct ScriptRng {
+    seed: u64,
+    state: [u64; 4],
+    noise: Noise,
+}
+
+impl ScriptRng {
+    /// Creates a generator whose whole output (including noise) is fixed by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: seed_state(seed),
+            noise: Noise::new(seed),
+        }
+    }
+
+    /// Creates a generator with a random seed.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// The seed this generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        next_xoshiro(&mut self.state)
+    }
+
+    /// Uniform value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Uniform value in `[min, max)`.
+    pub fn range_f64(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// Uniform integer in `[min, max]` (both inclusive), without modulo bias.
+    pub fn range_i64(&mut self, min: i64, max: i64) -> i64 {
+        let (min, max) = if max < min { (max, min) } else { (min, max) };
+        let span = (max.wrapping_sub(min) as u64).wrapping_add(1);
+        if span == 0 {
+            return self.next_u64() as i64;
+        }
+        let zone = u64::MAX - (u64::MAX - span + 1) % span;
+        loop {
+            let v = self.next_u64();
+            if v <= zone {
+                return min.wrapping_add((v % span) as i64);
+            }
+        }
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_i64(0, i as i64) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Perlin noise for this seed; see `Noise::sample`. Does not advance the generator.
+    pub fn noise(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.noise.sample(x, y, z)
+    }
+}
+
+/// Improved Perlin noise (Perlin 2002) with a seeded permutation table.
+#[derive(Clone, Debug)]
+pub struct Noise {
+    perm: Vec<u8>,
+}
+
+impl Noise {
+    /// Builds the permutation table for `seed`.
+    pub fn new(seed: u64) -> Self {
+        // A separate stream, so drawing random numbers never changes the noise field
+        let mut state = seed_state(seed ^ 0x6E6F_6973_655F_7631);
+        let mut table: Vec<u8> = (0..=255).collect();
+        for i in (1..256).rev() {
+            let j = (next_xoshiro(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        let mut perm = table.clone();
+        perm.extend_from_slice(&table);
+        Self { perm }
+    }
+
+    /// Samples smooth noise at `(x, y, z)`, roughly in `[-1, 1]` and `0` at integer points.
+    pub fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        let p = |i: usize| self.perm[i] as usize;
+
+        let (xf, yf, zf) = (x.floor(), y.floor(), z.floor());
+        let xi = (xf as i64 & 255) as usize;
+        let yi = (yf as i64 & 255) as usize;
+        let zi = (zf as i64 & 255) as usize;
+        let (x, y, z) = (x - xf, y - yf, z - zf);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let a = p(xi) + yi;
+        let aa = p(a) + zi;
+        let ab = p(a + 1) + zi;
+        let b = p(xi + 1) + yi;
+        let ba = p(b) + zi;
+        let bb = p(b + 1) + zi;
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p(aa), x, y, z), grad(p(ba), x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(p(ab), x, y - 1.0, z),
+                    grad(p(bb), x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p(aa + 1), x, y, z - 1.0),
+                    grad(p(ba + 1), x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p(ab + 1), x, y - 1.0, z - 1.0),
+                    grad(p(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+}
+
+/// Expands a 64-bit seed into xoshiro state with SplitMix64.
+fn seed_state(seed: u64) -> [u64; 4] {
+    let mut s = seed;
+    let mut next = || {
+        s = s.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = s;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    [next(), next(), next(), next()]
+}
+
+/// xoshiro256** step.
+fn next_xoshiro(s: &mut [u64; 4]) -> u64 {
+    let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+    let t = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+    result
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn grad(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
@@ -6,8 +6,10 @@
 //! - **MovieHandle**: Wrapper around `Director` for script access
 //! - **SceneHandle**: Reference to a timeline scene
 //! - **NodeHandle**: Reference to a scene graph node
+//! - **SymbolHandle**: Reference to a detached template subtree
 //! - **AudioTrackHandle**: Reference to an audio track
 
+use super::rng::ScriptRng;
 use crate::director::Director;
 use crate::types::NodeId;
 use std::sync::{Arc, Mutex};
@@ -16,6 +18,8 @@ use std::sync::{Arc, Mutex};
 #[derive(Clone)]
 pub struct MovieHandle {
     pub director: Arc<Mutex<Director>>,
+    /// This movie's random generator, seeded from its `new_director` config.
+    pub rng: Arc<Mutex<ScriptRng>>,
 }
 
 /// Handle to a specific Scene (or time segment) in the movie.
@@ -35,6 +39,16 @@ pub struct NodeHandle {
     pub id: NodeId,
 }
 
+/// Handle to a symbol: a detached master subtree whose instances follow its edits.
+///
+/// The symbol root has no parent and is never visited by the timeline, so it
+/// is not rendered itself. Editing it through its `NodeHandle` updates every instance.
+#[derive(Clone)]
+pub struct SymbolHandle {
+    pub director: Arc<Mutex<Director>>,
+    pub root_id: NodeId,
+}
+
 /// Handle to an audio track.
 #[derive(Clone)]
 pub struct AudioTrackHandle {
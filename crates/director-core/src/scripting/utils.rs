@@ -3,17 +3,25 @@
 //! Parsing helpers and utility functions for Rhai script bindings.
 //!
 //! ## Responsibilities
-//! - **Color Parsing**: `parse_hex_color` for hex string to Color conversion
+//! - **Color Parsing**: `parse_hex_color` for hex string to Color conversion, `parse_tint_color`
+//!   for backdrop tints with alpha
 //! - **Layout Parsing**: `parse_layout_style` for Taffy style properties
 //! - **Text Parsing**: `parse_text_style`, `parse_spans_from_dynamic`
+//! - **Paint Parsing**: `parse_gradient`, `parse_stroke_cap`, `parse_stroke_join`
+//! - **Chart Parsing**: `parse_chart_kind`
+//! - **Code Parsing**: `parse_code_language`
+//! - **Visualizer Parsing**: `parse_waveform_mode`, `parse_spectrum_style`, `parse_progress_source`
 //! - **Animation Parsing**: `parse_spring_config`, `parse_easing`
 //! - **Effect Helpers**: `apply_effect_to_node`, `extract_outer_style`
 
 use crate::animation::{EasingType, SpringConfig};
 use crate::director::Director;
 use crate::element::{TextShadow, TextSpan};
-use crate::node::{BoxNode, CompositionNode, EffectNode, EffectType};
-use crate::types::{Color, GradientConfig, NodeId, ObjectFit};
+use crate::node::{BoxNode, CompositionNode, EffectNode, EffectType, ProgressSource};
+use crate::types::{
+    ChartKind, ClipShape, CodeLanguage, Color, ColorInterpolation, GradientConfig, MatteMode,
+    NodeId, ObjectFit, SpectrumStyle, StrokeCap, StrokeJoin, WaveformMode, WorkingColorSpace,
+};
 use rhai::Map;
 use taffy::prelude::*;
 use taffy::style::{GridPlacement, GridTemplateComponent, Style};
@@ -43,8 +51,18 @@ pub fn extract_outer_style(source: &Style) -> Style {
     }
 }
 
-/// Apply an effect to a node by wrapping it in an EffectNode.
+/// Apply an effect to a node by wrapping it in an EffectNode, or by extending an adjustment layer.
 pub fn apply_effect_to_node(d: &mut Director, node_id: NodeId, effect: EffectType) -> NodeId {
+    // Adjustment layers collect effects in their own chain instead of being wrapped
+    if let Some(node) = d.scene.get_node_mut(node_id) {
+        if let Some(effect_node) = node.element.as_any_mut().downcast_mut::<EffectNode>() {
+            if effect_node.adjustment {
+                effect_node.effects.push(effect);
+                return node_id;
+            }
+        }
+    }
+
     let parent_id_opt = d.scene.get_node(node_id).and_then(|n| n.parent);
 
     let wrapper_style;
@@ -85,6 +103,7 @@ pub fn apply_effect_to_node(d: &mut Director, node_id: NodeId, effect: EffectTyp
         style: wrapper_style,
         shader_cache: d.assets.shader_cache.clone(),
         current_time: 0.0,
+        adjustment: false,
     };
 
     let effect_id = d.scene.add_node(Box::new(effect_node));
@@ -133,6 +152,21 @@ pub fn parse_hex_color(hex: &str) -> Option<Color> {
     ))
 }
 
+/// Parse a backdrop tint: anything `parse_hex_color` accepts, or "#RRGGBBAA" where the
+/// alpha sets how strongly the tint is mixed in.
+pub fn parse_tint_color(hex: &str) -> Option<Color> {
+    let digits = hex.trim_start_matches('#');
+    if digits.len() != 8 {
+        return parse_hex_color(hex);
+    }
+    let alpha = u8::from_str_radix(digits.get(6..)?, 16).ok()?;
+    let color = parse_hex_color(digits.get(..6)?)?;
+    Some(Color {
+        a: alpha as f32 / 255.0,
+        ..color
+    })
+}
+
 /// Parse text style properties from a Rhai map into a TextSpan.
 pub fn parse_text_style(map: &rhai::Map, span: &mut TextSpan) {
     if let Some(c) = map.get("color").and_then(|v| v.clone().into_string().ok()) {
@@ -168,54 +202,71 @@ pub fn parse_text_style(map: &rhai::Map, span: &mut TextSpan) {
     {
         span.stroke_color = parse_hex_color(&c);
     }
-    if let Some(g) = map.get("fill_gradient") {
-        if let Ok(arr) = g.clone().into_array() {
-            // Simple array of colors
-            let mut colors = Vec::new();
-            for item in arr {
-                if let Ok(s) = item.into_string() {
-                    if let Some(c) = parse_hex_color(&s) {
-                        colors.push(c);
-                    }
-                }
-            }
-            if !colors.is_empty() {
-                span.fill_gradient = Some(GradientConfig {
-                    colors,
-                    ..Default::default()
-                });
-            }
-        } else if let Some(gmap) = g.clone().try_cast::<Map>() {
-            // Advanced map
-            let mut config = GradientConfig::default();
-            if let Some(arr) = gmap.get("colors").and_then(|v| v.clone().into_array().ok()) {
-                let mut colors = Vec::new();
-                for item in arr {
-                    if let Ok(s) = item.into_string() {
-                        if let Some(c) = parse_hex_color(&s) {
-                            colors.push(c);
-                        }
-                    }
-                }
-                config.colors = colors;
-            }
-            if let Some(arr) = gmap.get("start").and_then(|v| v.clone().into_array().ok()) {
-                if arr.len() >= 2 {
-                    let x = arr[0].as_float().unwrap_or(0.0) as f32;
-                    let y = arr[1].as_float().unwrap_or(0.0) as f32;
-                    config.start = (x, y);
-                }
-            }
-            if let Some(arr) = gmap.get("end").and_then(|v| v.clone().into_array().ok()) {
-                if arr.len() >= 2 {
-                    let x = arr[0].as_float().unwrap_or(0.0) as f32;
-                    let y = arr[1].as_float().unwrap_or(0.0) as f32;
-                    config.end = (x, y);
-                }
-            }
-            span.fill_gradient = Some(config);
+    if let Some(g) = map.get("fill_gradient").and_then(parse_gradient) {
+        span.fill_gradient = Some(g);
+    }
+}
+
+/// Parse a gradient from an array of hex colors or a map.
+///
+/// The map form is `#{ colors: [...], positions: [...], start: [x, y], end: [x, y] }`,
+/// with points relative to the node's box.
+pub fn parse_gradient(value: &rhai::Dynamic) -> Option<GradientConfig> {
+    let parse_colors = |arr: rhai::Array| -> Vec<Color> {
+        arr.into_iter()
+            .filter_map(|item| item.into_string().ok())
+            .filter_map(|s| parse_hex_color(&s))
+            .collect()
+    };
+
+    if let Ok(arr) = value.clone().into_array() {
+        // Simple array of colors
+        let colors = parse_colors(arr);
+        return (!colors.is_empty()).then(|| GradientConfig {
+            colors,
+            ..Default::default()
+        });
+    }
+
+    // Advanced map
+    let gmap = value.clone().try_cast::<Map>()?;
+    let mut config = GradientConfig::default();
+    if let Some(arr) = gmap.get("colors").and_then(|v| v.clone().into_array().ok()) {
+        config.colors = parse_colors(arr);
+    }
+    if let Some(arr) = gmap
+        .get("positions")
+        .and_then(|v| v.clone().into_array().ok())
+    {
+        let positions: Vec<f32> = arr
+            .iter()
+            .filter_map(|v| {
+                v.as_float()
+                    .ok()
+                    .or_else(|| v.as_int().ok().map(|i| i as f64))
+            })
+            .map(|v| v as f32)
+            .collect();
+        if positions.len() == config.colors.len() {
+            config.positions = Some(positions);
+        }
+    }
+    let point = |key: &str| {
+        let arr = gmap.get(key)?.clone().into_array().ok()?;
+        if arr.len() < 2 {
+            return None;
         }
+        let x = arr[0].as_float().unwrap_or(0.0) as f32;
+        let y = arr[1].as_float().unwrap_or(0.0) as f32;
+        Some((x, y))
+    };
+    if let Some(start) = point("start") {
+        config.start = start;
+    }
+    if let Some(end) = point("end") {
+        config.end = end;
     }
+    Some(config)
 }
 
 /// Parse spring configuration from a Rhai map.
@@ -545,6 +596,191 @@ pub fn parse_object_fit(val: &str) -> Option<ObjectFit> {
     }
 }
 
+/// Parse a matte mode string (e.g. `"luma_inverted"`).
+pub fn parse_matte_mode(val: &str) -> Option<MatteMode> {
+    match val {
+        "alpha" => Some(MatteMode::Alpha),
+        "alpha_inverted" | "alpha-inverted" => Some(MatteMode::AlphaInverted),
+        "luma" => Some(MatteMode::Luma),
+        "luma_inverted" | "luma-inverted" => Some(MatteMode::LumaInverted),
+        _ => None,
+    }
+}
+
+/// Parse a working colour space name (e.g. `"linear_p3"`).
+pub fn parse_color_space(val: &str) -> Option<WorkingColorSpace> {
+    match val {
+        "srgb" => Some(WorkingColorSpace::Srgb),
+        "linear_srgb" | "linear-srgb" | "linear" => Some(WorkingColorSpace::LinearSrgb),
+        "linear_p3" | "linear-p3" | "linear_display_p3" => Some(WorkingColorSpace::LinearDisplayP3),
+        _ => None,
+    }
+}
+
+/// Parse a colour interpolation name (`"srgb"` or `"oklab"`).
+pub fn parse_color_interpolation(val: &str) -> Option<ColorInterpolation> {
+    match val {
+        "srgb" => Some(ColorInterpolation::Srgb),
+        "oklab" => Some(ColorInterpolation::Oklab),
+        _ => None,
+    }
+}
+
+/// Parse a stroke cap name (`"butt"`, `"round"`, `"square"`).
+pub fn parse_stroke_cap(val: &str) -> Option<StrokeCap> {
+    match val {
+        "butt" => Some(StrokeCap::Butt),
+        "round" => Some(StrokeCap::Round),
+        "square" => Some(StrokeCap::Square),
+        _ => None,
+    }
+}
+
+/// Parse a stroke join name (`"miter"`, `"round"`, `"bevel"`).
+pub fn parse_stroke_join(val: &str) -> Option<StrokeJoin> {
+    match val {
+        "miter" => Some(StrokeJoin::Miter),
+        "round" => Some(StrokeJoin::Round),
+        "bevel" => Some(StrokeJoin::Bevel),
+        _ => None,
+    }
+}
+
+/// Parse a chart type name (`"bar"`, `"line"`, `"area"`, `"pie"`).
+pub fn parse_chart_kind(val: &str) -> Option<ChartKind> {
+    match val {
+        "bar" => Some(ChartKind::Bar),
+        "line" => Some(ChartKind::Line),
+        "area" => Some(ChartKind::Area),
+        "pie" | "donut" => Some(ChartKind::Pie),
+        _ => None,
+    }
+}
+
+/// Parse a code language name (`"rust"`, `"js"`, `"python"`, ...); `"text"` turns off
+/// highlighting.
+pub fn parse_code_language(val: &str) -> Option<CodeLanguage> {
+    match val.to_ascii_lowercase().as_str() {
+        "plain" | "text" | "none" => Some(CodeLanguage::Plain),
+        "rust" | "rs" => Some(CodeLanguage::Rust),
+        "javascript" | "js" | "jsx" | "typescript" | "ts" | "tsx" => Some(CodeLanguage::JavaScript),
+        "python" | "py" => Some(CodeLanguage::Python),
+        "go" | "golang" => Some(CodeLanguage::Go),
+        "c" | "cpp" | "c++" | "java" | "csharp" | "c#" | "kotlin" | "swift" => {
+            Some(CodeLanguage::C)
+        }
+        "json" => Some(CodeLanguage::Json),
+        "shell" | "bash" | "sh" | "zsh" => Some(CodeLanguage::Shell),
+        "sql" => Some(CodeLanguage::Sql),
+        _ => None,
+    }
+}
+
+/// Parse a waveform mode (`"overview"` or `"scrolling"`).
+pub fn parse_waveform_mode(val: &str) -> Option<WaveformMode> {
+    match val {
+        "overview" | "full" => Some(WaveformMode::Overview),
+        "scrolling" | "scroll" => Some(WaveformMode::Scrolling),
+        _ => None,
+    }
+}
+
+/// Parse a spectrum style (`"bars"`, `"circle"` or `"line"`).
+pub fn parse_spectrum_style(val: &str) -> Option<SpectrumStyle> {
+    match val {
+        "bars" | "bar" => Some(SpectrumStyle::Bars),
+        "circle" | "radial" => Some(SpectrumStyle::Circle),
+        "line" => Some(SpectrumStyle::Line),
+        _ => None,
+    }
+}
+
+/// Parse the clock of a progress bar (`"scene"` or `"movie"`).
+///
+/// Audio clocks come from a track handle instead, so they aren't named here.
+pub fn parse_progress_source(val: &str) -> Option<ProgressSource> {
+    match val {
+        "scene" => Some(ProgressSource::Scene),
+        "movie" | "global" => Some(ProgressSource::Movie),
+        _ => None,
+    }
+}
+
+/// Parse a clip shape map.
+///
+/// Supported shapes:
+/// - `#{ shape: "rect", x, y, width, height, radius }` (bounds default to the node's box)
+/// - `#{ shape: "ellipse", x, y, width, height }`
+/// - `#{ shape: "polygon", points: [[x, y], ...] }`
+/// - `#{ shape: "path", d: "M0 0 L..." }`
+pub fn parse_clip_shape(map: &rhai::Map) -> Result<ClipShape, String> {
+    let num = |key: &str| {
+        map.get(key).and_then(|v| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f64))
+        })
+    };
+    let bounds = || match (num("width"), num("height")) {
+        (Some(w), Some(h)) => {
+            let (x, y) = (num("x").unwrap_or(0.0), num("y").unwrap_or(0.0));
+            Some(skia_safe::Rect::from_xywh(
+                x as f32, y as f32, w as f32, h as f32,
+            ))
+        }
+        _ => None,
+    };
+
+    let shape = map
+        .get("shape")
+        .and_then(|v| v.clone().into_string().ok())
+        .unwrap_or_else(|| "rect".to_string());
+
+    match shape.as_str() {
+        "rect" => Ok(ClipShape::Rect {
+            rect: bounds(),
+            radius: num("radius").unwrap_or(0.0) as f32,
+        }),
+        "ellipse" | "circle" => Ok(ClipShape::Ellipse { rect: bounds() }),
+        "polygon" => {
+            let points: Vec<skia_safe::Point> = map
+                .get("points")
+                .and_then(|v| v.clone().into_array().ok())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|p| {
+                    let p = p.into_array().ok()?;
+                    let coord = |v: &rhai::Dynamic| {
+                        v.as_float()
+                            .ok()
+                            .or_else(|| v.as_int().ok().map(|i| i as f64))
+                    };
+                    Some(skia_safe::Point::new(
+                        coord(p.first()?)? as f32,
+                        coord(p.get(1)?)? as f32,
+                    ))
+                })
+                .collect();
+            if points.len() < 3 {
+                return Err("A polygon clip needs at least 3 points".to_string());
+            }
+            let mut path = skia_safe::Path::new();
+            path.add_poly(&points, true);
+            Ok(ClipShape::Path(path))
+        }
+        "path" => {
+            let d = map
+                .get("d")
+                .and_then(|v| v.clone().into_string().ok())
+                .unwrap_or_default();
+            skia_safe::Path::from_svg(&d)
+                .map(ClipShape::Path)
+                .ok_or_else(|| format!("Invalid SVG path: {}", d))
+        }
+        other => Err(format!("Unknown clip shape '{}'", other)),
+    }
+}
+
 /// Parse text spans from a Rhai dynamic value.
 pub fn parse_spans_from_dynamic(content: rhai::Dynamic) -> Vec<TextSpan> {
     let mut spans = Vec::new();
@@ -13,7 +13,7 @@
 use crate::scene::SceneGraph;
 use crate::types::NodeId;
 use taffy::prelude::*;
-use tracing::instrument;
+use tracing::{instrument, trace_span};
 
 /// Manages the layout computation using the Taffy engine.
 ///
@@ -36,91 +36,94 @@ impl LayoutEngine {
 
     /// Computes the layout for the current frame.
     ///
+    /// Only the nodes in `scene.active_nodes` (set by `Director::update`) are synced and
+    /// laid out, so the cost scales with the visible scenes rather than the whole arena.
+    ///
     /// # Process
-    /// 1. **Sync Phase A**: Creates new Taffy nodes for new SceneNodes and updates styles for dirty nodes.
-    /// 2. **Sync Phase B**: Updates parent-child relationships in Taffy to match the Scene Graph.
+    /// 1. **Sync Phase A**: Drops Taffy nodes of destroyed SceneNodes, creates Taffy nodes for new
+    ///    active SceneNodes and updates styles for dirty ones.
+    /// 2. **Sync Phase B**: Updates parent-child relationships in Taffy where they changed.
     /// 3. **Compute**: Triggers `taffy.compute_layout` for all active scene roots.
     /// 4. **Write Back**: Copies the computed (x, y, w, h) from Taffy back to `SceneNode`.
     #[instrument(level = "debug", skip(self, scene), fields(time = time))]
     pub fn compute_layout(&mut self, scene: &mut SceneGraph, width: i32, height: i32, time: f64) {
-        // 1. Sync Phase A: Ensure Nodes Exist & Update Styles
-        // Iterate over all potential node IDs in the Scene
-        for (id, node_opt) in scene.nodes.iter_mut().enumerate() {
-            if let Some(node) = node_opt {
-                // Ensure existence in Taffy
-                let t_id = if let Some(&existing_t_id) = self.node_map.get(&id) {
-                    existing_t_id
-                } else {
-                    let style = node.element.layout_style();
-
-                    // All nodes now have context (Director NodeId) to support measure if needed
-                    let new_t_id = self.taffy.new_leaf_with_context(style, id).unwrap();
-                    self.node_map.insert(id, new_t_id);
-                    new_t_id
-                };
-
-                // Sync Style if dirty
-                if node.dirty_style {
-                    let style = node.element.layout_style();
-                    self.taffy.set_style(t_id, style).unwrap();
-
-                    // Taffy 0.9.2 doesn't support updating measure function per node this way.
-                    // Measure logic must be handled in compute_layout_with_measure.
-
-                    node.dirty_style = false;
-                }
+        // 1. Sync Phase A: Remove Deleted Nodes, Ensure Active Nodes Exist & Update Styles
+        for id in scene.removed_nodes.drain(..) {
+            if let Some(t_id) = self.node_map.remove(&id) {
+                self.taffy.remove(t_id).ok();
+            }
+        }
+
+        // Inactive nodes keep their dirty flag and are synced once their scene is visible
+        for &id in &scene.active_nodes {
+            let Some(node) = scene.nodes.get_mut(id).and_then(|n| n.as_mut()) else {
+                continue;
+            };
+
+            // Ensure existence in Taffy
+            let t_id = if let Some(&existing_t_id) = self.node_map.get(&id) {
+                existing_t_id
             } else {
-                // Node is deleted in Scene
-                if let Some(t_id) = self.node_map.remove(&id) {
-                    self.taffy.remove(t_id).ok();
-                }
+                let style = node.element.layout_style();
+
+                // All nodes now have context (Director NodeId) to support measure if needed
+                let new_t_id = self.taffy.new_leaf_with_context(style, id).unwrap();
+                self.node_map.insert(id, new_t_id);
+                new_t_id
+            };
+
+            // Sync Style if dirty
+            if node.dirty_style {
+                let style = node.element.layout_style();
+                self.taffy.set_style(t_id, style).unwrap();
+
+                // Taffy 0.9.2 doesn't support updating measure function per node this way.
+                // Measure logic must be handled in compute_layout_with_measure.
+
+                node.dirty_style = false;
+                // Invalidates recordings of this node's subtree
+                node.mark_content_changed();
             }
         }
 
         // 2. Sync Phase B: Update Relationships (Children)
-        // We iterate again. Since we updated all nodes in Phase A, all valid children should be in node_map.
-        for (id, node_opt) in scene.nodes.iter().enumerate() {
-            if let Some(node) = node_opt {
-                if let Some(&t_id) = self.node_map.get(&id) {
-                    let mut children_t_ids = Vec::with_capacity(node.children.len() + 1);
-
-                    for &child_id in &node.children {
-                        if let Some(&child_t_id) = self.node_map.get(&child_id) {
-                            children_t_ids.push(child_t_id);
-                        }
-                    }
-                    if let Some(mask_id) = node.mask_node {
-                        if let Some(&mask_t_id) = self.node_map.get(&mask_id) {
-                            children_t_ids.push(mask_t_id);
-                        }
-                    }
+        // All active nodes exist after Phase A, and every child of an active node is active.
+        let mut children_t_ids = Vec::new();
+        for &id in &scene.active_nodes {
+            let (Some(node), Some(&t_id)) = (scene.get_node(id), self.node_map.get(&id)) else {
+                continue;
+            };
 
-                    // Always set children to ensure structure is correct
-                    // Taffy's set_children is optimized to do nothing if children list hasn't changed.
-                    self.taffy.set_children(t_id, &children_t_ids).unwrap();
+            children_t_ids.clear();
+            for &child_id in &node.children {
+                if let Some(&child_t_id) = self.node_map.get(&child_id) {
+                    children_t_ids.push(child_t_id);
                 }
             }
-        }
-
-        // 3. Compute Layout for Active Roots
-        // Iterate scene nodes to find active roots (parent is None + recently visited)
-        let mut active_roots = Vec::new();
-        for (id, node_opt) in scene.nodes.iter().enumerate() {
-            if let Some(node) = node_opt {
-                // Is a root?
-                if node.parent.is_none() {
-                    // Is active? (Visited in this frame)
-                    if (node.last_visit_time - time).abs() < 0.001 {
-                        active_roots.push(id);
-                    }
+            if let Some(mask_id) = node.mask_node {
+                if let Some(&mask_t_id) = self.node_map.get(&mask_id) {
+                    children_t_ids.push(mask_t_id);
                 }
             }
+
+            // `set_children` always invalidates Taffy's layout cache, so skip unchanged lists
+            if !self
+                .taffy
+                .child_ids(t_id)
+                .eq(children_t_ids.iter().copied())
+            {
+                self.taffy.set_children(t_id, &children_t_ids).unwrap();
+            }
         }
 
+        // 3. Compute Layout for Active Roots
+        let active_roots = scene.active_roots.clone();
+
         for root_id in active_roots {
             // Need to handle missing node safely
             if scene.get_node(root_id).is_some() {
                 if let Some(&root_t_id) = self.node_map.get(&root_id) {
+                    let _span = trace_span!("layout_root", node = root_id).entered();
                     // Taffy measure closure
                     let measure_func = |known_dimensions: Size<Option<f32>>,
                                         available_space: Size<AvailableSpace>,
@@ -131,6 +134,9 @@ impl LayoutEngine {
                         if let Some(director_node_id) = context {
                             if let Some(node) = scene.get_node(*director_node_id) {
                                 if node.element.needs_measure() {
+                                    let _span =
+                                        trace_span!("measure_node", node = *director_node_id)
+                                            .entered();
                                     return node.element.measure(known_dimensions, available_space);
                                 }
                             }
@@ -1,5 +1,7 @@
 pub mod assets;
 pub mod layout;
+pub mod profiler;
+pub mod render_cache;
 pub mod renderer;
 pub mod transitions;
 
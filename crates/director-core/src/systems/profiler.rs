@@ -0,0 +1,381 @@
+//! # Profiler System
+//!
+//! Per-node and per-frame timings collected from the engine's `tracing` spans.
+//!
+//! ## Responsibilities
+//! - **Span Timing**: `ProfileLayer` measures how long each span is entered.
+//! - **Attribution**: Spans with a `node` field are attributed to that node. Self time
+//!   excludes nested spans, so a parent isn't blamed for its children or effect filters.
+//! - **Reporting**: `ProfileReport` lists the slowest nodes and frames as JSON, a text
+//!   summary, or folded stacks for flame graph tools.
+//!
+//! ## Spans
+//! - `frame` (`index`, `time`): one rendered or exported frame.
+//! - `update_node` (`node`): `Element::update` and animation sampling.
+//! - `compute_layout`, `layout_root` (`node`), `measure_node` (`node`): Taffy layout.
+//! - `render_node` (`node`): drawing a node and its subtree.
+//! - `build_effect_filter`: effect filter construction.
+//!
+//! The per-node spans are at `trace` level and cost next to nothing unless a
+//! subscriber enables them.
+//!
+//! ## Usage
+//! ```rust,no_run
+//! use director_core::systems::profiler::Profiler;
+//! use tracing_subscriber::prelude::*;
+//!
+//! let profiler = Profiler::new();
+//! tracing_subscriber::registry().with(profiler.layer()).init();
+//! // ... render or export ...
+//! println!("{}", profiler.report().summary(10));
+//! ```
+
+use crate::types::NodeId;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Collects span timings for one or more renders.
+///
+/// Clones share the same data, so keep one handle and install `layer()` in the subscriber.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    data: Arc<Mutex<ProfileData>>,
+}
+
+#[derive(Default)]
+struct ProfileData {
+    frames: Vec<FrameTiming>,
+    spans: HashMap<(&'static str, Option<NodeId>), NodeTiming>,
+    stacks: HashMap<String, Duration>,
+}
+
+impl Profiler {
+    /// Creates an empty profiler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `tracing_subscriber` layer that records into this profiler.
+    pub fn layer(&self) -> ProfileLayer {
+        ProfileLayer {
+            data: self.data.clone(),
+        }
+    }
+
+    /// Builds a report from everything recorded so far.
+    pub fn report(&self) -> ProfileReport {
+        let data = self.data.lock().unwrap();
+
+        let mut nodes: Vec<NodeTiming> = data.spans.values().cloned().collect();
+        nodes.sort_by(|a, b| b.self_ms.total_cmp(&a.self_ms));
+
+        let mut stacks: Vec<StackTiming> = data
+            .stacks
+            .iter()
+            .map(|(stack, time)| StackTiming {
+                stack: stack.clone(),
+                self_ms: time.as_secs_f64() * 1000.0,
+            })
+            .collect();
+        stacks.sort_by(|a, b| a.stack.cmp(&b.stack));
+
+        ProfileReport {
+            frames: data.frames.clone(),
+            nodes,
+            stacks,
+        }
+    }
+
+    /// Discards all recorded timings.
+    pub fn reset(&self) {
+        *self.data.lock().unwrap() = ProfileData::default();
+    }
+}
+
+/// Timing of a single `frame` span.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameTiming {
+    /// Frame number, for exported frames.
+    pub index: Option<u64>,
+    /// Timeline time in seconds.
+    pub time: Option<f64>,
+    pub duration_ms: f64,
+}
+
+/// Accumulated timing of one span name, per node when the span has a `node` field.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeTiming {
+    /// Span name, e.g. `render_node` or `update_node`.
+    pub span: &'static str,
+    pub node: Option<NodeId>,
+    pub calls: u64,
+    /// Time including nested spans.
+    pub total_ms: f64,
+    /// Time excluding nested spans.
+    pub self_ms: f64,
+    /// Longest single call, including nested spans.
+    pub max_ms: f64,
+}
+
+/// Self time of one span stack, e.g. `render_node#0;render_node#4`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StackTiming {
+    pub stack: String,
+    pub self_ms: f64,
+}
+
+/// Result of a profiled render.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    /// Frames in the order they were rendered.
+    pub frames: Vec<FrameTiming>,
+    /// Span timings, slowest self time first.
+    pub nodes: Vec<NodeTiming>,
+    /// Self time per span stack (frames merged), sorted by stack.
+    pub stacks: Vec<StackTiming>,
+}
+
+impl ProfileReport {
+    /// Returns the `count` slowest frames, slowest first.
+    pub fn slowest_frames(&self, count: usize) -> Vec<&FrameTiming> {
+        let mut frames: Vec<&FrameTiming> = self.frames.iter().collect();
+        frames.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+        frames.truncate(count);
+        frames
+    }
+
+    /// Returns the `count` node timings with the most self time.
+    pub fn slowest_nodes(&self, count: usize) -> Vec<&NodeTiming> {
+        self.nodes
+            .iter()
+            .filter(|n| n.node.is_some())
+            .take(count)
+            .collect()
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Folded stacks (`a;b;c <microseconds>` per line), as read by `flamegraph.pl` and inferno.
+    pub fn folded(&self) -> String {
+        let mut out = String::new();
+        for stack in &self.stacks {
+            let micros = (stack.self_ms * 1000.0).round() as u64;
+            if micros > 0 {
+                let _ = writeln!(out, "{} {}", stack.stack, micros);
+            }
+        }
+        out
+    }
+
+    /// Human-readable summary of the slowest frames and nodes.
+    pub fn summary(&self, count: usize) -> String {
+        let mut out = String::new();
+        let total: f64 = self.frames.iter().map(|f| f.duration_ms).sum();
+        let _ = writeln!(
+            out,
+            "{} frames, {:.1} ms total, {:.2} ms average",
+            self.frames.len(),
+            total,
+            total / self.frames.len().max(1) as f64
+        );
+
+        let _ = writeln!(out, "Slowest frames:");
+        for frame in self.slowest_frames(count) {
+            let _ = writeln!(
+                out,
+                "  frame {:>6}  t={:>8.3}s  {:>9.3} ms",
+                frame.index.map_or("-".to_string(), |i| i.to_string()),
+                frame.time.unwrap_or(0.0),
+                frame.duration_ms
+            );
+        }
+
+        let _ = writeln!(out, "Slowest nodes (self time):");
+        for node in self.slowest_nodes(count) {
+            let _ = writeln!(
+                out,
+                "  {:<14} #{:<6} {:>9.3} ms self  {:>9.3} ms total  {:>6} calls  {:>8.3} ms max",
+                node.span,
+                node.node.unwrap_or_default(),
+                node.self_ms,
+                node.total_ms,
+                node.calls,
+                node.max_ms
+            );
+        }
+        out
+    }
+}
+
+/// `tracing_subscriber` layer that feeds a `Profiler`.
+///
+/// Events are ignored; only span enter/exit times are measured.
+pub struct ProfileLayer {
+    data: Arc<Mutex<ProfileData>>,
+}
+
+/// Per-span state stored in the registry's span extensions.
+struct SpanState {
+    label: String,
+    fields: SpanFields,
+    entered: Option<Instant>,
+    busy: Duration,
+    child_busy: Duration,
+    longest: Duration,
+}
+
+#[derive(Default)]
+struct SpanFields {
+    node: Option<NodeId>,
+    index: Option<u64>,
+    time: Option<f64>,
+}
+
+impl Visit for SpanFields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "node" => self.node = Some(value as NodeId),
+            "index" => self.index = Some(value),
+            _ => {}
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if value >= 0 {
+            self.record_u64(field, value as u64);
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "time" {
+            self.time = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        let label = match fields.node {
+            Some(node) => format!("{}#{}", span.name(), node),
+            None => span.name().to_string(),
+        };
+        span.extensions_mut().insert(SpanState {
+            label,
+            fields,
+            entered: None,
+            busy: Duration::ZERO,
+            child_busy: Duration::ZERO,
+            longest: Duration::ZERO,
+        });
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(state) = span.extensions_mut().get_mut::<SpanState>() {
+                state.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let elapsed = {
+            let mut extensions = span.extensions_mut();
+            let Some(state) = extensions.get_mut::<SpanState>() else {
+                return;
+            };
+            let Some(entered) = state.entered.take() else {
+                return;
+            };
+            let elapsed = entered.elapsed();
+            state.busy += elapsed;
+            state.longest = state.longest.max(elapsed);
+            elapsed
+        };
+        if let Some(parent) = span.parent() {
+            if let Some(state) = parent.extensions_mut().get_mut::<SpanState>() {
+                state.child_busy += elapsed;
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(state) = span.extensions_mut().remove::<SpanState>() else {
+            return;
+        };
+        let self_time = state.busy.saturating_sub(state.child_busy);
+        let to_ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+        // Frames are left out of stacks so the same node merges across frames
+        let mut stack = String::new();
+        for ancestor in span.scope().from_root() {
+            if ancestor.name() == "frame" {
+                continue;
+            }
+            let extensions = ancestor.extensions();
+            let label = match extensions.get::<SpanState>() {
+                Some(s) => s.label.as_str(),
+                None if ancestor.id() == id => state.label.as_str(),
+                None => ancestor.name(),
+            };
+            if !stack.is_empty() {
+                stack.push(';');
+            }
+            stack.push_str(label);
+        }
+
+        let mut data = self.data.lock().unwrap();
+        if span.name() == "frame" {
+            data.frames.push(FrameTiming {
+                index: state.fields.index,
+                time: state.fields.time,
+                duration_ms: to_ms(state.busy),
+            });
+            return;
+        }
+
+        let entry = data
+            .spans
+            .entry((span.name(), state.fields.node))
+            .or_insert_with(|| NodeTiming {
+                span: span.name(),
+                node: state.fields.node,
+                calls: 0,
+                total_ms: 0.0,
+                self_ms: 0.0,
+                max_ms: 0.0,
+            });
+        entry.calls += 1;
+        entry.total_ms += to_ms(state.busy);
+        entry.self_ms += to_ms(self_time);
+        entry.max_ms = entry.max_ms.max(to_ms(state.longest));
+
+        *data.stacks.entry(stack).or_default() += self_time;
+    }
+}
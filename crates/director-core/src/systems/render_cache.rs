@@ -0,0 +1,193 @@
+//! # Render Cache System
+//!
+//! Retained-mode caching of static subtrees.
+//!
+//! ## Responsibilities
+//! - **Cache Keys**: Fingerprints each subtree from its content versions, layout and child transforms.
+//! - **Recording Storage**: Holds the `Picture` recorded for a node while its key stays the same.
+//!
+//! ## Key Types
+//! - `RenderCache`: Per-node recording slot, owned by `SceneNode`.
+//!
+//! ## Invalidation
+//! A node's `content_version` is bumped by `SceneNode::mark_content_changed` for
+//! drawing-only changes (audio visualizers, progress clocks, clip shapes) and
+//! whenever its layout style is synced while dirty (which includes `Element::update`
+//! reporting a change). Keys also cover the layout size, clip and
+//! compositing settings, and the position and transform of every child, so moving a
+//! child only re-records its ancestors. Nodes with drivers, audio bindings or frame
+//! callbacks have no key and always render directly.
+//!
+//! A subtree is recorded the second frame its key is seen and replayed from then on,
+//! so nodes that change every frame never pay for recording.
+
+use crate::scene::{SceneGraph, SceneNode};
+use crate::types::NodeId;
+use skia_safe::Picture;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Recording slot for a node's rendered subtree.
+///
+/// Cloning a node does not copy its recording.
+#[derive(Default)]
+pub struct RenderCache {
+    slot: Mutex<CacheSlot>,
+}
+
+#[derive(Default)]
+struct CacheSlot {
+    key: Option<u64>,
+    picture: Option<Picture>,
+}
+
+/// Outcome of looking up a frame key in a `RenderCache`.
+pub(crate) enum CacheLookup {
+    /// A recording for this key exists; replay it.
+    Hit(Picture),
+    /// The key matched the previous frame but nothing is recorded yet.
+    Record,
+    /// The key is new; render directly.
+    Miss,
+}
+
+impl Clone for RenderCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl RenderCache {
+    /// Looks up `key`, remembering it so an unchanged key records on the next frame.
+    pub(crate) fn lookup(&self, key: u64) -> CacheLookup {
+        let mut slot = self.slot.lock().unwrap();
+        if slot.key != Some(key) {
+            slot.key = Some(key);
+            slot.picture = None;
+            return CacheLookup::Miss;
+        }
+        match &slot.picture {
+            Some(picture) => CacheLookup::Hit(picture.clone()),
+            None => CacheLookup::Record,
+        }
+    }
+
+    /// Stores the recording made for `key`.
+    pub(crate) fn store(&self, key: u64, picture: Picture) {
+        let mut slot = self.slot.lock().unwrap();
+        if slot.key == Some(key) {
+            slot.picture = Some(picture);
+        }
+    }
+
+    /// Drops any recording and forgets the last seen key.
+    pub fn clear(&self) {
+        *self.slot.lock().unwrap() = CacheSlot::default();
+    }
+
+    /// Returns true if a recording is currently held.
+    pub fn is_recorded(&self) -> bool {
+        self.slot.lock().unwrap().picture.is_some()
+    }
+}
+
+/// Combines a node's cache key with the opacity it is drawn at.
+pub(crate) fn frame_key(cache_key: u64, parent_opacity: f32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    cache_key.hash(&mut hasher);
+    parent_opacity.to_bits().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Recomputes `cache_key` for a node and its whole subtree (including masks).
+///
+/// Returns the node's key, or `None` if it (or anything below it) can't be cached.
+/// Nodes without a key drop their recording.
+pub(crate) fn update_cache_keys(
+    scene: &mut SceneGraph,
+    id: NodeId,
+    enabled: bool,
+    depth: usize,
+) -> Option<u64> {
+    if depth > 100 {
+        return None;
+    }
+    let (children, mask) = {
+        let node = scene.get_node(id)?;
+        (node.children.clone(), node.mask_node)
+    };
+
+    let mut complete = true;
+    let mut child_keys = Vec::with_capacity(children.len());
+    for child in children {
+        let key = update_cache_keys(scene, child, enabled, depth + 1);
+        complete &= key.is_some();
+        child_keys.push((child, key));
+    }
+    let mask_key = mask.map(|m| (m, update_cache_keys(scene, m, enabled, depth + 1)));
+    complete &= mask_key.is_none_or(|(_, key)| key.is_some());
+
+    let key = if enabled && complete {
+        scene.get_node(id).filter(|n| is_cacheable(n)).map(|node| {
+            let mut hasher = DefaultHasher::new();
+            node.content_version.hash(&mut hasher);
+            node.layout_rect.width().to_bits().hash(&mut hasher);
+            node.layout_rect.height().to_bits().hash(&mut hasher);
+            (node.blend_mode as i32).hash(&mut hasher);
+            (node.mask_mode as u8).hash(&mut hasher);
+            node.clip_path.is_some().hash(&mut hasher);
+            if let Some(clip) = &node.clip_path {
+                clip.feather.current_value.to_bits().hash(&mut hasher);
+                clip.expansion.current_value.to_bits().hash(&mut hasher);
+                clip.invert.hash(&mut hasher);
+            }
+            for (child, key) in child_keys.iter().chain(mask_key.iter()) {
+                child.hash(&mut hasher);
+                key.hash(&mut hasher);
+                if let Some(child_node) = scene.get_node(*child) {
+                    hash_placement(child_node, &mut hasher);
+                }
+            }
+            hasher.finish()
+        })
+    } else {
+        None
+    };
+
+    if let Some(node) = scene.get_node_mut(id) {
+        node.cache_key = key;
+        if key.is_none() {
+            node.render_cache.clear();
+        }
+    }
+    key
+}
+
+/// Nodes whose values are overridden outside of `update` can't be trusted to stay static.
+fn is_cacheable(node: &SceneNode) -> bool {
+    node.drivers.is_empty() && node.audio_bindings.is_empty() && node.frame_callbacks.is_empty()
+}
+
+/// Hashes everything the parent applies before drawing a child: position, transform and order.
+fn hash_placement(node: &SceneNode, hasher: &mut DefaultHasher) {
+    let r = node.layout_rect;
+    let t = &node.transform;
+    let values = [
+        r.left,
+        r.top,
+        t.translate_x.current_value,
+        t.translate_y.current_value,
+        t.scale_x.current_value,
+        t.scale_y.current_value,
+        t.rotation.current_value,
+        t.skew_x.current_value,
+        t.skew_y.current_value,
+        t.pivot_x,
+        t.pivot_y,
+    ];
+    for v in values {
+        v.to_bits().hash(hasher);
+    }
+    node.z_index.hash(hasher);
+}
@@ -5,6 +5,7 @@
 //! ## Responsibilities
 //! - **Scene Traversal**: Recursively paints `SceneNode`s to Canvas (`render_recursive`).
 //! - **Layer Composition**: Manages canvas save/restore for transforms.
+//! - **Render Caching**: Replays recorded pictures of static subtrees (see `systems::render_cache`).
 //! - **Debug Rendering**: Single-frame rendering for previews (`render_frame`).
 //!
 //! ## Key Functions
@@ -17,12 +18,16 @@
 
 use crate::director::{Director, TimelineItem};
 use crate::errors::RenderError;
-use crate::scene::SceneGraph;
+use crate::node::EffectNode;
+use crate::scene::{SceneGraph, SceneNode};
 use crate::systems::assets::AssetManager;
 use crate::systems::layout::LayoutEngine;
+use crate::systems::render_cache::{frame_key, CacheLookup};
 use crate::systems::transitions::draw_transition;
-use crate::types::NodeId;
-use tracing::debug;
+use crate::types::{ClipPath, MatteMode, NodeId};
+use skia_safe::color_filters::{self, Clamp};
+use skia_safe::image_filters;
+use tracing::{debug, trace_span};
 
 #[cfg(feature = "vulkan")]
 use skia_safe::gpu::DirectContext;
@@ -34,9 +39,12 @@ pub type GpuContext = DirectContext;
 /// Placeholder for GPU context when Vulkan feature is disabled.
 pub type GpuContext = ();
 
+/// Half-size of the area recorded for cached subtrees (content may overflow its layout box).
+const RECORD_EXTENT: f32 = 32768.0;
+
 /// Recursively renders a node and its children to the canvas.
 ///
-/// Handles transformation stack, blending modes, and masking.
+/// Handles transformation stack, blending modes, masking, clip paths and adjustment layers.
 pub fn render_recursive(
     scene: &SceneGraph,
     assets: &AssetManager,
@@ -49,112 +57,259 @@ pub fn render_recursive(
         return Err(RenderError::RecursionLimit);
     }
     if let Some(node) = scene.get_node(node_id) {
+        let _span = trace_span!("render_node", node = node_id).entered();
         canvas.save();
 
-        // Layout Position
-        let layout_x = node.layout_rect.left;
-        let layout_y = node.layout_rect.top;
-
-        // Transform Properties
-        let tx = node.transform.translate_x.current_value;
-        let ty = node.transform.translate_y.current_value;
-        let scale_x = node.transform.scale_x.current_value;
-        let scale_y = node.transform.scale_y.current_value;
-        let rotation = node.transform.rotation.current_value;
-        let skew_x = node.transform.skew_x.current_value;
-        let skew_y = node.transform.skew_y.current_value;
-
-        // Pivot Calculation (absolute)
-        let pivot_x = node.layout_rect.width() * node.transform.pivot_x;
-        let pivot_y = node.layout_rect.height() * node.transform.pivot_y;
-
-        // Apply Transform Stack
-        // 1. Move to position
-        canvas.translate((layout_x + tx, layout_y + ty));
-
-        // 2. Move to pivot
-        canvas.translate((pivot_x, pivot_y));
-
-        // 3. Rotate
-        canvas.rotate(rotation, None);
-
-        // 4. Scale
-        canvas.scale((scale_x, scale_y));
-
-        // 5. Skew (Degrees to Tangent)
-        let tan_skew_x = skew_x.to_radians().tan();
-        let tan_skew_y = skew_y.to_radians().tan();
-        canvas.skew((tan_skew_x, tan_skew_y));
-
-        // 6. Move back from pivot
-        canvas.translate((-pivot_x, -pivot_y));
+        // Layout position and transform (around the pivot)
+        canvas.concat(&node.local_matrix());
 
         let local_rect =
             skia_safe::Rect::from_wh(node.layout_rect.width(), node.layout_rect.height());
 
-        let mut last_error = Ok(());
-        let mut draw_children = |canvas: &skia_safe::Canvas| {
-            // Z-Index Sorting
-            let mut sorted_children: Vec<(NodeId, i32)> = Vec::with_capacity(node.children.len());
-            for &child_id in &node.children {
-                if let Some(child) = scene.get_node(child_id) {
-                    sorted_children.push((child_id, child.z_index));
+        // Static subtrees replay the picture recorded on an earlier frame
+        let result = match node.cache_key.map(|k| frame_key(k, parent_opacity)) {
+            Some(key) => match node.render_cache.lookup(key) {
+                CacheLookup::Hit(picture) => {
+                    canvas.draw_picture(&picture, None, None);
+                    Ok(())
                 }
-            }
-            sorted_children.sort_by_key(|k| k.1);
-
-            for (child_id, _) in sorted_children {
-                if let Err(e) =
-                    render_recursive(scene, assets, child_id, canvas, parent_opacity, depth + 1)
-                {
-                    last_error = Err(e);
+                CacheLookup::Record => {
+                    let mut recorder = skia_safe::PictureRecorder::new();
+                    let bounds = skia_safe::Rect::new(
+                        -RECORD_EXTENT,
+                        -RECORD_EXTENT,
+                        RECORD_EXTENT,
+                        RECORD_EXTENT,
+                    );
+                    let recording = recorder.begin_recording(bounds, None);
+                    let r = render_content(
+                        scene,
+                        assets,
+                        node,
+                        recording,
+                        local_rect,
+                        parent_opacity,
+                        depth,
+                    );
+                    if let Some(picture) = recorder.finish_recording_as_picture(None) {
+                        canvas.draw_picture(&picture, None, None);
+                        if r.is_ok() {
+                            node.render_cache.store(key, picture);
+                        }
+                    }
+                    r
                 }
-            }
+                CacheLookup::Miss => render_content(
+                    scene,
+                    assets,
+                    node,
+                    canvas,
+                    local_rect,
+                    parent_opacity,
+                    depth,
+                ),
+            },
+            None => render_content(
+                scene,
+                assets,
+                node,
+                canvas,
+                local_rect,
+                parent_opacity,
+                depth,
+            ),
         };
 
-        // Check if we need a save layer for blending or masking
-        let need_layer =
-            node.mask_node.is_some() || node.blend_mode != skia_safe::BlendMode::SrcOver;
+        canvas.restore();
 
-        let result = if need_layer {
-            let mut paint = skia_safe::Paint::default();
-            paint.set_blend_mode(node.blend_mode);
+        result?;
+    }
+    Ok(())
+}
 
-            // Create an isolated layer.
-            canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&paint));
+/// Draws a node's element, children, mask and clip in its local coordinate space.
+///
+/// Expects the node's transform to already be applied to `canvas`.
+fn render_content(
+    scene: &SceneGraph,
+    assets: &AssetManager,
+    node: &SceneNode,
+    canvas: &skia_safe::Canvas,
+    local_rect: skia_safe::Rect,
+    parent_opacity: f32,
+    depth: usize,
+) -> Result<(), RenderError> {
+    let mut last_error = Ok(());
+    let mut draw_children = |canvas: &skia_safe::Canvas| {
+        // Z-Index Sorting
+        let mut sorted_children: Vec<(NodeId, i32)> = Vec::with_capacity(node.children.len());
+        for &child_id in &node.children {
+            if let Some(child) = scene.get_node(child_id) {
+                sorted_children.push((child_id, child.z_index));
+            }
+        }
+        sorted_children.sort_by_key(|k| k.1);
+
+        for (child_id, _) in sorted_children {
+            if let Err(e) =
+                render_recursive(scene, assets, child_id, canvas, parent_opacity, depth + 1)
+            {
+                last_error = Err(e);
+            }
+        }
+    };
+
+    // Adjustment layers filter what the parent has drawn so far (their backdrop)
+    let adjustment = node
+        .element
+        .as_any()
+        .downcast_ref::<EffectNode>()
+        .filter(|e| e.adjustment);
+
+    // Check if we need a save layer for blending, masking, clipping or adjustment
+    let need_layer = adjustment.is_some()
+        || node.mask_node.is_some()
+        || node.clip_path.is_some()
+        || node.blend_mode != skia_safe::BlendMode::SrcOver;
+
+    let result = if need_layer {
+        let mut paint = skia_safe::Paint::default();
+        paint.set_blend_mode(node.blend_mode);
+
+        let backdrop = adjustment.and_then(|e| e.image_filter(local_rect));
+        if adjustment.is_some() {
+            // Limit the adjustment (and its children) to the node's bounds
+            canvas.clip_rect(local_rect, None, true);
+            paint.set_alpha_f(parent_opacity);
+        }
 
-            let r = node
-                .element
-                .render(canvas, local_rect, parent_opacity, &mut draw_children);
+        // Create an isolated layer, seeded with the filtered backdrop for adjustment layers.
+        let mut layer = skia_safe::canvas::SaveLayerRec::default().paint(&paint);
+        if let Some(filter) = &backdrop {
+            layer = layer.backdrop(filter);
+        }
+        canvas.save_layer(&layer);
 
-            if r.is_ok() && last_error.is_ok() {
-                if let Some(mask_id) = node.mask_node {
-                    let mut mask_paint = skia_safe::Paint::default();
-                    mask_paint.set_blend_mode(skia_safe::BlendMode::DstIn);
+        let r = node
+            .element
+            .render(canvas, local_rect, parent_opacity, &mut draw_children);
 
-                    canvas
-                        .save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&mask_paint));
-                    if let Err(e) = render_recursive(scene, assets, mask_id, canvas, 1.0, depth + 1)
-                    {
-                        last_error = Err(e);
-                    }
-                    canvas.restore();
+        if r.is_ok() && last_error.is_ok() {
+            if let Some(mask_id) = node.mask_node {
+                let mask_paint = matte_paint(node.mask_mode);
+                canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&mask_paint));
+                if let Err(e) = render_recursive(scene, assets, mask_id, canvas, 1.0, depth + 1) {
+                    last_error = Err(e);
                 }
+                canvas.restore();
             }
 
-            canvas.restore();
-            r
-        } else {
-            node.element
-                .render(canvas, local_rect, parent_opacity, &mut draw_children)
-        };
+            if let Some(clip) = &node.clip_path {
+                apply_clip_path(canvas, clip, local_rect);
+            }
+        }
 
         canvas.restore();
+        r
+    } else {
+        node.element
+            .render(canvas, local_rect, parent_opacity, &mut draw_children)
+    };
 
-        result?;
-        last_error?;
+    result?;
+    last_error
+}
+
+/// Builds the paint used to composite a mask layer onto its owner.
+///
+/// Alpha mattes use the mask's alpha directly (`DstIn`/`DstOut`). Luma mattes first
+/// turn the mask into an alpha-only image of its luminance times its alpha, so a
+/// faint white mask reveals its owner only faintly.
+fn matte_paint(mode: MatteMode) -> skia_safe::Paint {
+    #[rustfmt::skip]
+    const LUMA: [f32; 20] = [
+        0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0,
+        0.2126, 0.7152, 0.0722, 0.0, 0.0,
+    ];
+
+    // The matrix sees unpremultiplied colour, so weight its luminance by the mask's
+    // own alpha: DstIn keeps the luminance image where the unfiltered mask is drawn
+    let luma_matte = || {
+        let luma = color_filters::matrix_row_major(&LUMA, Clamp::Yes);
+        image_filters::blend(
+            skia_safe::BlendMode::DstIn,
+            image_filters::color_filter(luma, None, None),
+            None,
+            None,
+        )
+    };
+
+    let mut paint = skia_safe::Paint::default();
+    let blend = match mode {
+        MatteMode::Alpha => skia_safe::BlendMode::DstIn,
+        MatteMode::AlphaInverted => skia_safe::BlendMode::DstOut,
+        MatteMode::Luma => {
+            paint.set_image_filter(luma_matte());
+            skia_safe::BlendMode::DstIn
+        }
+        MatteMode::LumaInverted => {
+            // Cutting out by luminance keeps the owner where the mask is dark or empty
+            paint.set_image_filter(luma_matte());
+            skia_safe::BlendMode::DstOut
+        }
+    };
+    paint.set_blend_mode(blend);
+    paint
+}
+
+/// Cuts the current layer down to a node's `clip_path`.
+///
+/// The shape is drawn as a coverage layer: expansion strokes outwards (or erases
+/// inwards) with round joins, and feather blurs the whole layer before it is
+/// composited with `DstIn` (`DstOut` when inverted).
+fn apply_clip_path(canvas: &skia_safe::Canvas, clip: &ClipPath, bounds: skia_safe::Rect) {
+    let path = clip.to_path(bounds);
+    let feather = clip.feather.current_value.max(0.0);
+    let expansion = clip.expansion.current_value;
+
+    let mut layer_paint = skia_safe::Paint::default();
+    layer_paint.set_blend_mode(if clip.invert {
+        skia_safe::BlendMode::DstOut
+    } else {
+        skia_safe::BlendMode::DstIn
+    });
+    if feather > 0.0 {
+        layer_paint.set_image_filter(image_filters::blur(
+            (feather, feather),
+            skia_safe::TileMode::Decal,
+            None,
+            None,
+        ));
     }
-    Ok(())
+    canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&layer_paint));
+
+    let mut shape_paint = skia_safe::Paint::default();
+    shape_paint.set_anti_alias(true);
+    if expansion > 0.0 {
+        shape_paint.set_style(skia_safe::PaintStyle::StrokeAndFill);
+        shape_paint.set_stroke_width(expansion * 2.0);
+        shape_paint.set_stroke_join(skia_safe::PaintJoin::Round);
+    }
+    canvas.draw_path(&path, &shape_paint);
+
+    if expansion < 0.0 {
+        let mut erase_paint = skia_safe::Paint::default();
+        erase_paint.set_anti_alias(true);
+        erase_paint.set_style(skia_safe::PaintStyle::Stroke);
+        erase_paint.set_stroke_width(-expansion * 2.0);
+        erase_paint.set_stroke_join(skia_safe::PaintJoin::Round);
+        erase_paint.set_blend_mode(skia_safe::BlendMode::Clear);
+        canvas.draw_path(&path, &erase_paint);
+    }
+
+    canvas.restore();
 }
 
 /// Renders a single frame at a specific timestamp to the provided canvas.
@@ -165,6 +320,8 @@ pub fn render_frame(
     time: f64,
     canvas: &skia_safe::Canvas,
 ) -> Result<(), RenderError> {
+    let _frame = trace_span!("frame", time).entered();
+    let _interpolation = director.color_interpolation.enter();
     let mut layout_engine = LayoutEngine::new();
     director.update(time);
     layout_engine.compute_layout(&mut director.scene, director.width, director.height, time);
@@ -199,11 +356,29 @@ pub(crate) fn render_at_time(
     canvas: &skia_safe::Canvas,
     surfaces: &mut Option<(skia_safe::Surface, skia_safe::Surface)>,
 ) -> Result<(), RenderError> {
+    prepare_frame(director, layout_engine, time);
+    draw_frame(director, time, canvas, surfaces)
+}
+
+/// Runs update, layout and post-layout for `time` without drawing.
+pub(crate) fn prepare_frame(director: &mut Director, layout_engine: &mut LayoutEngine, time: f64) {
     director.update(time);
     layout_engine.compute_layout(&mut director.scene, director.width, director.height, time);
     director.run_post_layout(time);
+}
 
+/// Draws a prepared frame, including any active transition.
+///
+/// The canvas matrix is honoured throughout, so a frame can be drawn scaled or as
+/// a translated tile. Transition surfaces must match the canvas's device size.
+pub(crate) fn draw_frame(
+    director: &Director,
+    time: f64,
+    canvas: &skia_safe::Canvas,
+    surfaces: &mut Option<(skia_safe::Surface, skia_safe::Surface)>,
+) -> Result<(), RenderError> {
     let assets_ref = &director.assets;
+    let _interpolation = director.color_interpolation.enter();
 
     canvas.clear(skia_safe::Color::BLACK);
 
@@ -235,25 +410,21 @@ pub(crate) fn render_at_time(
                             director.timeline.get(trans.from_scene_idx),
                             director.timeline.get(trans.to_scene_idx),
                         ) {
-                            surf_a.canvas().clear(skia_safe::Color::TRANSPARENT);
-                            surf_b.canvas().clear(skia_safe::Color::TRANSPARENT);
-
-                            render_recursive(
-                                &director.scene,
-                                assets_ref,
-                                item_a.scene_root,
-                                surf_a.canvas(),
-                                1.0,
-                                0,
-                            )?;
-                            render_recursive(
-                                &director.scene,
-                                assets_ref,
-                                item_b.scene_root,
-                                surf_b.canvas(),
-                                1.0,
-                                0,
-                            )?;
+                            // Scene surfaces share the frame's device space (scaled or tiled)
+                            let matrix = skia_safe::M44::from(canvas.local_to_device_as_3x3());
+                            for (surf, root) in [
+                                (&mut *surf_a, item_a.scene_root),
+                                (&mut *surf_b, item_b.scene_root),
+                            ] {
+                                let c = surf.canvas();
+                                c.clear(skia_safe::Color::TRANSPARENT);
+                                c.save();
+                                c.set_matrix(&matrix);
+                                let r =
+                                    render_recursive(&director.scene, assets_ref, root, c, 1.0, 0);
+                                c.restore();
+                                r?;
+                            }
 
                             let img_a = surf_a.image_snapshot();
                             let img_b = surf_b.image_snapshot();
@@ -164,11 +164,13 @@ pub fn draw_transition(
 
         let uniforms_data = Data::new_copy(&uniform_bytes);
 
+        // Scene images are in device space, which may be supersampled or a tile of the frame
+        let to_frame = canvas.local_to_device_as_3x3().invert().unwrap_or_default();
         let shader_a = img_a
-            .to_shader(None, skia_safe::SamplingOptions::default(), None)
+            .to_shader(None, skia_safe::SamplingOptions::default(), &to_frame)
             .unwrap();
         let shader_b = img_b
-            .to_shader(None, skia_safe::SamplingOptions::default(), None)
+            .to_shader(None, skia_safe::SamplingOptions::default(), &to_frame)
             .unwrap();
 
         let children = [ChildPtr::Shader(shader_a), ChildPtr::Shader(shader_b)];
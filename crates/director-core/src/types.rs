@@ -3,9 +3,14 @@
 //! Shared data types used across the engine.
 //!
 //! ## Responsibilities
-//! - **Color**: RGBA color representation with Skia conversion.
+//! - **Color**: RGBA color representation with Skia conversion and OKLab conversion.
+//! - **ColorInterpolation**: How animated colours are blended (sRGB channels or OKLab).
+//! - **WorkingColorSpace**: The colour space (and surface precision) used for compositing.
 //! - **Transform**: Animated 2D transforms (scale, rotation, translation).
 //! - **ObjectFit**: Image/video scaling modes (Cover, Contain, Fill).
+//! - **MatteMode**: How a mask node is applied to its owner (alpha or luma, optionally inverted).
+//! - **ClipPath**: Geometry-based clip masks with feather, expansion and invert.
+//! - **StrokeCap / StrokeJoin**: Line end and corner styles for stroked shapes.
 //!
 //! ## Key Types
 //! - `Color`: Float-based RGBA color.
@@ -15,7 +20,11 @@
 use crate::animation::Animated;
 use keyframe::CanTween;
 use serde::{Deserialize, Serialize};
-use skia_safe::{Color4f, Path};
+use skia_safe::{
+    named_primaries, named_transfer_fn, AlphaType, Color4f, ColorSpace, ColorType, ImageInfo,
+    Matrix, PaintCap, PaintJoin, Path, RRect, Rect,
+};
+use std::cell::Cell;
 
 /// Specifies how the content of a replaceable element (img, video) should
 /// be resized to fit its container.
@@ -39,6 +48,21 @@ impl Default for ObjectFit {
     }
 }
 
+/// Specifies which channel of a mask node controls the visibility of its owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatteMode {
+    /// The owner is visible where the mask is opaque.
+    #[default]
+    Alpha,
+    /// The owner is visible where the mask is transparent.
+    AlphaInverted,
+    /// The owner is visible where the mask is bright (Rec. 709 luminance, weighted by alpha).
+    Luma,
+    /// The owner is visible where the mask is dark or transparent.
+    LumaInverted,
+}
+
 // --- From director.rs ---
 
 /// A unique identifier for a node in the scene graph.
@@ -51,6 +75,77 @@ pub struct PathAnimationState {
     pub progress: Animated<f32>,
 }
 
+/// Geometry of a `ClipPath`, in the node's local coordinates.
+#[derive(Clone)]
+pub enum ClipShape {
+    /// An arbitrary path in node-local pixels (SVG path data or a polygon).
+    Path(Path),
+    /// A (rounded) rectangle. `None` uses the node's layout bounds.
+    Rect { rect: Option<Rect>, radius: f32 },
+    /// An ellipse inscribed in the rectangle. `None` uses the node's layout bounds.
+    Ellipse { rect: Option<Rect> },
+    /// An outline defined in a coordinate space of `size`, stretched to the node's bounds
+    /// (used for `VectorNode` outlines, matching how the SVG itself is drawn).
+    Outline { path: Path, size: (f32, f32) },
+}
+
+/// A clip mask drawn directly from geometry, without a separate mask node.
+#[derive(Clone)]
+pub struct ClipPath {
+    pub shape: ClipShape,
+    /// Softness of the clip edge in pixels (Gaussian blur sigma).
+    pub feather: Animated<f32>,
+    /// Grows (positive) or shrinks (negative) the shape by this many pixels.
+    pub expansion: Animated<f32>,
+    /// Keeps the content outside the shape instead of inside it.
+    pub invert: bool,
+}
+
+impl ClipPath {
+    pub fn new(shape: ClipShape) -> Self {
+        Self {
+            shape,
+            feather: Animated::new(0.0),
+            expansion: Animated::new(0.0),
+            invert: false,
+        }
+    }
+
+    pub fn update(&mut self, time: f64) {
+        self.feather.update(time);
+        self.expansion.update(time);
+    }
+
+    /// Resolves the shape to a path for a node whose local bounds are `bounds`.
+    pub fn to_path(&self, bounds: Rect) -> Path {
+        match &self.shape {
+            ClipShape::Path(path) => path.clone(),
+            ClipShape::Rect { rect, radius } => {
+                let rect = rect.unwrap_or(bounds);
+                let mut path = Path::new();
+                if *radius > 0.0 {
+                    path.add_rrect(RRect::new_rect_xy(rect, *radius, *radius), None);
+                } else {
+                    path.add_rect(rect, None);
+                }
+                path
+            }
+            ClipShape::Ellipse { rect } => {
+                let mut path = Path::new();
+                path.add_oval(rect.unwrap_or(bounds), None);
+                path
+            }
+            ClipShape::Outline { path, size } => {
+                if size.0 <= 0.0 || size.1 <= 0.0 {
+                    return path.clone();
+                }
+                let matrix = Matrix::scale((bounds.width() / size.0, bounds.height() / size.1));
+                path.with_transform(&matrix)
+            }
+        }
+    }
+}
+
 /// Represents the affine transformation state of a node.
 #[derive(Clone, Debug)]
 pub struct Transform {
@@ -106,7 +201,7 @@ impl Color {
         a: 1.0,
     };
 
-    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
         Self { r, g, b, a }
     }
 
@@ -132,18 +227,166 @@ impl Default for Color {
     }
 }
 
+/// How colour animations blend between keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorInterpolation {
+    /// Per-channel lerp of the sRGB-encoded values (default).
+    #[default]
+    Srgb,
+    /// Premultiplied OKLab, so midpoints keep their perceived lightness instead of dipping
+    /// through the darker, muddier colours of an sRGB lerp.
+    Oklab,
+}
+
+thread_local! {
+    static COLOR_INTERPOLATION: Cell<ColorInterpolation> =
+        const { Cell::new(ColorInterpolation::Srgb) };
+}
+
+/// Restores the previous colour interpolation when dropped.
+pub struct ColorInterpolationGuard(ColorInterpolation);
+
+impl Drop for ColorInterpolationGuard {
+    fn drop(&mut self) {
+        COLOR_INTERPOLATION.set(self.0);
+    }
+}
+
+impl ColorInterpolation {
+    /// Makes colour tweens on this thread use this interpolation until the guard drops.
+    ///
+    /// `CanTween` has no context argument, so the director enters its setting for each
+    /// update and draw instead of passing it down.
+    pub fn enter(self) -> ColorInterpolationGuard {
+        ColorInterpolationGuard(COLOR_INTERPOLATION.replace(self))
+    }
+}
+
 impl CanTween for Color {
     fn ease(from: Self, to: Self, time: impl keyframe::num_traits::Float) -> Self {
         let t = time.to_f64().unwrap() as f32;
-        Self {
-            r: from.r + (to.r - from.r) * t,
-            g: from.g + (to.g - from.g) * t,
-            b: from.b + (to.b - from.b) * t,
-            a: from.a + (to.a - from.a) * t,
+        match COLOR_INTERPOLATION.get() {
+            ColorInterpolation::Srgb => Self {
+                r: from.r + (to.r - from.r) * t,
+                g: from.g + (to.g - from.g) * t,
+                b: from.b + (to.b - from.b) * t,
+                a: from.a + (to.a - from.a) * t,
+            },
+            ColorInterpolation::Oklab => Self::lerp_oklab(from, to, t),
         }
     }
 }
 
+impl Color {
+    /// Blends `from` and `to` in premultiplied OKLab. `t` outside `0 - 1` extrapolates, so
+    /// overshooting easings overshoot the colour too (clamped to the sRGB gamut).
+    pub fn lerp_oklab(from: Self, to: Self, t: f32) -> Self {
+        let a = from.a + (to.a - from.a) * t;
+        if a <= 0.0 {
+            return Self { a: 0.0, ..to };
+        }
+        let [l1, a1, b1] = from.to_oklab();
+        let [l2, a2, b2] = to.to_oklab();
+        let mix = |x: f32, y: f32| (x * from.a + (y * to.a - x * from.a) * t) / a;
+        Self::from_oklab([mix(l1, l2), mix(a1, a2), mix(b1, b2)], a)
+    }
+
+    /// Converts the (sRGB-encoded) colour to OKLab `[L, a, b]`.
+    pub fn to_oklab(&self) -> [f32; 3] {
+        let [r, g, b] = [self.r, self.g, self.b].map(srgb_to_linear);
+
+        let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+        let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+        let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+        let [l, m, s] = [l, m, s].map(f32::cbrt);
+
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+
+    /// Builds an sRGB colour from OKLab `[L, a, b]`, clamping out-of-gamut channels.
+    pub fn from_oklab([l, a, b]: [f32; 3], alpha: f32) -> Self {
+        let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+        let [l, m, s] = [l_, m_, s_].map(|v| v * v * v);
+
+        let [r, g, b] = [
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_4 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        ]
+        .map(|c| linear_to_srgb(c.clamp(0.0, 1.0)));
+        Self { r, g, b, a: alpha }
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Colour space that the scene is composited in during export.
+///
+/// `Srgb` renders into 8-bit sRGB surfaces, blending gamma-encoded values. The linear
+/// options render into half-float (F16) surfaces so blending, blurs and motion blur
+/// happen in linear light; frames are converted back to sRGB-encoded Rec.709 for encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkingColorSpace {
+    /// 8-bit, gamma-encoded sRGB (default).
+    #[default]
+    Srgb,
+    /// Half-float, linear sRGB primaries.
+    LinearSrgb,
+    /// Half-float, linear Display P3 primaries (wider gamut for intermediate results).
+    LinearDisplayP3,
+}
+
+impl WorkingColorSpace {
+    /// Returns the Skia colour space of surfaces rendered in this working space.
+    pub fn color_space(&self) -> ColorSpace {
+        match self {
+            Self::Srgb => ColorSpace::new_srgb(),
+            Self::LinearSrgb => ColorSpace::new_srgb_linear(),
+            Self::LinearDisplayP3 => ColorSpace::new_cicp(
+                named_primaries::CicpId::SMPTE_EG_432_1,
+                named_transfer_fn::CicpId::Linear,
+            )
+            .unwrap_or_else(ColorSpace::new_srgb_linear),
+        }
+    }
+
+    /// Returns the image info for a working surface of the given size.
+    pub fn image_info(&self, (width, height): (i32, i32)) -> ImageInfo {
+        let color_type = match self {
+            Self::Srgb => ColorType::RGBA8888,
+            Self::LinearSrgb | Self::LinearDisplayP3 => ColorType::RGBAF16,
+        };
+        ImageInfo::new(
+            (width, height),
+            color_type,
+            AlphaType::Premul,
+            Some(self.color_space()),
+        )
+    }
+}
+
 /// Configuration for a linear gradient fill.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GradientConfig {
@@ -168,6 +411,117 @@ impl Default for GradientConfig {
     }
 }
 
+/// Shape of the ends of stroked lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrokeCap {
+    /// Ends exactly at the end point.
+    #[default]
+    Butt,
+    /// Extends past the end point by a half circle.
+    Round,
+    /// Extends past the end point by half the stroke width.
+    Square,
+}
+
+impl StrokeCap {
+    pub fn to_skia(self) -> PaintCap {
+        match self {
+            Self::Butt => PaintCap::Butt,
+            Self::Round => PaintCap::Round,
+            Self::Square => PaintCap::Square,
+        }
+    }
+}
+
+/// Shape of the corners of stroked lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrokeJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl StrokeJoin {
+    pub fn to_skia(self) -> PaintJoin {
+        match self {
+            Self::Miter => PaintJoin::Miter,
+            Self::Round => PaintJoin::Round,
+            Self::Bevel => PaintJoin::Bevel,
+        }
+    }
+}
+
+/// How a chart node draws its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartKind {
+    /// Vertical bars, grouped side by side per label.
+    #[default]
+    Bar,
+    /// A line through each series' values.
+    Line,
+    /// A line with the space below it filled.
+    Area,
+    /// Slices of the first series.
+    Pie,
+}
+
+/// Language whose syntax a code node highlights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeLanguage {
+    /// No highlighting.
+    #[default]
+    Plain,
+    Rust,
+    /// JavaScript and TypeScript.
+    #[serde(
+        rename = "javascript",
+        alias = "js",
+        alias = "typescript",
+        alias = "ts"
+    )]
+    JavaScript,
+    #[serde(alias = "py")]
+    Python,
+    Go,
+    /// C, C++, C#, Java and other C-like languages.
+    #[serde(alias = "cpp", alias = "java", alias = "csharp")]
+    C,
+    Json,
+    /// Shell scripts (sh, bash, zsh).
+    #[serde(alias = "bash", alias = "sh")]
+    Shell,
+    Sql,
+}
+
+/// Which part of its track a waveform node shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaveformMode {
+    /// The whole track, with a playhead moving across it.
+    #[default]
+    Overview,
+    /// A window of the track scrolling past a playhead in the middle.
+    Scrolling,
+}
+
+/// How a spectrum node draws its frequency bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpectrumStyle {
+    /// Vertical bars rising from the bottom edge.
+    #[default]
+    Bars,
+    /// Bars pointing outwards from a ring.
+    Circle,
+    /// A line through the band levels.
+    Line,
+}
+
 // --- From tokens.rs ---
 
 /// Represents a "safe zone" or padding area to avoid UI elements on specific platforms.
@@ -49,7 +49,7 @@ pub enum VideoResponse {
 mod real {
     use super::*;
     use ndarray::Array3;
-    use video_rs::ffmpeg::{self, codec, format, software, ChannelLayout};
+    use video_rs::ffmpeg::{self, codec, color, format, software, ChannelLayout};
     pub use video_rs::{Location as Locator, Time};
 
     /// Hardware acceleration options for video encoding.
@@ -147,6 +147,17 @@ mod real {
             v_encoder.set_format(format::Pixel::YUV420P);
             v_encoder.set_time_base((1, 90000));
 
+            // Tag the stream as Rec.709 (primaries, transfer, matrix, limited range) so players
+            // don't have to guess. Frames are written with their display encoding unchanged,
+            // which is how Rec.709 SDR video is viewed.
+            v_encoder.set_colorspace(color::Space::BT709);
+            v_encoder.set_color_range(color::Range::MPEG);
+            unsafe {
+                let ctx = v_encoder.as_mut_ptr();
+                (*ctx).color_primaries = ffmpeg::ffi::AVColorPrimaries::AVCOL_PRI_BT709;
+                (*ctx).color_trc = ffmpeg::ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
+            }
+
             if global_header {
                 v_encoder.set_flags(codec::flag::Flags::GLOBAL_HEADER);
             }
@@ -178,7 +189,7 @@ mod real {
             let audio_idx = o_stream_a.index();
 
             // Scaler
-            let scaler = software::scaling::Context::get(
+            let mut scaler = software::scaling::Context::get(
                 format::Pixel::RGBA,
                 settings.width as u32,
                 settings.height as u32,
@@ -188,6 +199,23 @@ mod real {
                 software::scaling::flag::Flags::BILINEAR,
             )?;
 
+            // swscale defaults to BT.601 coefficients; convert full-range RGB with BT.709 to
+            // match the stream tags
+            unsafe {
+                let coefficients =
+                    ffmpeg::ffi::sws_getCoefficients(ffmpeg::ffi::SWS_CS_ITU709 as i32);
+                ffmpeg::ffi::sws_setColorspaceDetails(
+                    scaler.as_mut_ptr(),
+                    coefficients,
+                    1,
+                    coefficients,
+                    0,
+                    0,
+                    1 << 16,
+                    1 << 16,
+                );
+            }
+
             output.write_header()?;
 
             // Pre-allocate frame buffers for performance
@@ -196,11 +224,15 @@ mod real {
                 settings.width as u32,
                 settings.height as u32,
             );
-            let yuv_frame = ffmpeg::util::frame::Video::new(
+            let mut yuv_frame = ffmpeg::util::frame::Video::new(
                 format::Pixel::YUV420P,
                 settings.width as u32,
                 settings.height as u32,
             );
+            yuv_frame.set_color_space(color::Space::BT709);
+            yuv_frame.set_color_range(color::Range::MPEG);
+            yuv_frame.set_color_primaries(color::Primaries::BT709);
+            yuv_frame.set_color_transfer_characteristic(color::TransferCharacteristic::BT709);
 
             // Pre-allocate audio channel buffers (AAC frame size is typically 1024)
             let audio_frame_size = a_encoder.frame_size() as usize;
@@ -0,0 +1,71 @@
+//! Adjustment Layer Tests
+//!
+//! Tests for `add_adjustment_layer`, which filters the backdrop beneath a node.
+
+use director_core::node::EffectNode;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test a grayscale adjustment layer over half of a red box.
+///
+/// Validates:
+/// - apply_effect() on an adjustment layer extends its chain instead of wrapping it
+/// - The backdrop inside the layer's bounds is filtered
+/// - Content outside the layer's bounds is untouched
+#[test]
+fn adjustment_layer_filters_backdrop() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+scene.add_box(#{ width: 100.0, height: 100.0, bg_color: "#FF0000" });
+
+let grade = scene.add_adjustment_layer(#{ width: 50.0 });
+grade.apply_effect("grayscale");
+
+movie
+"##;
+
+    let movie = engine
+        .eval::<director_core::scripting::MovieHandle>(script)
+        .expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    // IDs: 0=root, 1=red box, 2=adjustment layer (no wrapper node)
+    assert_eq!(director.scene.nodes.len(), 3);
+    let layer = director.scene.get_node(2).unwrap();
+    let effect = layer.element.as_any().downcast_ref::<EffectNode>().unwrap();
+    assert!(effect.adjustment);
+    assert_eq!(effect.effects.len(), 1);
+    assert!(layer.children.is_empty());
+
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+
+    let pixel = |x: usize, y: usize| {
+        let idx = (y * 100 + x) * 4;
+        (pixels[idx], pixels[idx + 1], pixels[idx + 2])
+    };
+
+    let (r, g, b) = pixel(25, 50);
+    assert!(
+        r.abs_diff(g) < 10 && g.abs_diff(b) < 10,
+        "Inside the layer should be gray, got {:?}",
+        (r, g, b)
+    );
+
+    let (r, g, b) = pixel(75, 50);
+    assert!(
+        (r > 200 || b > 200) && g < 50,
+        "Outside the layer should stay red, got {:?}",
+        (r, g, b)
+    );
+}
@@ -0,0 +1,73 @@
+//! Backdrop Tests
+//!
+//! Tests for the BoxNode `backdrop_*` props, which filter what is drawn behind a box.
+
+use director_core::node::BoxNode;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test a frosted-glass box over a red background.
+///
+/// Validates:
+/// - backdrop_* props are parsed, including an "#RRGGBBAA" tint
+/// - Other colour props still reject "#RRGGBBAA"
+/// - The backdrop behind the box is desaturated, the rest is untouched
+#[test]
+fn box_backdrop_filters_behind_box() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+scene.add_box(#{ width: 100.0, height: 100.0, bg_color: "#FF0000" });
+scene.add_box(#{
+    position: "absolute", left: 0.0, top: 0.0, width: 50.0, height: 100.0,
+    border_radius: 8.0,
+    backdrop_blur: 4.0,
+    backdrop_saturation: 0.0,
+    backdrop_tint: "#FFFFFF00",
+    border_color: "#FFFFFF66"
+});
+movie
+"##;
+
+    let movie = engine
+        .eval::<director_core::scripting::MovieHandle>(script)
+        .expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    let glass = director.scene.get_node(2).unwrap();
+    let b = glass.element.as_any().downcast_ref::<BoxNode>().unwrap();
+    assert_eq!(b.backdrop_blur.current_value, 4.0);
+    assert_eq!(b.backdrop_tint.as_ref().unwrap().current_value.a, 0.0);
+    assert!(b.border_color.is_none());
+
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+
+    let pixel = |x: usize, y: usize| {
+        let idx = (y * 100 + x) * 4;
+        (pixels[idx], pixels[idx + 1], pixels[idx + 2])
+    };
+
+    let (r, g, b) = pixel(25, 50);
+    assert!(
+        r.abs_diff(g) < 10 && g.abs_diff(b) < 10,
+        "Behind the glass should be gray, got {:?}",
+        (r, g, b)
+    );
+
+    let (r, g, b) = pixel(75, 50);
+    assert!(
+        (r > 200 || b > 200) && g < 50,
+        "Outside the glass should stay red, got {:?}",
+        (r, g, b)
+    );
+}
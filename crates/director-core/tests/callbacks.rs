@@ -0,0 +1,120 @@
+//! Frame Callback Tests
+//!
+//! Tests for per-frame `on_frame` script callbacks and their sandbox limits.
+
+use director_core::node::BoxNode;
+use director_core::scripting::{run_script, MovieHandle};
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test node and movie callbacks writing properties during update.
+///
+/// Validates:
+/// - Node callbacks receive local time and can set transform/element properties
+/// - Movie callbacks receive global time and resolve captured node handles
+/// - Callbacks override keyframe animation values
+#[test]
+fn on_frame_sets_properties() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+movie.add_scene(1.0);
+let scene = movie.add_scene(2.0);
+
+let spinner = scene.add_box(#{ width: 100.0, height: 100.0, bg_color: "#FF0000" });
+spinner.animate("rotation", 0.0, 10.0, 2.0, "linear");
+spinner.on_frame(|t, node| {
+    node.rotation = t * 90.0;
+    node.x = 10;
+    node.opacity = 0.5;
+});
+
+let follower = scene.add_box(#{ width: 10.0, height: 10.0 });
+movie.on_frame(|t, frame| {
+    let f = frame.node(follower);
+    f.y = t;
+});
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(1.5);
+
+    // IDs: 0=scene 1 root, 1=scene 2 root, 2=spinner, 3=follower
+    let spinner = director.scene.get_node(2).unwrap();
+    assert!((spinner.transform.rotation.current_value - 45.0).abs() < 0.001);
+    assert_eq!(spinner.transform.translate_x.current_value, 10.0);
+    let b = spinner.element.as_any().downcast_ref::<BoxNode>().unwrap();
+    assert!((b.opacity.current_value - 0.5).abs() < 0.001);
+
+    let follower = director.scene.get_node(3).unwrap();
+    assert!((follower.transform.translate_y.current_value - 1.5).abs() < 0.001);
+}
+
+/// Test that runaway callbacks are stopped by the sandbox.
+///
+/// Validates:
+/// - An infinite loop hits the operation limit instead of hanging update()
+/// - Other callbacks still run and the scene graph is restored
+#[test]
+fn on_frame_runaway_callback_is_stopped() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(1.0);
+let a = scene.add_box(#{ width: 10.0, height: 10.0 });
+a.on_frame(|t, node| { loop { } });
+a.on_frame(|t, node| { node.scale = 2.0; });
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(0.5);
+
+    assert_eq!(
+        director.scene.nodes.len(),
+        2,
+        "Scene graph must be restored"
+    );
+    let a = director.scene.get_node(1).unwrap();
+    assert_eq!(a.transform.scale_x.current_value, 2.0);
+}
+
+/// Test that callbacks can call functions defined by the script.
+///
+/// Validates:
+/// - Scripts run through `run_script` keep their functions for callbacks
+/// - Node and movie callbacks can call them, including nested calls
+#[test]
+fn on_frame_calls_script_functions() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+fn wobble(t) { offset() + t * 10.0 }
+fn offset() { 5.0 }
+
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(2.0);
+let a = scene.add_box(#{ width: 10.0, height: 10.0 });
+a.on_frame(|t, node| { node.x = wobble(t); });
+movie.on_frame(|t, frame| { frame.node(a).y = offset(); });
+movie
+"##;
+
+    let movie = run_script(&engine, script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(1.0);
+
+    let a = director.scene.get_node(1).unwrap();
+    assert_eq!(a.transform.translate_x.current_value, 15.0);
+    assert_eq!(a.transform.translate_y.current_value, 5.0);
+}
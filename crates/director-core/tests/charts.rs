@@ -0,0 +1,139 @@
+//! Chart Tests
+//!
+//! Tests for `ChartNode` build-ins, data transitions and the `add_chart` API.
+
+use director_core::director::Director;
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Renders `time` and returns whether the pixel at `(x, y)` is drawn, i.e. isn't the
+/// black the frame is cleared to.
+fn drawn(director: &mut Director, time: f64, x: usize, y: usize) -> bool {
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(director, time, surface.canvas()).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+    let i = (y * 100 + x) * 4;
+    pixels[i..i + 3].iter().any(|&c| c > 0)
+}
+
+/// Test bar growth and transitions between datasets.
+///
+/// Validates:
+/// - `build` grows bars from the baseline
+/// - Bar heights follow their values on the value axis
+/// - `animate_data` holds for its delay, then moves the bars to the new values
+#[test]
+fn bar_chart_builds_and_transitions() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(4.0);
+let chart = scene.add_chart(#{
+    type: "bar",
+    width: 100.0,
+    height: 100.0,
+    labels: ["A", "B"],
+    data: [10, 20],
+    axes: false,
+    legend: false,
+    grid_color: "none",
+    bar_gap: 0.0,
+    stagger: 0.0,
+});
+chart.animate("build", 0.0, 1.0, 1.0, "linear");
+chart.animate_data([20, 10], 1.0, "linear", 2.0);
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    assert!(!drawn(&mut director, 0.0, 25, 95), "Nothing before the build");
+    assert!(drawn(&mut director, 0.5, 75, 95), "Bars grow from the baseline");
+    assert!(!drawn(&mut director, 0.5, 75, 5), "Bars are still growing");
+    assert!(drawn(&mut director, 1.5, 25, 75), "First bar reaches its value");
+    assert!(!drawn(&mut director, 1.5, 25, 25), "First bar stops at half height");
+    assert!(drawn(&mut director, 1.5, 75, 5), "Second bar fills the axis");
+    assert!(drawn(&mut director, 3.0, 25, 5), "First bar grew to the new value");
+    assert!(!drawn(&mut director, 3.0, 75, 25), "Second bar shrank to the new value");
+}
+
+/// Test pie sweeps, line draw-on and script errors.
+///
+/// Validates:
+/// - A half-built pie covers only the first half of its sweep
+/// - A line chart draws on from the left
+/// - Unknown chart types, mismatched datasets and non-chart nodes are script errors
+#[test]
+fn pie_and_line_charts() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(2.0);
+scene.add_chart(#{
+    type: "pie",
+    width: 100.0,
+    height: 100.0,
+    position: "absolute",
+    data: [1, 1, 1, 1],
+    legend: false,
+    build: 0.5,
+});
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    assert!(drawn(&mut director, 0.0, 75, 50), "Right half is swept");
+    assert!(!drawn(&mut director, 0.0, 25, 50), "Left half is not swept yet");
+    drop(director);
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(2.0);
+let chart = scene.add_chart(#{
+    type: "line",
+    width: 100.0,
+    height: 100.0,
+    labels: ["a", "b", "c", "d"],
+    data: [5, 5, 5, 5],
+    max: 10,
+    axes: false,
+    legend: false,
+    grid_color: "none",
+    line_width: 6.0,
+});
+chart.animate("build", 0.0, 1.0, 1.0, "linear");
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    assert!(drawn(&mut director, 0.5, 20, 50), "Line starts on the left");
+    assert!(!drawn(&mut director, 0.5, 80, 50), "Line has not reached the right");
+    assert!(drawn(&mut director, 1.0, 80, 50), "Line is complete");
+    drop(director);
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_chart(#{ type: "radar" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_chart(#{ data: [1, [2]] });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_chart(#{ data: [[1], [2]] }).animate_data([1], 1.0, "linear");"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_box(#{}).animate_data([1], 1.0, "linear");"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
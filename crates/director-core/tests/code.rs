@@ -0,0 +1,163 @@
+//! Code Tests
+//!
+//! Tests for the syntax tokenizer, `CodeNode` typing, edits and focus, and the `add_code` API.
+
+use director_core::node::{tokenize, CodeNode, Token, TokenKind};
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::types::CodeLanguage;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Kind of the first token on `line` whose trimmed text is `text`.
+fn kind(lines: &[Vec<Token>], line: usize, text: &str) -> TokenKind {
+    lines[line]
+        .iter()
+        .find(|t| t.text.trim() == text)
+        .unwrap_or_else(|| panic!("No token '{}' on line {}: {:?}", text, line, lines[line]))
+        .kind
+}
+
+/// Test highlighting of common languages.
+///
+/// Validates:
+/// - Keywords, types, function names, numbers, strings and comments are told apart
+/// - Block comments and multi-line strings are split into their lines
+/// - JSON keys differ from values, SQL keywords ignore case, shell variables stand out
+/// - Plain text keeps its lines without highlighting
+#[test]
+fn tokenize_languages() {
+    let rust = tokenize(
+        "fn main() {\n    let x: u32 = 42; // answer\n    println!(\"{}\", x);\n}",
+        CodeLanguage::Rust,
+    );
+    assert_eq!(rust.len(), 4);
+    assert_eq!(kind(&rust, 0, "fn"), TokenKind::Keyword);
+    assert_eq!(kind(&rust, 0, "main"), TokenKind::Function);
+    assert_eq!(kind(&rust, 1, "u32"), TokenKind::Type);
+    assert_eq!(kind(&rust, 1, "42"), TokenKind::Number);
+    assert_eq!(kind(&rust, 1, "// answer"), TokenKind::Comment);
+    assert_eq!(kind(&rust, 2, "println!"), TokenKind::Function);
+    assert_eq!(kind(&rust, 2, "\"{}\""), TokenKind::String);
+
+    let js = tokenize("/* one\ntwo */ const s = `a\nb`;", CodeLanguage::JavaScript);
+    assert_eq!(js.len(), 3);
+    assert_eq!(kind(&js, 0, "/* one"), TokenKind::Comment);
+    assert_eq!(kind(&js, 1, "two */"), TokenKind::Comment);
+    assert_eq!(kind(&js, 1, "const"), TokenKind::Keyword);
+    assert_eq!(kind(&js, 2, "b`"), TokenKind::String);
+
+    let python = tokenize("@cache\ndef f():\n    return None", CodeLanguage::Python);
+    assert_eq!(kind(&python, 0, "@cache"), TokenKind::Function);
+    assert_eq!(kind(&python, 1, "f"), TokenKind::Function);
+    assert_eq!(kind(&python, 2, "None"), TokenKind::Constant);
+
+    let json = tokenize(
+        "{ \"name\": \"director\", \"ok\": true }",
+        CodeLanguage::Json,
+    );
+    assert_eq!(kind(&json, 0, "\"name\""), TokenKind::Type);
+    assert_eq!(kind(&json, 0, "\"director\""), TokenKind::String);
+    assert_eq!(kind(&json, 0, "true"), TokenKind::Constant);
+
+    let sql = tokenize("SELECT id FROM users -- all", CodeLanguage::Sql);
+    assert_eq!(kind(&sql, 0, "SELECT"), TokenKind::Keyword);
+    assert_eq!(kind(&sql, 0, "-- all"), TokenKind::Comment);
+
+    let shell = tokenize("echo $HOME", CodeLanguage::Shell);
+    assert_eq!(kind(&shell, 0, "$HOME"), TokenKind::Type);
+
+    let plain = tokenize("let x = 1\n\nend", CodeLanguage::Plain);
+    assert_eq!(plain.len(), 3);
+    assert!(plain[1].is_empty());
+    assert_eq!(plain[0][0].kind, TokenKind::Plain);
+}
+
+/// Counts the drawn pixels of a frame: those that aren't the black it is cleared to.
+fn coverage(movie: &MovieHandle, time: f64) -> usize {
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((400, 200)).unwrap();
+    render_frame(&mut director, time, surface.canvas()).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (400, 200),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut pixels = vec![0u8; 400 * 200 * 4];
+    assert!(surface.read_pixels(&info, &mut pixels, 400 * 4, (0, 0)));
+    pixels
+        .chunks(4)
+        .filter(|px| px[..3].iter().any(|&c| c > 0))
+        .count()
+}
+
+/// Test typing, code transitions and line focus.
+///
+/// Validates:
+/// - `typed` reveals the code progressively
+/// - `animate_code` holds for its delay, then steps to the new version
+/// - `focus_lines` focuses lines by number and the `highlight` prop focuses from the start
+/// - Unknown languages and themes, bad line numbers and non-code nodes are script errors
+#[test]
+fn code_typing_edits_and_focus() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(400, 200, 30);
+let scene = movie.add_scene(6.0);
+let code = scene.add_code(#{
+    code: "let total = price * count;\nprint(total);",
+    language: "js",
+    background: false,
+    line_numbers: false,
+    highlight: [1],
+    typed: 0.0,
+});
+code.animate("typed", 0.0, 1.0, 1.0, "linear");
+code.animate_code("let total = price * count;\nlet tax = total * 0.2;\nprint(total + tax);", 1.0, "linear", 2.0);
+code.focus_lines([2, 3], 1.0, "linear", 3.0);
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let empty = coverage(&movie, 0.0);
+    let half = coverage(&movie, 0.5);
+    let full = coverage(&movie, 1.0);
+    assert!(empty < half && half < full, "Typing reveals the code");
+
+    let state = |time: f64| {
+        let mut director = movie.director.lock().unwrap();
+        director.update(time);
+        let node = director.scene.get_node(1).unwrap();
+        let code = node.element.as_any().downcast_ref::<CodeNode>().unwrap();
+        (
+            code.snapshots.len(),
+            code.step.current_value,
+            code.focus.current_value.0.clone(),
+        )
+    };
+    let (versions, step, focus) = state(1.5);
+    assert_eq!(versions, 2);
+    assert_eq!(step, 0.0, "Holds the first version during the delay");
+    assert_eq!(focus, vec![1.0, 0.0, 0.0]);
+    assert_eq!(state(2.5).1, 0.5, "Halfway through the transition");
+    assert_eq!(state(3.0).1, 1.0);
+    assert_eq!(
+        state(4.0).2,
+        vec![0.0, 1.0, 1.0],
+        "Focus moved to lines 2 and 3"
+    );
+    assert!(coverage(&movie, 4.0) > 0);
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_code(#{ language: "cobol" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_code(#{ theme: "neon" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_code(#{ highlight: [0] });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_box(#{}).animate_code("x", 1.0, "linear");"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
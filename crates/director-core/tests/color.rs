@@ -0,0 +1,176 @@
+//! Colour Management Tests
+//!
+//! Tests for sRGB and OKLab colour interpolation and the linear working colour space.
+
+use director_core::animation::{Animated, EasingType};
+use director_core::node::BoxNode;
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::types::{Color, ColorInterpolation, WorkingColorSpace};
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use keyframe::CanTween;
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test colour tweening in sRGB (default) and OKLab.
+///
+/// Validates:
+/// - By default channels lerp as before, and overshooting `t` extrapolates
+/// - In OKLab a red to green midpoint stays bright instead of a muddy (0.5, 0.5, 0)
+/// - Fading in from transparent keeps the target colour (premultiplied interpolation)
+/// - OKLab extrapolates past the endpoints too, and conversion round-trips
+/// - The guard restores the previous interpolation
+#[test]
+fn color_tween_modes() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let green = Color::new(0.0, 1.0, 0.0, 1.0);
+    assert_eq!(Color::ease(red, green, 0.5), Color::new(0.5, 0.5, 0.0, 1.0));
+    assert_eq!(
+        Color::ease(red, green, 1.5),
+        Color::new(-0.5, 1.5, 0.0, 1.0)
+    );
+
+    let guard = ColorInterpolation::Oklab.enter();
+    let close = |a: Color, b: Color| {
+        (a.r - b.r).abs() < 1e-3 && (a.g - b.g).abs() < 1e-3 && (a.b - b.b).abs() < 1e-3
+    };
+    assert!(close(Color::ease(red, green, 0.0), red));
+    assert!(close(Color::ease(red, green, 1.0), green));
+
+    let mid = Color::ease(red, green, 0.5);
+    assert!(
+        mid.r > 0.6 && mid.g > 0.5 && mid.b < 0.1,
+        "Midpoint should stay bright, got {:?}",
+        mid
+    );
+
+    let clear = Color::new(0.0, 0.0, 0.0, 0.0);
+    let fade = Color::ease(clear, Color::WHITE, 0.5);
+    assert!((fade.a - 0.5).abs() < 1e-4);
+    assert!(
+        fade.r > 0.99 && fade.g > 0.99 && fade.b > 0.99,
+        "Fade-in should not pass through gray, got {:?}",
+        fade
+    );
+
+    let gray = Color::new(0.5, 0.5, 0.5, 1.0);
+    let past = Color::ease(gray, Color::WHITE, 1.2);
+    assert_eq!(past.a, 1.0);
+    assert!(close(past, Color::WHITE), "Overshoot clamps to the gamut");
+    let under = Color::ease(gray, Color::WHITE, -0.2);
+    assert!(
+        under.r < 0.5,
+        "Undershoot darkens past the start, got {:?}",
+        under
+    );
+
+    let orange = Color::new(1.0, 0.5, 0.2, 1.0);
+    let back = Color::from_oklab(orange.to_oklab(), 1.0);
+    assert!(close(back, orange));
+
+    drop(guard);
+    assert_eq!(Color::ease(red, green, 0.5), Color::new(0.5, 0.5, 0.0, 1.0));
+}
+
+/// Test the director's colour interpolation setting.
+///
+/// Validates:
+/// - `color_interpolation` in the director config and `set_color_interpolation` select it
+/// - Animated colours on nodes blend in the director's interpolation during `update`
+/// - Unknown names are script errors
+#[test]
+fn director_color_interpolation() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let mid_color = |script: &str| {
+        let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+        let mut director = movie.director.lock().unwrap();
+        let mut color = Animated::new(Color::new(1.0, 0.0, 0.0, 1.0));
+        color.add_keyframe(Color::new(0.0, 1.0, 0.0, 1.0), 1.0, EasingType::Linear);
+        let node = director.scene.get_node_mut(1).unwrap();
+        let box_node = node.element.as_any_mut().downcast_mut::<BoxNode>().unwrap();
+        box_node.bg_color = Some(color);
+        director.update(0.5);
+        let node = director.scene.get_node(1).unwrap();
+        let box_node = node.element.as_any().downcast_ref::<BoxNode>().unwrap();
+        (
+            director.color_interpolation,
+            box_node.bg_color.as_ref().unwrap().current_value,
+        )
+    };
+
+    let (mode, srgb) =
+        mid_color("let movie = new_director(20, 20, 30); movie.add_scene(1.0).add_box(#{}); movie");
+    assert_eq!(mode, ColorInterpolation::Srgb);
+    assert_eq!(srgb, Color::new(0.5, 0.5, 0.0, 1.0));
+
+    let (mode, oklab) = mid_color(
+        r#"let movie = new_director(20, 20, 30, #{ color_interpolation: "oklab" }); movie.add_scene(1.0).add_box(#{}); movie"#,
+    );
+    assert_eq!(mode, ColorInterpolation::Oklab);
+    assert!(oklab.r > 0.6 && oklab.g > 0.5, "Got {:?}", oklab);
+
+    let (mode, _) = mid_color(
+        r#"let movie = new_director(20, 20, 30); movie.set_color_interpolation("oklab"); movie.add_scene(1.0).add_box(#{}); movie"#,
+    );
+    assert_eq!(mode, ColorInterpolation::Oklab);
+
+    assert!(engine
+        .run(r#"let movie = new_director(20, 20, 30); movie.set_color_interpolation("hsl");"#)
+        .is_err());
+}
+
+/// Test blending in the linear working colour space.
+///
+/// Validates:
+/// - `color_space` in the director config selects the working space
+/// - 50% white over black is 50% light (sRGB ~188) in linear space, vs ~128 in sRGB
+#[test]
+fn linear_working_space_blends_in_linear_light() {
+    let render = |space: &str| {
+        let mut engine = Engine::new();
+        register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+        let script = format!(
+            r##"
+let movie = new_director(20, 20, 30, #{{ color_space: "{}" }});
+let scene = movie.add_scene(1.0);
+scene.add_box(#{{ width: 20.0, height: 20.0 }});
+movie
+"##,
+            space
+        );
+        let movie = engine.eval::<MovieHandle>(&script).expect("Script failed");
+        let mut director = movie.director.lock().unwrap();
+        let node = director.scene.get_node_mut(1).unwrap();
+        let half_white = Color::new(1.0, 1.0, 1.0, 0.5);
+        node.element
+            .as_any_mut()
+            .downcast_mut::<BoxNode>()
+            .unwrap()
+            .bg_color = Some(Animated::new(half_white));
+
+        let info = director.color_space.image_info((20, 20));
+        let mut surface = skia_safe::surfaces::raster(&info, None, None).unwrap();
+        render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+        let out = skia_safe::ImageInfo::new(
+            (20, 20),
+            skia_safe::ColorType::RGBA8888,
+            skia_safe::AlphaType::Premul,
+            Some(skia_safe::ColorSpace::new_srgb()),
+        );
+        let mut pixels = vec![0u8; 20 * 20 * 4];
+        assert!(surface.read_pixels(&out, &mut pixels, 20 * 4, (0, 0)));
+        (director.color_space, pixels[(10 * 20 + 10) * 4])
+    };
+
+    let (space, gamma) = render("srgb");
+    assert_eq!(space, WorkingColorSpace::Srgb);
+    assert!(gamma.abs_diff(128) < 6, "Expected ~128, got {}", gamma);
+
+    let (space, linear) = render("linear_srgb");
+    assert_eq!(space, WorkingColorSpace::LinearSrgb);
+    assert!(linear.abs_diff(188) < 6, "Expected ~188, got {}", linear);
+}
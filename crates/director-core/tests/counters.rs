@@ -0,0 +1,193 @@
+//! Counter Tests
+//!
+//! Tests for `NumberFormat`, counting text nodes and the `add_counter` API.
+
+use director_core::node::{NumberFormat, TextNode};
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+fn format(value: f64, configure: impl FnOnce(&mut NumberFormat)) -> String {
+    let mut format = NumberFormat::default();
+    configure(&mut format);
+    format.format(value)
+}
+
+/// Test number formatting across locales.
+///
+/// Validates:
+/// - Grouping and decimal separators follow the locale, including Indian lakh grouping
+/// - Currencies use their symbol, placement and usual decimals
+/// - Percent multiplies by 100 and negative zero loses its sign
+#[test]
+fn number_format_locales() {
+    assert_eq!(
+        format(1234567.891, |f| f.decimals = Some(2)),
+        "1,234,567.89"
+    );
+    assert_eq!(format(1234567.0, |f| f.grouping = false), "1234567");
+    assert_eq!(
+        format(1234567.0, |f| f.locale = "fr-FR".into()),
+        "1\u{202F}234\u{202F}567"
+    );
+    assert_eq!(
+        format(12345678.0, |f| f.locale = "en-IN".into()),
+        "1,23,45,678"
+    );
+
+    assert_eq!(
+        format(1234.5, |f| {
+            f.currency = Some("EUR".into());
+            f.locale = "de-DE".into();
+        }),
+        "1.234,50\u{A0}€"
+    );
+    assert_eq!(format(-5.5, |f| f.currency = Some("USD".into())), "-$5.50");
+    assert_eq!(
+        format(1234.6, |f| f.currency = Some("JPY".into())),
+        "¥1,235"
+    );
+    assert_eq!(
+        format(1234.5, |f| {
+            f.currency = Some("CHF".into());
+            f.locale = "de-CH".into();
+        }),
+        "CHF\u{A0}1’234.50"
+    );
+
+    assert_eq!(
+        format(0.256, |f| {
+            f.percent = true;
+            f.decimals = Some(1);
+        }),
+        "25.6%"
+    );
+    assert_eq!(
+        format(0.5, |f| {
+            f.percent = true;
+            f.locale = "de".into();
+        }),
+        "50\u{A0}%"
+    );
+    assert_eq!(format(-0.001, |f| f.decimals = Some(2)), "0.00");
+    assert_eq!(
+        format(42.0, |f| {
+            f.prefix = "~".into();
+            f.suffix = " users".into();
+        }),
+        "~42 users"
+    );
+}
+
+/// Returns the counter text of node `id` at `time`.
+fn text_at(movie: &MovieHandle, id: usize, time: f64) -> String {
+    let mut director = movie.director.lock().unwrap();
+    director.update(time);
+    let node = director.scene.get_node(id).unwrap();
+    let text = node.element.as_any().downcast_ref::<TextNode>().unwrap();
+    text.spans[0].text.clone()
+}
+
+/// Test counting with tweens and springs.
+///
+/// Validates:
+/// - `count_to` tweens the value and rewrites the text
+/// - Counts queue after each other
+/// - A spring lands exactly on its target
+/// - Counters default to tabular figures
+/// - Non-numeric values, bad currency codes and non-counter nodes are script errors
+#[test]
+fn counter_counts_to_values() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(400, 200, 30);
+let scene = movie.add_scene(12.0);
+let total = scene.add_counter(#{ value: 0, prefix: "$", size: 40.0 });
+total.count_to(1000000, 2.0, "linear");
+total.count_to(2000000, 1.0, "linear");
+let share = scene.add_counter(#{ value: 0.0, percent: true, decimals: 1 });
+share.count_to(0.425, #{ stiffness: 120.0, damping: 12.0 });
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    // IDs: 0=root, 1=total, 2=share
+    assert_eq!(text_at(&movie, 1, 0.0), "$0");
+    assert_eq!(text_at(&movie, 1, 1.0), "$500,000");
+    assert_eq!(text_at(&movie, 1, 2.5), "$1,500,000");
+    assert_eq!(text_at(&movie, 1, 4.0), "$2,000,000");
+    assert_eq!(text_at(&movie, 2, 11.0), "42.5%");
+
+    {
+        let director = movie.director.lock().unwrap();
+        let node = director.scene.get_node(2).unwrap();
+        let text = node.element.as_any().downcast_ref::<TextNode>().unwrap();
+        assert!(text.tabular_figures);
+        assert_eq!(text.counter.as_ref().unwrap().value.current_value, 0.425);
+    }
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_counter(#{ value: "many" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_counter(#{ currency: "dollars" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_counter(#{}).count_to("x", 1.0, "linear");"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_text(#{ content: "1" }).count_to(5, 1.0, "linear");"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
+
+/// Test driving a counter's value from another node.
+///
+/// Validates:
+/// - A driver writing `value` rewrites the counter text
+/// - The paragraph is rebuilt, so the rendered text changes width
+#[test]
+fn counter_value_can_be_driven() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(400, 200, 30);
+let scene = movie.add_scene(2.0);
+let bar = scene.add_box(#{ width: 100.0, height: 10.0 });
+bar.animate("scale", 0.0, 1.0, 1.0, "linear");
+let label = scene.add_counter(#{ value: 0, percent: true, size: 40.0 });
+label.drive("value", bar, "scale");
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    // IDs: 0=root, 1=bar, 2=label
+    assert_eq!(text_at(&movie, 2, 0.5), "50%");
+
+    let rendered_width = |time: f64| {
+        let mut director = movie.director.lock().unwrap();
+        let mut surface = skia_safe::surfaces::raster_n32_premul((400, 200)).unwrap();
+        render_frame(&mut director, time, surface.canvas()).unwrap();
+        let node = director.scene.get_node(2).unwrap();
+        let text = node.element.as_any().downcast_ref::<TextNode>().unwrap();
+        assert_eq!(text.counter.as_ref().unwrap().value.current_value, time);
+        let width = text
+            .paragraph
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .max_intrinsic_width();
+        (text.spans[0].text.clone(), width)
+    };
+    let (empty, narrow) = rendered_width(0.0);
+    let (full, wide) = rendered_width(1.0);
+    assert_eq!(empty, "0%");
+    assert_eq!(full, "100%");
+    assert!(
+        wide > narrow,
+        "Paragraph follows the driven text ({} vs {})",
+        wide,
+        narrow
+    );
+}
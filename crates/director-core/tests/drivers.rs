@@ -0,0 +1,190 @@
+//! Property Driver Tests
+//!
+//! Tests for `drive` links between node properties.
+
+use director_core::node::TextNode;
+use director_core::scripting::MovieHandle;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test mapped, clamped and chained drivers.
+///
+/// Validates:
+/// - Multiplier and offset map the source value
+/// - min/max clamp the output
+/// - A driver whose source is itself driven sees the updated value
+/// - Text content can be driven with a fixed number of decimals
+#[test]
+fn drivers_map_and_chain_properties() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(2.0);
+
+let leader = scene.add_box(#{ width: 10.0, height: 10.0 });
+leader.animate("x", 0.0, 100.0, 2.0, "linear");
+
+let shadow = scene.add_box(#{ width: 10.0, height: 10.0 });
+shadow.drive("y", leader, "x", #{ multiplier: 2.0, offset: 5 });
+
+let clamped = scene.add_box(#{ width: 10.0, height: 10.0 });
+clamped.drive("rotation", shadow, "y", #{ max: 50.0 });
+
+let label = scene.add_text(#{ content: "0" });
+label.drive("content", leader, "x", #{ decimals: 1 });
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(0.5);
+
+    // IDs: 0=root, 1=leader, 2=shadow, 3=clamped, 4=label
+    let y = director
+        .scene
+        .get_node(2)
+        .unwrap()
+        .transform
+        .translate_y
+        .current_value;
+    assert!((y - 55.0).abs() < 0.01, "25 * 2 + 5, got {}", y);
+
+    let rotation = director
+        .scene
+        .get_node(3)
+        .unwrap()
+        .transform
+        .rotation
+        .current_value;
+    assert!(
+        (rotation - 50.0).abs() < 0.01,
+        "Clamped to max, got {}",
+        rotation
+    );
+
+    let label = director.scene.get_node(4).unwrap();
+    let text = label.element.as_any().downcast_ref::<TextNode>().unwrap();
+    let content: String = text.spans.iter().map(|s| s.text.clone()).collect();
+    assert_eq!(content, "25.0");
+}
+
+/// Test delayed drivers.
+///
+/// Validates:
+/// - The driven value shows the source at `t - delay`, even on the first frame rendered
+/// - Frames don't depend on the order they are rendered in
+/// - Before the source starts, its first keyframe is used
+/// - A delayed driver reading a driven source replays that source's driver
+#[test]
+fn delayed_driver_lags_source() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 10);
+let scene = movie.add_scene(2.0);
+
+let leader = scene.add_box(#{ width: 10.0, height: 10.0 });
+leader.animate("x", 0.0, 100.0, 1.0, "linear");
+
+let follower = scene.add_box(#{ width: 10.0, height: 10.0 });
+follower.drive("x", leader, "x", #{ delay: 0.2 });
+
+let tail = scene.add_box(#{ width: 10.0, height: 10.0 });
+tail.drive("x", follower, "x", #{ delay: 0.1, offset: 1.0 });
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    // IDs: 0=root, 1=leader, 2=follower, 3=tail
+    let mut x_at = |time: f64, id: usize| {
+        director.update(time);
+        director
+            .scene
+            .get_node(id)
+            .unwrap()
+            .transform
+            .translate_x
+            .current_value
+    };
+    let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+    let x = x_at(0.7, 2);
+    assert!(
+        close(x, 50.0),
+        "Should show the value from t=0.5, got {}",
+        x
+    );
+    let x = x_at(0.5, 2);
+    assert!(close(x, 30.0), "Seeking back shows t=0.3, got {}", x);
+    let x = x_at(0.7, 2);
+    assert!(close(x, 50.0), "Same frame, same value, got {}", x);
+
+    let x = x_at(0.1, 2);
+    assert!(close(x, 0.0), "Leader hasn't moved before t=0, got {}", x);
+
+    let x = x_at(0.7, 3);
+    assert!(close(x, 41.0), "Leader at t=0.4 plus the offset, got {}", x);
+}
+
+/// Test drivers with several weighted inputs.
+///
+/// Validates:
+/// - Inputs are weighted and summed before the multiplier and offset
+/// - `weight` defaults to 1
+/// - Empty input lists, inputs without a node or property, and nodes of other movies
+///   are script errors
+#[test]
+fn drivers_sum_weighted_inputs() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(2.0);
+
+let a = scene.add_box(#{ width: 10.0, height: 10.0 });
+a.animate("x", 0.0, 100.0, 2.0, "linear");
+let b = scene.add_box(#{ width: 10.0, height: 10.0 });
+b.animate("y", 0.0, 40.0, 2.0, "linear");
+
+let mid = scene.add_box(#{ width: 10.0, height: 10.0 });
+mid.drive("x", [#{ node: a, property: "x", weight: 0.5 }, #{ node: b, property: "y" }], #{ offset: 2.0 });
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(1.0);
+
+    // IDs: 0=root, 1=a, 2=b, 3=mid
+    let x = director
+        .scene
+        .get_node(3)
+        .unwrap()
+        .transform
+        .translate_x
+        .current_value;
+    assert!((x - 47.0).abs() < 0.01, "50 * 0.5 + 20 + 2, got {}", x);
+
+    let setup = "let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); let a = scene.add_box(#{}); let b = scene.add_box(#{});";
+    for call in [
+        r#"b.drive("x", []);"#,
+        r#"b.drive("x", [#{ property: "x" }]);"#,
+        r#"b.drive("x", [#{ node: a }]);"#,
+        r#"b.drive("x", [#{ node: a, property: "x", weight: "half" }]);"#,
+        r#"b.drive("x", [a]);"#,
+        r#"let other = new_director(100, 100, 30).add_scene(1.0).add_box(#{}); b.drive("x", [#{ node: other, property: "x" }]);"#,
+    ] {
+        let script = format!("{} {}", setup, call);
+        assert!(engine.run(&script).is_err(), "{} should fail", call);
+    }
+}
@@ -0,0 +1,111 @@
+//! Reparenting and Reordering Tests
+//!
+//! Tests for `reparent`, `move_before`/`move_after` and `bring_to_front`/`send_to_back`.
+
+use director_core::scripting::MovieHandle;
+use director_core::systems::layout::LayoutEngine;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test that a reparented node is laid out inside its new parent.
+///
+/// Validates:
+/// - reparent() updates both children lists and the parent link
+/// - A persistent LayoutEngine picks up the new structure on the next frame
+#[test]
+fn reparent_keeps_layout_in_sync() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(400, 400, 30);
+let scene = movie.add_scene(2.0);
+
+let left = scene.add_box(#{ width: 100.0, height: 100.0 });
+let right = scene.add_box(#{ width: 200.0, height: 100.0, padding: 20.0 });
+let item = left.add_box(#{ width: 10.0, height: 10.0 });
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut layout_engine = LayoutEngine::new();
+
+    // IDs: 0=root, 1=left, 2=right, 3=item
+    {
+        let mut d = movie.director.lock().unwrap();
+        d.update(0.0);
+        layout_engine.compute_layout(&mut d.scene, 400, 400, 0.0);
+        let rect = d.scene.get_node(3).unwrap().layout_rect;
+        assert_eq!(rect.left, 0.0, "Item starts at the origin of 'left'");
+    }
+
+    {
+        let mut d = movie.director.lock().unwrap();
+        assert!(d.scene.reparent(3, 2, None));
+        assert!(!d.scene.get_node(1).unwrap().children.contains(&3));
+        assert_eq!(d.scene.get_node(2).unwrap().children, vec![3]);
+        assert_eq!(d.scene.get_node(3).unwrap().parent, Some(2));
+
+        d.update(0.1);
+        layout_engine.compute_layout(&mut d.scene, 400, 400, 0.1);
+        let rect = d.scene.get_node(3).unwrap().layout_rect;
+        assert_eq!(
+            rect.left, 20.0,
+            "Item should now sit inside 'right' padding"
+        );
+    }
+}
+
+/// Test script-level reordering and cycle rejection.
+///
+/// Validates:
+/// - move_before/move_after/bring_to_front/send_to_back reorder siblings
+/// - reparent() into a descendant is rejected with a script error
+#[test]
+fn reorder_siblings_via_script() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(400, 400, 30);
+let scene = movie.add_scene(1.0);
+
+let a = scene.add_box(#{ width: 10.0, height: 10.0 });
+let b = scene.add_box(#{ width: 10.0, height: 10.0 });
+let c = scene.add_box(#{ width: 10.0, height: 10.0 });
+
+c.move_before(a);   // c, a, b
+a.move_after(b);    // c, b, a
+c.bring_to_front(); // b, a, c
+a.send_to_back();   // a, b, c
+
+let inner = a.add_box(#{ width: 5.0, height: 5.0 });
+inner.reparent(b, 0);
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    {
+        let d = movie.director.lock().unwrap();
+        // IDs: 0=root, 1=a, 2=b, 3=c, 4=inner
+        assert_eq!(d.scene.get_node(0).unwrap().children, vec![1, 2, 3]);
+        assert_eq!(d.scene.get_node(2).unwrap().children, vec![4]);
+        assert!(d.scene.get_node(1).unwrap().children.is_empty());
+    }
+
+    let mut scope = rhai::Scope::new();
+    scope.push("movie", movie.clone());
+    let cycle = engine.eval_with_scope::<()>(
+        &mut scope,
+        r#"
+let scene = movie.add_scene(1.0);
+let outer = scene.add_box(#{});
+let nested = outer.add_box(#{});
+outer.reparent(nested);
+"#,
+    );
+    assert!(cycle.is_err(), "Reparenting into a descendant must fail");
+}
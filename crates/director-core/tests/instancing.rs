@@ -0,0 +1,306 @@
+//! Subtree Cloning and Symbol Instancing Tests
+//!
+//! Tests for `SceneGraph::clone_subtree`, `duplicate`, `make_symbol`, `add_instance` and
+//! keeping instances in sync with their master.
+
+use director_core::animation::Animated;
+use director_core::node::{BoxNode, TextNode};
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Test deep duplication of a subtree via Rhai.
+///
+/// Validates:
+/// - duplicate() attaches the copy to the original's parent
+/// - Children and masks are copied to new IDs, not shared
+/// - Animations are carried over to the copy
+#[test]
+fn duplicate_deep_copies_subtree() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(2.0);
+
+let card = scene.add_box(#{ width: 100.0, height: 100.0, bg_color: "#FF0000" });
+let inner = card.add_box(#{ width: 50.0, height: 50.0, bg_color: "#00FF00" });
+let mask = card.add_box(#{ width: 80.0, height: 80.0, bg_color: "#FFFFFF" });
+card.set_mask(mask);
+card.animate("scale", 0.0, 1.0, 1.0, "linear");
+
+let copy = card.duplicate();
+
+movie
+"##;
+
+    let result = engine.eval::<director_core::scripting::MovieHandle>(script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let movie = result.unwrap();
+    let mut director = movie.director.lock().unwrap();
+
+    // IDs: 0=root, 1=card, 2=inner, 3=mask, 4=copy, 5=inner copy, 6=mask copy
+    let root = director.scene.get_node(0).unwrap();
+    assert_eq!(root.children, vec![1, 4], "Copy should be a sibling");
+
+    let copy = director.scene.get_node(4).expect("Copy should exist");
+    assert_eq!(copy.parent, Some(0));
+    assert_eq!(copy.children, vec![5], "Copy should own a copied child");
+    assert_eq!(copy.mask_node, Some(6), "Copy should own a copied mask");
+    assert_eq!(director.scene.get_node(5).unwrap().parent, Some(4));
+    assert_eq!(director.scene.get_node(6).unwrap().parent, Some(4));
+
+    director.update(0.5);
+    let original_scale = director
+        .scene
+        .get_node(1)
+        .unwrap()
+        .transform
+        .scale_x
+        .current_value;
+    let copy_scale = director
+        .scene
+        .get_node(4)
+        .unwrap()
+        .transform
+        .scale_x
+        .current_value;
+    assert!((copy_scale - original_scale).abs() < 0.001);
+    assert!(
+        (copy_scale - 0.5).abs() < 0.01,
+        "Animation should be cloned"
+    );
+}
+
+/// Test symbol instancing with per-instance overrides.
+///
+/// Validates:
+/// - make_symbol() detaches the template from the scene
+/// - add_instance() applies root and positional child overrides
+/// - The template itself is left untouched
+#[test]
+fn symbol_instances_apply_overrides() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(1.0);
+
+let card = scene.add_box(#{ width: 100.0, height: 60.0, bg_color: "#333333" });
+card.add_text(#{ content: "Title", color: "#FFFFFF" });
+let card_symbol = card.make_symbol();
+
+scene.add_instance(card_symbol);
+scene.add_instance(card_symbol, #{
+    bg_color: "#FF0000",
+    width: 200.0,
+    children: [#{ content: "Second", color: "#000000" }]
+});
+
+movie
+"##;
+
+    let result = engine.eval::<director_core::scripting::MovieHandle>(script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let movie = result.unwrap();
+    let director = movie.director.lock().unwrap();
+
+    // IDs: 0=root, 1=card (symbol), 2=title, 3/4=instance 1, 5/6=instance 2
+    let root = director.scene.get_node(0).unwrap();
+    assert_eq!(
+        root.children,
+        vec![3, 5],
+        "Only instances should be attached"
+    );
+    assert_eq!(director.scene.get_node(1).unwrap().parent, None);
+
+    let bg = |id| {
+        let n = director.scene.get_node(id).unwrap();
+        let b = n.element.as_any().downcast_ref::<BoxNode>().unwrap();
+        b.bg_color.as_ref().unwrap().current_value
+    };
+    assert!(bg(1).r < 0.5, "Template should keep its colour");
+    assert!(bg(3).r < 0.5, "Plain instance should match the template");
+    assert!(bg(5).r > 0.9, "Override should recolour the instance");
+
+    let width = director
+        .scene
+        .get_node(5)
+        .unwrap()
+        .element
+        .layout_style()
+        .size
+        .width;
+    assert_eq!(width, taffy::style::Dimension::length(200.0));
+
+    let text = |id| {
+        let n = director.scene.get_node(id).unwrap();
+        let t = n.element.as_any().downcast_ref::<TextNode>().unwrap();
+        t.spans.iter().map(|s| s.text.clone()).collect::<String>()
+    };
+    assert_eq!(text(4), "Title");
+    assert_eq!(text(6), "Second");
+}
+
+/// Test that instances follow edits made to their master.
+///
+/// Validates:
+/// - Editing the master rebuilds instances in place, keeping their node IDs
+/// - Overrides are applied again on top of the edited master
+/// - Children added to the master after instancing appear in every instance
+/// - Each instance keeps its own root animation
+/// - Unchanged masters leave instances alone; edited ones replace local changes
+#[test]
+fn symbol_instances_follow_master_edits() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(1.0);
+
+let card = scene.add_box(#{ width: 100.0, height: 60.0, bg_color: "#333333" });
+let title = card.add_text(#{ content: "Title", color: "#FFFFFF" });
+let card_symbol = card.make_symbol();
+
+let plain = scene.add_instance(card_symbol);
+let red = scene.add_instance(card_symbol, #{ bg_color: "#FF0000" });
+red.animate("x", 0.0, 100.0, 1.0, "linear");
+
+card.set_style(#{ height: 80.0 });
+title.set_content("Renamed");
+card.add_box(#{ width: 10.0, height: 10.0 });
+
+movie
+"##;
+
+    let movie = engine
+        .eval::<director_core::scripting::MovieHandle>(script)
+        .expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(0.5);
+
+    // IDs: 0=root, 1=card (symbol), 2=title, 3/4=plain, 5/6=red, 7=badge, 8/9=badge copies
+    let root = director.scene.get_node(0).unwrap();
+    assert_eq!(root.children, vec![3, 5], "Instances keep their IDs");
+
+    let box_node = |id| {
+        let n = director.scene.get_node(id).unwrap();
+        n.element
+            .as_any()
+            .downcast_ref::<BoxNode>()
+            .unwrap()
+            .clone()
+    };
+    for id in [3, 5] {
+        let instance = box_node(id);
+        assert_eq!(
+            instance.style.size.height,
+            taffy::style::Dimension::length(80.0),
+            "Master edits reach instance {}",
+            id
+        );
+
+        let children = &director.scene.get_node(id).unwrap().children;
+        assert_eq!(
+            children.len(),
+            2,
+            "New master child reaches instance {}",
+            id
+        );
+        let text = director.scene.get_node(children[0]).unwrap();
+        let text = text.element.as_any().downcast_ref::<TextNode>().unwrap();
+        let content: String = text.spans.iter().map(|s| s.text.clone()).collect();
+        assert_eq!(content, "Renamed");
+    }
+    assert!(box_node(3).bg_color.unwrap().current_value.r < 0.5);
+    assert!(
+        box_node(5).bg_color.unwrap().current_value.r > 0.9,
+        "Overrides survive a master edit"
+    );
+
+    let x = director
+        .scene
+        .get_node(5)
+        .unwrap()
+        .transform
+        .translate_x
+        .current_value;
+    assert!(
+        (x - 50.0).abs() < 0.01,
+        "Instance keeps its animation, got {}",
+        x
+    );
+
+    // Local edits to an instance last until the master changes again
+    let z_index = |director: &director_core::Director| director.scene.get_node(4).unwrap().z_index;
+    director.scene.get_node_mut(4).unwrap().z_index = 7;
+    director.update(0.6);
+    assert_eq!(
+        z_index(&director),
+        7,
+        "An unchanged master doesn't rebuild instances"
+    );
+
+    director.scene.get_node_mut(2).unwrap().z_index = 1;
+    director.update(0.7);
+    assert_eq!(z_index(&director), 1, "Master edits replace local ones");
+}
+
+/// Test that drivers inside a copied subtree read from the copy.
+///
+/// Validates:
+/// - duplicate() points internal drivers at the duplicated nodes
+/// - add_instance() points internal drivers at the instance's own nodes
+/// - Rebuilding an instance after a master edit keeps them there
+#[test]
+fn copied_drivers_follow_their_own_nodes() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(500, 500, 30);
+let scene = movie.add_scene(1.0);
+
+let card = scene.add_box(#{ width: 100.0, height: 60.0 });
+let handle = card.add_box(#{ width: 10.0, height: 10.0 });
+let shadow = card.add_box(#{ width: 10.0, height: 10.0 });
+shadow.drive("x", handle, "x", #{ offset: 5.0 });
+
+let copy = card.duplicate();
+let card_symbol = card.make_symbol();
+let instance = scene.add_instance(card_symbol);
+card.set_style(#{ height: 80.0 });
+
+movie
+"##;
+
+    let movie = engine
+        .eval::<director_core::scripting::MovieHandle>(script)
+        .expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    // IDs: 0=root, 1-3=card (symbol), 4-6=copy, 7-9=instance
+    assert_eq!(director.scene.get_node(0).unwrap().children, vec![4, 7]);
+    for (handle, shadow) in [(5, 6), (8, 9)] {
+        director
+            .scene
+            .get_node_mut(handle)
+            .unwrap()
+            .transform
+            .translate_x = Animated::new(40.0);
+        director.update(0.0);
+
+        let shadow = director.scene.get_node(shadow).unwrap();
+        assert_eq!(shadow.drivers[0].inputs[0].source, handle);
+        assert_eq!(
+            shadow.transform.translate_x.current_value, 45.0,
+            "Shadow follows its own handle {}",
+            handle
+        );
+    }
+}
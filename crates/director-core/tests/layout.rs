@@ -250,3 +250,71 @@ fn grid_layout_basic() {
     assert_eq!(n2.layout_rect.width(), 500.0);
     assert_eq!(n2.layout_rect.height(), 500.0);
 }
+
+/// Test that per-frame passes only touch the active scenes.
+///
+/// Validates:
+/// - `active_nodes` holds only the visible scene's nodes
+/// - Style changes to an inactive scene wait until it becomes active
+/// - Destroyed nodes are dropped from layout without disturbing their siblings
+#[test]
+fn update_and_layout_skip_inactive_scenes() {
+    let mut d = Director::new(
+        400,
+        400,
+        30,
+        Arc::new(DefaultAssetLoader),
+        RenderMode::Preview,
+        None,
+    );
+
+    let add_scene = |d: &mut Director, start: f64| -> (NodeId, NodeId) {
+        let root = d.scene.add_node(Box::new(BoxNode::new()));
+        let mut child = BoxNode::new();
+        child.style.size = Size {
+            width: Dimension::length(50.0),
+            height: Dimension::length(50.0),
+        };
+        let child_id = d.scene.add_node(Box::new(child));
+        d.scene.add_child(root, child_id);
+        d.timeline.push(TimelineItem {
+            scene_root: root,
+            start_time: start,
+            duration: 1.0,
+            z_index: 0,
+            audio_tracks: vec![],
+        });
+        (root, child_id)
+    };
+    let (first_root, first_child) = add_scene(&mut d, 0.0);
+    let (second_root, second_child) = add_scene(&mut d, 1.0);
+
+    let mut layout_engine = director_core::systems::layout::LayoutEngine::new();
+    d.update(0.5);
+    layout_engine.compute_layout(&mut d.scene, 400, 400, 0.5);
+    assert_eq!(d.scene.active_nodes, vec![first_root, first_child]);
+    assert_eq!(d.scene.active_roots, vec![first_root]);
+    assert!(d.scene.get_node(second_child).unwrap().dirty_style);
+
+    {
+        let node = d.scene.get_node_mut(second_child).unwrap();
+        let box_node = node.element.as_any_mut().downcast_mut::<BoxNode>().unwrap();
+        box_node.style.size.width = Dimension::length(80.0);
+    }
+
+    d.update(1.5);
+    layout_engine.compute_layout(&mut d.scene, 400, 400, 1.5);
+    assert_eq!(d.scene.active_nodes, vec![second_root, second_child]);
+    let node = d.scene.get_node(second_child).unwrap();
+    assert!(!node.dirty_style);
+    assert_eq!(node.layout_rect.width(), 80.0);
+
+    let extra = d.scene.add_node(Box::new(BoxNode::new()));
+    d.scene.add_child(second_root, extra);
+    d.scene.destroy_node(second_child);
+    d.update(1.6);
+    layout_engine.compute_layout(&mut d.scene, 400, 400, 1.6);
+    assert!(d.scene.removed_nodes.is_empty());
+    assert_eq!(d.scene.active_nodes, vec![second_root, extra]);
+    assert_eq!(d.scene.get_node(extra).unwrap().layout_rect.top, 0.0);
+}
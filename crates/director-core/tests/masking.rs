@@ -1,7 +1,11 @@
 //! Masking and Blend Mode Tests
 //!
-//! Tests for alpha masking (`set_mask`) and blend mode (`set_blend_mode`) APIs.
+//! Tests for track mattes (`set_mask`) and blend mode (`set_blend_mode`) APIs.
 
+use director_core::animation::Animated;
+use director_core::node::BoxNode;
+use director_core::systems::renderer::render_frame;
+use director_core::types::Color;
 use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
 use rhai::Engine;
 use std::sync::Arc;
@@ -127,3 +131,205 @@ movie
         );
     }
 }
+
+/// Test that each matte mode reads the mask correctly when rendered.
+///
+/// Validates:
+/// - An opaque black mask reveals the owner in "alpha" but hides it in "luma"
+/// - Inverted modes reveal the owner outside the mask
+/// - Unknown modes are rejected
+#[test]
+fn matte_modes_render() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    // (mode, left half inside the mask visible, right half outside visible)
+    let cases = [
+        ("alpha", true, false),
+        ("alpha_inverted", false, true),
+        ("luma", false, false),
+        ("luma_inverted", true, true),
+    ];
+
+    for (mode, left_visible, right_visible) in cases {
+        let script = format!(
+            r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+let red = scene.add_box(#{{ width: 100.0, height: 100.0, bg_color: "#FF0000" }});
+let mask = scene.add_box(#{{ width: 50.0, height: 100.0, bg_color: "#000000" }});
+red.set_mask(mask, "{}");
+movie
+"##,
+            mode
+        );
+
+        let movie = engine
+            .eval::<director_core::scripting::MovieHandle>(&script)
+            .expect("Script failed");
+        let mut director = movie.director.lock().unwrap();
+
+        let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+        render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+
+        // Red in either RGBA or BGRA byte order
+        let is_red = |x: usize, y: usize| {
+            let idx = (y * 100 + x) * 4;
+            (pixels[idx] > 200 || pixels[idx + 2] > 200) && pixels[idx + 1] < 50
+        };
+
+        assert_eq!(is_red(25, 50), left_visible, "'{}' inside the mask", mode);
+        assert_eq!(is_red(75, 50), right_visible, "'{}' outside the mask", mode);
+    }
+
+    let invalid = engine.eval::<director_core::scripting::MovieHandle>(
+        r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+let a = scene.add_box(#{});
+a.set_mask(scene.add_box(#{}), "stencil");
+movie
+"##,
+    );
+    assert!(invalid.is_err(), "Unknown matte modes should fail");
+}
+
+/// Test luma mattes with a semi-transparent mask.
+///
+/// Validates:
+/// - A 20%-opaque white mask reveals about 20% of the owner in "luma"
+/// - "luma_inverted" keeps about 80% of the owner under the same mask
+#[test]
+fn luma_matte_weights_mask_alpha() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    for (mode, expected) in [("luma", 51), ("luma_inverted", 204)] {
+        let script = format!(
+            r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+let red = scene.add_box(#{{ width: 100.0, height: 100.0, bg_color: "#FF0000" }});
+let mask = scene.add_box(#{{ width: 100.0, height: 100.0 }});
+red.set_mask(mask, "{}");
+movie
+"##,
+            mode
+        );
+
+        let movie = engine
+            .eval::<director_core::scripting::MovieHandle>(&script)
+            .expect("Script failed");
+        let mut director = movie.director.lock().unwrap();
+
+        // IDs: 0=root, 1=red, 2=mask
+        let mask = director.scene.get_node_mut(2).unwrap();
+        mask.element
+            .as_any_mut()
+            .downcast_mut::<BoxNode>()
+            .unwrap()
+            .bg_color = Some(Animated::new(Color::new(1.0, 1.0, 1.0, 0.2)));
+
+        let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+        render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+
+        // Red over the black background, in either RGBA or BGRA byte order
+        let idx = (50 * 100 + 50) * 4;
+        let red = pixels[idx].max(pixels[idx + 2]);
+        assert!(
+            red.abs_diff(expected) < 8,
+            "'{}' should show ~{} red, got {}",
+            mode,
+            expected,
+            red
+        );
+    }
+}
+
+/// Test shape-based clip paths without a mask node.
+///
+/// Validates:
+/// - Ellipse clips hide the corners; `invert` keeps only the corners
+/// - Negative expansion shrinks a rect clip
+/// - clip_feather is animatable
+#[test]
+fn clip_path_shapes_render() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    // (clip call, center visible, corner visible, edge visible)
+    let cases = [
+        (r#"set_clip_path(#{ shape: "ellipse" })"#, true, false, true),
+        (
+            r#"set_clip_path(#{ shape: "ellipse" }, #{ invert: true })"#,
+            false,
+            true,
+            false,
+        ),
+        (
+            r#"set_clip_path("M0 0 L100 0 L100 100 L0 100 Z", #{ expansion: -20 })"#,
+            true,
+            false,
+            false,
+        ),
+    ];
+
+    for (clip, center_visible, corner_visible, edge_visible) in cases {
+        let script = format!(
+            r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+let red = scene.add_box(#{{ width: 100.0, height: 100.0, bg_color: "#FF0000" }});
+red.{};
+movie
+"##,
+            clip
+        );
+
+        let movie = engine
+            .eval::<director_core::scripting::MovieHandle>(&script)
+            .expect("Script failed");
+        let mut director = movie.director.lock().unwrap();
+
+        let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+        render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+
+        let is_red = |x: usize, y: usize| {
+            let idx = (y * 100 + x) * 4;
+            (pixels[idx] > 200 || pixels[idx + 2] > 200) && pixels[idx + 1] < 50
+        };
+
+        assert_eq!(is_red(50, 50), center_visible, "{}: center", clip);
+        assert_eq!(is_red(3, 3), corner_visible, "{}: corner", clip);
+        assert_eq!(is_red(10, 50), edge_visible, "{}: edge", clip);
+    }
+
+    let movie = engine
+        .eval::<director_core::scripting::MovieHandle>(
+            r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(2.0);
+let a = scene.add_box(#{ width: 100.0, height: 100.0 });
+a.set_clip_path(#{ shape: "rect", radius: 10 });
+a.animate("clip_feather", 0.0, 20.0, 2.0, "linear");
+movie
+"##,
+        )
+        .expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    director.update(1.0);
+    let a = director.scene.get_node(1).unwrap();
+    assert!((a.get_property("clip_feather").unwrap() - 10.0).abs() < 0.01);
+}
@@ -0,0 +1,176 @@
+//! Particle Tests
+//!
+//! Tests for `ParticleNode` emission, determinism and the `add_particles` API.
+
+use director_core::node::LifeCurve;
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::types::Color;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+const SCRIPT: &str = r##"
+let movie = new_director(100, 100, 30, #{ seed: SEED });
+let scene = movie.add_scene(4.0);
+scene.add_particles(#{
+    width: 100.0,
+    height: 100.0,
+    emitter: #{ type: "rect", x: 20.0, y: 60.0, width: 60.0, height: 20.0 },
+    rate: 30.0,
+    bursts: [[0.5, 40]],
+    lifetime: [0.5, 1.5],
+    speed: [20.0, 60.0],
+    spread: 180.0,
+    gravity: 40.0,
+    drag: 0.5,
+    turbulence: 15.0,
+    spin: [-90.0, 90.0],
+    sprite: "square",
+    size: [4.0, 8.0],
+    color: ["#FF0000", "#0000FF"],
+    alpha: [1.0, 0.2],
+});
+movie
+"##;
+
+fn movie(seed: i64) -> MovieHandle {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+    engine
+        .eval::<MovieHandle>(&SCRIPT.replace("SEED", &seed.to_string()))
+        .expect("Script failed")
+}
+
+/// Renders each time in order and returns the pixels of every frame.
+fn render(movie: &MovieHandle, times: &[f64]) -> Vec<Vec<u8>> {
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    times
+        .iter()
+        .map(|&time| {
+            render_frame(&mut director, time, surface.canvas()).unwrap();
+            let mut pixels = vec![0u8; 100 * 100 * 4];
+            assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+            pixels
+        })
+        .collect()
+}
+
+/// Counts the drawn pixels: those that aren't the black the frame is cleared to.
+fn coverage(pixels: &[u8]) -> usize {
+    pixels
+        .chunks(4)
+        .filter(|px| px[..3].iter().any(|&c| c > 0))
+        .count()
+}
+
+/// Test that particles only depend on the seed and the frame time.
+///
+/// Validates:
+/// - A frame renders identically whether it is rendered first, last or repeatedly
+/// - Separate movies with the same seed render identical frames
+/// - A different seed produces different particles
+#[test]
+fn particles_are_deterministic() {
+    let a = movie(42);
+    let forward = render(&a, &[0.0, 0.5, 1.0, 1.5, 2.0]);
+    let backward = render(&a, &[2.0, 1.5, 1.0, 0.5, 0.0]);
+    for (i, frame) in forward.iter().enumerate() {
+        assert_eq!(
+            frame,
+            &backward[4 - i],
+            "Frame {} depends on render order",
+            i
+        );
+    }
+    assert!(coverage(&forward[3]) > 0, "Particles are drawn");
+
+    let b = movie(42);
+    assert_eq!(render(&b, &[1.5])[0], forward[3], "Same seed, same frame");
+
+    let c = movie(7);
+    assert_ne!(render(&c, &[1.5])[0], forward[3], "Different seed");
+}
+
+/// Test emission timing and over-life curves.
+///
+/// Validates:
+/// - Bursts emit at their time and disappear after their lifetime
+/// - `duration` stops the steady stream
+/// - Life curves interpolate between evenly spaced keys
+/// - Invalid emitters, sprites and bursts are script errors
+#[test]
+fn particle_emission_and_curves() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30, #{ seed: 3 });
+let scene = movie.add_scene(4.0);
+scene.add_particles(#{
+    width: 100.0,
+    height: 100.0,
+    rate: 0.0,
+    bursts: [[1.0, 30]],
+    lifetime: 0.5,
+    speed: [0.0, 40.0],
+    spread: 360.0,
+    size: 6.0,
+    alpha: 1.0,
+});
+scene.add_particles(#{
+    width: 100.0,
+    height: 100.0,
+    position: "absolute",
+    emitter: #{ type: "line", from: [0.0, 90.0], to: [100.0, 90.0] },
+    rate: 20.0,
+    duration: 2.0,
+    lifetime: 0.25,
+    speed: 0.0,
+    sprite: #{ path: "M0 0 L10 0 L5 10 Z" },
+    size: 6.0,
+    alpha: 1.0,
+});
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let frames = render(&movie, &[0.9, 1.2, 1.6, 3.0]);
+    let top = |pixels: &[u8]| coverage(&pixels[..80 * 100 * 4]);
+    let bottom = |pixels: &[u8]| coverage(&pixels[80 * 100 * 4..]);
+
+    assert_eq!(top(&frames[0]), 0, "Burst has not fired yet");
+    assert!(top(&frames[1]) > 0, "Burst is visible");
+    assert_eq!(top(&frames[2]), 0, "Burst particles have died");
+    assert!(bottom(&frames[1]) > 0, "Stream is emitting");
+    assert_eq!(bottom(&frames[3]), 0, "Stream stopped after its duration");
+
+    let curve = LifeCurve::even(vec![0.0, 10.0, 0.0]);
+    assert_eq!(curve.sample(0.25), 5.0);
+    assert_eq!(curve.sample(0.5), 10.0);
+    assert_eq!(curve.sample(2.0), 0.0);
+    let colors = LifeCurve::even(vec![Color::BLACK, Color::WHITE]);
+    assert_eq!(colors.sample(1.0), Color::WHITE);
+
+    for props in [
+        r#"emitter: "spiral""#,
+        r#"emitter: #{ type: "line", from: [0.0, 0.0] }"#,
+        r#"sprite: "star""#,
+        r#"sprite: #{ path: "Q" }"#,
+        r#"bursts: [[1.0]]"#,
+        r#"color: "nope""#,
+    ] {
+        let script = format!(
+            "let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_particles(#{{ {} }});",
+            props
+        );
+        assert!(engine.run(&script).is_err(), "{} should fail", props);
+    }
+}
@@ -0,0 +1,75 @@
+//! Profiling Tests
+//!
+//! Tests for per-node span timings collected by `Profiler`.
+
+use director_core::scripting::MovieHandle;
+use director_core::systems::profiler::Profiler;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+use tracing_subscriber::prelude::*;
+
+/// Test that rendering produces a per-node, per-frame report.
+///
+/// Validates:
+/// - Each `render_frame` call is recorded as a frame
+/// - `render_node` and `update_node` timings are attributed to node IDs
+/// - Effect filter construction nests under the node that draws it
+/// - JSON and folded outputs are produced
+#[test]
+fn profiler_reports_nodes_and_frames() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+let card = scene.add_box(#{ width: 50.0, height: 50.0, bg_color: "#3366FF" });
+card.apply_effect("blur", 4.0);
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    let profiler = Profiler::new();
+    let subscriber = tracing_subscriber::registry().with(profiler.layer());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+        for frame in 0..2 {
+            render_frame(&mut director, frame as f64 * 0.1, surface.canvas()).unwrap();
+        }
+    });
+
+    let report = profiler.report();
+    assert_eq!(report.frames.len(), 2);
+    assert_eq!(report.frames[1].time, Some(0.1));
+
+    let root = report
+        .nodes
+        .iter()
+        .find(|n| n.span == "render_node" && n.node == Some(0))
+        .expect("Root render timing");
+    assert_eq!(root.calls, 2);
+    assert!(root.total_ms >= root.self_ms);
+    assert!(report
+        .nodes
+        .iter()
+        .any(|n| n.span == "update_node" && n.node == Some(1)));
+    assert!(!report.slowest_nodes(3).is_empty());
+
+    assert!(
+        report
+            .stacks
+            .iter()
+            .any(|s| s.stack.starts_with("render_node#0;")
+                && s.stack.ends_with(";build_effect_filter")),
+        "Effect filters should nest under render spans: {:?}",
+        report.stacks
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["frames"].as_array().unwrap().len(), 2);
+    assert!(report.summary(5).contains("Slowest nodes"));
+}
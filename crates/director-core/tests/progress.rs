@@ -0,0 +1,143 @@
+//! Progress Tests
+//!
+//! Tests for `ProgressNode` following scene, movie and audio clocks, and the
+//! `add_progress` API.
+
+use director_core::node::ProgressNode;
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+/// Progress node `id` at `time`.
+fn progress_at(movie: &MovieHandle, id: usize, time: f64) -> ProgressNode {
+    let mut director = movie.director.lock().unwrap();
+    director.update(time);
+    let node = director.scene.get_node(id).unwrap();
+    node.element
+        .as_any()
+        .downcast_ref::<ProgressNode>()
+        .unwrap()
+        .clone()
+}
+
+/// Test the clocks a bar can follow.
+///
+/// Validates:
+/// - Scene bars fill over their own scene, from its start
+/// - Movie bars fill over the whole timeline
+/// - `start` and `end` narrow the part of the clock the bar spans
+/// - Audio bars follow their track's playback, wrapping round when it loops
+#[test]
+fn progress_follows_clocks() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let intro = movie.add_scene(2.0);
+let main = movie.add_scene(4.0);
+let track = movie.add_audio("missing.wav");
+main.add_progress(#{});
+main.add_progress(#{ source: "movie" });
+main.add_progress(#{ source: "movie", start: 2.0, end: 4.0 });
+main.add_progress(#{ track: track });
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    {
+        let mut director = movie.director.lock().unwrap();
+        let sample_rate = director.audio_mixer.sample_rate as usize;
+        let track = director.audio_mixer.get_track_mut(0).unwrap();
+        // Two seconds of silent stereo
+        track.samples = vec![0.0; sample_rate * 2 * 2];
+        track.loop_audio = true;
+    }
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+
+    // IDs: 0=intro root, 1=main root, 2=scene, 3=movie, 4=movie 2s - 4s, 5=audio
+    let scene = progress_at(&movie, 2, 3.0);
+    assert_eq!((scene.time, scene.duration), (1.0, 4.0));
+    assert!(close(scene.progress(), 0.25));
+    assert!(close(progress_at(&movie, 2, 5.9).progress(), 0.975));
+
+    assert!(close(progress_at(&movie, 3, 3.0).progress(), 0.5));
+    assert!(close(progress_at(&movie, 4, 3.0).progress(), 0.5));
+    assert_eq!(progress_at(&movie, 4, 5.0).progress(), 1.0);
+
+    let audio = progress_at(&movie, 5, 2.5);
+    assert_eq!(audio.duration, 2.0);
+    assert!(close(audio.progress(), 0.25), "Looped back to the start");
+}
+
+/// Renders a 100x20 frame at `time` and returns its RGBA pixels.
+fn render(movie: &MovieHandle, time: f64) -> Vec<u8> {
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 20)).unwrap();
+    render_frame(&mut director, time, surface.canvas()).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 20),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut pixels = vec![0u8; 100 * 20 * 4];
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+    pixels
+}
+
+/// Test chapter segments, drawing and script errors.
+///
+/// Validates:
+/// - Markers split the bar into segments, ignoring markers outside it
+/// - The fill covers the bar up to the current time, with gaps between segments
+/// - Unknown sources, audio without a track, tracks of other movies, empty ranges, bad
+///   markers and bad knobs are script errors
+#[test]
+fn progress_segments_and_errors() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 20, 30);
+let scene = movie.add_scene(4.0);
+scene.add_progress(#{
+    width: 100.0,
+    height: 20.0,
+    markers: [2.0, 1.0, 9.0],
+    gap: 10.0,
+    radius: 0.0,
+    background: "#0000FF",
+    fill: "#FF0000",
+});
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let progress = progress_at(&movie, 1, 2.0);
+    assert_eq!(
+        progress.segments(),
+        vec![(0.0, 0.25), (0.25, 0.5), (0.5, 1.0)]
+    );
+
+    let pixels = render(&movie, 2.0);
+    let at = |x: usize| &pixels[(10 * 100 + x) * 4..][..4];
+    assert_eq!(at(10), [255, 0, 0, 255], "Filled first chapter");
+    assert_eq!(at(40), [255, 0, 0, 255], "Filled second chapter");
+    assert_eq!(at(50), [0, 0, 0, 255], "Gap between chapters shows the cleared frame");
+    assert_eq!(at(75), [0, 0, 255, 255], "Unfilled last chapter");
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ source: "wallclock" });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ source: "audio" });"#,
+        r#"let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); scene.add_progress(#{ source: "movie", track: scene.add_audio("a.wav") });"#,
+        r#"let other = new_director(100, 100, 30); let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ track: other.add_audio("a.wav") });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ start: 2.0, end: 1.0 });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ markers: ["soon"] });"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_progress(#{ knob: "big" });"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
@@ -0,0 +1,143 @@
+//! Render Cache Tests
+//!
+//! Tests for retained-mode caching of static subtrees between frames.
+
+use director_core::animation::Animated;
+use director_core::node::BoxNode;
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::types::Color;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader, Director};
+use rhai::Engine;
+use std::sync::Arc;
+
+fn render_pixels(director: &mut Director, time: f64) -> Vec<u8> {
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(director, time, surface.canvas()).unwrap();
+
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    let info = skia_safe::ImageInfo::new_n32_premul((100, 100), None);
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+    pixels
+}
+
+fn recorded(director: &Director, id: usize) -> bool {
+    director
+        .scene
+        .get_node(id)
+        .unwrap()
+        .render_cache
+        .is_recorded()
+}
+
+/// Test that static subtrees are recorded, replayed and invalidated.
+///
+/// Validates:
+/// - A static box with text is recorded once its key repeats
+/// - A moving node keeps its own recording; only its parent re-renders
+/// - Replayed frames match frames rendered with caching disabled
+/// - Marking a node dirty drops its recording and shows the new content
+#[test]
+fn static_subtrees_are_cached_and_invalidated() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 10);
+let scene = movie.add_scene(2.0);
+
+let card = scene.add_box(#{ width: 100.0, height: 50.0, bg_color: "#FF0000" });
+card.add_text(#{ content: "Cached", size: 12.0 });
+
+let mover = scene.add_box(#{ width: 20.0, height: 20.0, bg_color: "#00FF00" });
+mover.animate("x", 0.0, 40.0, 2.0, "linear");
+
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    for frame in 0..3 {
+        render_pixels(&mut director, frame as f64 * 0.1);
+    }
+
+    // IDs: 0=root, 1=card, 2=text, 3=mover
+    assert!(recorded(&director, 1), "Static card should be recorded");
+    assert!(recorded(&director, 2), "Static text should be recorded");
+    assert!(
+        recorded(&director, 3),
+        "Moving box content should be recorded"
+    );
+    assert!(!recorded(&director, 0), "Root changes every frame");
+
+    let cached = render_pixels(&mut director, 0.3);
+    director.render_caching = false;
+    let direct = render_pixels(&mut director, 0.3);
+    assert!(
+        !recorded(&director, 1),
+        "Disabling caching drops recordings"
+    );
+    assert_eq!(cached, direct, "Cached frame should match direct rendering");
+
+    director.render_caching = true;
+    render_pixels(&mut director, 0.4);
+    render_pixels(&mut director, 0.5);
+    assert!(recorded(&director, 1));
+
+    {
+        let card = director.scene.get_node_mut(1).unwrap();
+        let box_node = card.element.as_any_mut().downcast_mut::<BoxNode>().unwrap();
+        box_node.bg_color = Some(Animated::new(Color::new(0.0, 0.0, 1.0, 1.0)));
+        card.dirty_style = true;
+    }
+    let pixels = render_pixels(&mut director, 0.6);
+    assert!(!recorded(&director, 1), "Dirty card must re-render");
+
+    // The card spans the full width; sample its left edge, away from the text
+    let card_top = director.scene.get_node(1).unwrap().layout_rect.top as usize;
+    let idx = ((card_top + 5) * 100 + 2) * 4;
+    let (b, g, r) = (pixels[idx], pixels[idx + 1], pixels[idx + 2]);
+    assert!(
+        b > 200 && r < 50 && g < 50,
+        "Card should now be blue, got {:?}",
+        (r, g, b)
+    );
+}
+
+/// Test that drawing-only changes invalidate recordings without re-syncing layout.
+///
+/// Validates:
+/// - A progress bar whose clock moves bumps its content version every frame
+/// - Its layout style stays clean, so layout isn't re-synced for it
+/// - The bar is never replayed from a stale recording
+#[test]
+fn content_changes_skip_layout_sync() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 10);
+let scene = movie.add_scene(2.0);
+scene.add_progress(#{ width: 100.0, height: 10.0 });
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    render_pixels(&mut director, 0.0);
+
+    // IDs: 0=root, 1=progress
+    for frame in 1..4 {
+        let version = director.scene.get_node(1).unwrap().content_version;
+        director.update(frame as f64 * 0.1);
+        let bar = director.scene.get_node(1).unwrap();
+        assert!(!bar.dirty_style, "Progress must not dirty its layout style");
+        assert!(
+            bar.content_version > version,
+            "Progress must invalidate its recording"
+        );
+        render_pixels(&mut director, frame as f64 * 0.1);
+    }
+    assert!(!recorded(&director, 1), "A moving bar is never replayed");
+}
@@ -0,0 +1,367 @@
+//! Shape Tests
+//!
+//! Tests for `ShapeNode` geometry, fills, strokes, trim paths and the `add_shape` API.
+
+use director_core::animation::Animated;
+use director_core::node::{morph_paths, trim_path, ShapeGeometry, ShapeNode, VectorNode};
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::types::Color;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader, Element};
+use rhai::Engine;
+use skia_safe::{ContourMeasureIter, Path, Rect};
+use std::sync::Arc;
+
+/// Test that shapes are filled and stroked.
+///
+/// Validates:
+/// - The fill covers the inside of an ellipse
+/// - The stroke is centred on the outline
+/// - Pixels outside the ellipse are left as the cleared (black) frame
+#[test]
+fn shape_renders_fill_and_stroke() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(1.0);
+scene.add_shape(#{
+    shape: "circle",
+    width: 100.0,
+    height: 100.0,
+    fill: "#FF0000",
+    stroke: "#0000FF",
+    stroke_width: 10.0,
+});
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(&mut director, 0.0, surface.canvas()).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+    let px = |x: usize, y: usize| {
+        let i = (y * 100 + x) * 4;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    };
+
+    assert_eq!(px(50, 50), [255, 0, 0, 255], "Fill inside the ellipse");
+    assert_eq!(px(50, 2), [0, 0, 255, 255], "Stroke on the outline");
+    assert_eq!(
+        px(2, 2),
+        [0, 0, 0, 255],
+        "Corners outside the ellipse show the cleared frame"
+    );
+}
+
+/// Test shape geometry, sizing and animatable parameters.
+///
+/// Validates:
+/// - Stars have two corners per tip and polygons one per side
+/// - Path shapes without a size are measured from their path
+/// - `points` can be animated through `animate`
+/// - Unknown shapes and invalid path data are script errors
+#[test]
+fn shape_geometry_and_parameters() {
+    let bounds = Rect::from_wh(100.0, 100.0);
+    let mut star = ShapeNode::new(ShapeGeometry::Star);
+    assert_eq!(star.outline(bounds).count_points(), 10);
+    star.geometry = ShapeGeometry::Polygon;
+    star.points.current_value = 6.0;
+    assert_eq!(star.outline(bounds).count_points(), 6);
+
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(200, 200, 30);
+let scene = movie.add_scene(2.0);
+let arrow = scene.add_shape(#{
+    shape: "path",
+    d: "M0 20 L60 20 L60 0 L100 40 L60 80 L60 60 L0 60 Z",
+    fill: "#3366FF",
+});
+let badge = scene.add_shape(#{ shape: "star", width: 50.0, height: 50.0, fill: ["#FFE259", "#FFA751"] });
+badge.animate("points", 5.0, 8.0, 1.0, "linear");
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+
+    let mut surface = skia_safe::surfaces::raster_n32_premul((200, 200)).unwrap();
+    render_frame(&mut director, 1.0, surface.canvas()).unwrap();
+
+    // IDs: 0=scene root, 1=arrow, 2=badge
+    let arrow = director.scene.get_node(1).unwrap();
+    assert_eq!(arrow.layout_rect.width(), 100.0);
+    assert_eq!(arrow.layout_rect.height(), 80.0);
+    let badge = director.scene.get_node(2).unwrap();
+    assert_eq!(badge.element.get_property("points"), Some(8.0));
+
+    for props in [r#"#{ shape: "hexagon" }"#, r#"#{ shape: "path", d: "Q" }"#] {
+        let script = format!(
+            "let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_shape({});",
+            props
+        );
+        assert!(engine.run(&script).is_err(), "{} should fail", props);
+    }
+}
+
+/// Test trimming strokes of shapes and SVGs.
+///
+/// Validates:
+/// - `trim_path` keeps the requested fraction of the path length
+/// - Offsets wrap the trimmed range past the end of the path
+/// - Trimmed shape and SVG strokes only draw the visible part
+/// - An untrimmed SVG with a clip path matches resvg, even while other properties animate
+/// - An SVG the trim renderer can't draw exactly keeps that renderer when the trim
+///   animation ends, so the last frames match
+#[test]
+fn trim_paths_cut_strokes() {
+    let length = |path: &Path| -> f32 {
+        ContourMeasureIter::new(path, false, None)
+            .map(|c| c.length())
+            .sum()
+    };
+    let mut line = Path::new();
+    line.move_to((0.0, 50.0)).line_to((100.0, 50.0));
+
+    assert_eq!(length(&trim_path(&line, 0.0, 1.0, 0.0)), 100.0);
+    assert!((length(&trim_path(&line, 0.25, 0.75, 0.0)) - 50.0).abs() < 0.01);
+    assert_eq!(trim_path(&line, 0.5, 0.5, 0.0).count_points(), 0);
+    let wrapped = trim_path(&line, 0.0, 0.5, 0.75);
+    assert!((length(&wrapped) - 50.0).abs() < 0.01);
+    assert_eq!(ContourMeasureIter::new(&wrapped, false, None).count(), 2);
+
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let draw = |element: &dyn Element| {
+        let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+        element
+            .render(
+                surface.canvas(),
+                Rect::from_wh(100.0, 100.0),
+                1.0,
+                &mut |_: &skia_safe::Canvas| {},
+            )
+            .unwrap();
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+        move |x: usize, y: usize| pixels[(y * 100 + x) * 4 + 3]
+    };
+
+    let mut shape = ShapeNode::new(ShapeGeometry::Path(line));
+    shape.stroke_color = Some(Animated::new(Color::new(1.0, 1.0, 1.0, 1.0)));
+    shape.stroke_width = Animated::new(10.0);
+    shape.set_property("trim_end", 0.5);
+    let alpha = draw(&shape);
+    assert_eq!(alpha(25, 50), 255, "Start of the line is drawn");
+    assert_eq!(alpha(75, 50), 0, "End of the line is trimmed");
+
+    let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <path d="M0 50 H100" stroke="#FFFFFF" stroke-width="10"/>
+    </svg>"##;
+    let mut vector = VectorNode::new(svg);
+    vector.set_property("trim_start", 0.5);
+    let alpha = draw(&vector);
+    assert_eq!(alpha(25, 50), 0, "Start of the SVG stroke is trimmed");
+    assert_eq!(alpha(75, 50), 255, "End of the SVG stroke is drawn");
+
+    let clipped = br##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <clipPath id="left"><rect width="50" height="100"/></clipPath>
+        <path d="M0 50 H100" stroke="#FFFFFF" stroke-width="10" clip-path="url(#left)"/>
+    </svg>"##;
+    let mut expected = tiny_skia::Pixmap::new(100, 100).unwrap();
+    let tree = usvg::Tree::from_data(clipped, &usvg::Options::default()).unwrap();
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::default(),
+        &mut expected.as_mut(),
+    );
+    let resvg_alpha = |x: u32, y: u32| expected.pixel(x, y).unwrap().alpha();
+    let mut vector = VectorNode::new(clipped);
+    vector.animate_property("opacity", 1.0, 1.0, 1.0, "linear");
+    vector.update(0.5);
+    let alpha = draw(&vector);
+    for (x, y) in [(25, 50), (75, 50)] {
+        assert_eq!(
+            alpha(x, y),
+            resvg_alpha(x as u32, y as u32),
+            "An untrimmed SVG is drawn by resvg, clip paths included"
+        );
+    }
+    assert_eq!(alpha(75, 50), 0, "The clip path hides the right half");
+
+    let mut vector = VectorNode::new(clipped);
+    vector.animate_property("trim_end", 0.0, 1.0, 1.0, "linear");
+    vector.update(0.99);
+    let almost = draw(&vector)(75, 50);
+    vector.update(2.0);
+    assert_eq!(
+        draw(&vector)(75, 50),
+        almost,
+        "The renderer doesn't change when the trim ends"
+    );
+}
+
+/// Test morphing shapes between outlines.
+///
+/// Validates:
+/// - Morphs blend resampled points between both paths
+/// - Opposite windings are aligned instead of collapsing through the centre
+/// - `animate_path` chains morphs and ends exactly on the target outline
+/// - Non-shapes and invalid path data are script errors
+#[test]
+fn shape_morphs_between_paths() {
+    let square = |x: f32, ccw: bool| {
+        let mut p = Path::new();
+        if ccw {
+            p.move_to((x, 0.0))
+                .line_to((x, 100.0))
+                .line_to((x + 100.0, 100.0))
+                .line_to((x + 100.0, 0.0));
+        } else {
+            p.move_to((x, 0.0))
+                .line_to((x + 100.0, 0.0))
+                .line_to((x + 100.0, 100.0))
+                .line_to((x, 100.0));
+        }
+        p.close();
+        p
+    };
+
+    let halfway = morph_paths(&square(0.0, false), &square(100.0, false), 0.5);
+    let bounds = halfway.compute_tight_bounds();
+    assert!((bounds.left - 50.0).abs() < 0.5 && (bounds.right - 150.0).abs() < 0.5);
+
+    let flipped = morph_paths(&square(0.0, false), &square(0.0, true), 0.5);
+    assert!(flipped.compute_tight_bounds().width() > 99.0);
+
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(3.0);
+let icon = scene.add_shape(#{ width: 100.0, height: 100.0, fill: "#FFFFFF" });
+icon.animate_path("M50 0 L100 100 L0 100 Z", 1.0, "ease_in_out");
+icon.animate_path("M0 0 L50 0 L50 50 L0 50 Z", 1.0, "back_out");
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    // The white icon is drawn over the black the frame is cleared to
+    let mut red_at = |time: f64, x: usize, y: usize| {
+        render_frame(&mut director, time, surface.canvas()).unwrap();
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+        pixels[(y * 100 + x) * 4]
+    };
+
+    assert_eq!(red_at(0.0, 5, 5), 255, "Starts as the full rectangle");
+    assert_eq!(red_at(1.0, 5, 5), 0, "Triangle corner is empty");
+    assert_eq!(red_at(1.0, 50, 90), 255, "Triangle body is filled");
+    assert_eq!(red_at(2.5, 25, 25), 255, "Ends inside the small square");
+    assert_eq!(red_at(2.5, 75, 75), 0, "Ends outside the small square");
+    drop(director);
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_box(#{}).animate_path("M0 0 L10 10", 1.0, "linear");"#,
+        r#"let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_shape(#{}).animate_path("Q", 1.0, "linear");"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
+
+/// Test boolean path operations between nodes.
+///
+/// Validates:
+/// - `path_difference` cuts the second operand out of the first
+/// - Operands are only drawn as part of the combined outline
+/// - The result follows animated operands every frame
+/// - Fewer than two operands and nested operands are script errors
+#[test]
+fn path_booleans_combine_operands() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(2.0);
+let plate = scene.add_shape(#{ width: 100.0, height: 100.0, position: "absolute", fill: "#00FF00" });
+let hole = scene.add_shape(#{ shape: "circle", width: 40.0, height: 40.0, position: "absolute", fill: "#00FF00" });
+hole.animate("x", 0.0, 60.0, 1.0, "linear");
+path_difference(plate, hole, #{ fill: "#FF0000" });
+movie
+"##;
+
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut render = |time: f64| {
+        render_frame(&mut director, time, surface.canvas()).unwrap();
+        let mut pixels = vec![0u8; 100 * 100 * 4];
+        assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+        move |x: usize, y: usize| {
+            let i = (y * 100 + x) * 4;
+            [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+        }
+    };
+
+    let px = render(0.0);
+    assert_eq!(px(20, 20), [0, 0, 0, 255], "Hole is cut out");
+    assert_eq!(
+        px(80, 20),
+        [255, 0, 0, 255],
+        "Rest of the plate uses the result fill"
+    );
+    let px = render(1.0);
+    assert_eq!(px(20, 20), [255, 0, 0, 255], "Hole has moved away");
+    assert_eq!(
+        px(80, 20),
+        [0, 0, 0, 255],
+        "Hole follows the animated operand"
+    );
+    drop(director);
+
+    for ops in [
+        "path_union([a])",
+        "path_xor(a, a)",
+        "path_intersect(a, a.add_shape(#{}))",
+    ] {
+        let script = format!(
+            "let movie = new_director(100, 100, 30); let a = movie.add_scene(1.0).add_shape(#{{}}); {};",
+            ops
+        );
+        assert!(engine.run(&script).is_err(), "{} should fail", ops);
+    }
+}
@@ -0,0 +1,232 @@
+//! Visualizer Tests
+//!
+//! Tests for `WaveformNode` and `SpectrumNode` reading mixer tracks, and the
+//! `add_waveform` / `add_spectrum` API.
+
+use director_core::node::{SpectrumNode, WaveformNode};
+use director_core::scripting::MovieHandle;
+use director_core::systems::renderer::render_frame;
+use director_core::{scripting::register_rhai_api, DefaultAssetLoader};
+use rhai::Engine;
+use std::sync::Arc;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+/// Interleaved stereo sine of `frequency` Hz, `seconds` long.
+fn tone(seconds: f32, amplitude: f32, frequency: f32) -> Vec<f32> {
+    let frames = (seconds * SAMPLE_RATE) as usize;
+    (0..frames)
+        .flat_map(|i| {
+            let phase = std::f32::consts::TAU * frequency * i as f32 / SAMPLE_RATE;
+            let v = phase.sin() * amplitude;
+            [v, v]
+        })
+        .collect()
+}
+
+/// Runs `script`, then replaces the samples of the first audio track (loaded from a
+/// missing file, so empty) with `samples`.
+fn movie_with_track(engine: &Engine, script: &str, samples: Vec<f32>) -> MovieHandle {
+    let movie = engine.eval::<MovieHandle>(script).expect("Script failed");
+    movie
+        .director
+        .lock()
+        .unwrap()
+        .audio_mixer
+        .get_track_mut(0)
+        .unwrap()
+        .samples = samples;
+    movie
+}
+
+/// Levels of spectrum node `id` at `time`.
+fn spectrum_at(movie: &MovieHandle, id: usize, time: f64) -> Vec<f32> {
+    let mut director = movie.director.lock().unwrap();
+    director.update(time);
+    let node = director.scene.get_node(id).unwrap();
+    let spectrum = node
+        .element
+        .as_any()
+        .downcast_ref::<SpectrumNode>()
+        .unwrap();
+    spectrum.levels.clone()
+}
+
+/// Levels and playhead of waveform node `id` at `time`.
+fn waveform_at(movie: &MovieHandle, id: usize, time: f64) -> (Vec<f32>, f32) {
+    let mut director = movie.director.lock().unwrap();
+    director.update(time);
+    let node = director.scene.get_node(id).unwrap();
+    let waveform = node
+        .element
+        .as_any()
+        .downcast_ref::<WaveformNode>()
+        .unwrap();
+    (waveform.levels.clone(), waveform.progress)
+}
+
+/// Test spectrum levels of a tone followed by silence.
+///
+/// Validates:
+/// - The band holding the tone's frequency is the loudest
+/// - Silence empties every band
+/// - Smoothing blends in the preceding frames, so levels fall off after the tone ends
+#[test]
+fn spectrum_follows_track() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(3.0);
+let track = scene.add_audio("missing.wav");
+scene.add_spectrum(track, #{ bands: 8, smoothing: 0.0 });
+scene.add_spectrum(track, #{ bands: 8, smoothing: 0.5 });
+movie
+"##;
+
+    let mut samples = tone(1.0, 0.8, 1500.0);
+    samples.resize(samples.len() * 2, 0.0);
+    let movie = movie_with_track(&engine, script, samples);
+
+    // IDs: 0=root, 1=instant, 2=smoothed
+    let levels = spectrum_at(&movie, 1, 0.5);
+    assert_eq!(levels.len(), 8);
+    // Bands are log-spaced over 40 - 16000 Hz, so 1500 Hz falls in band 4 (800 - 1692 Hz)
+    let loudest = (0..8).max_by(|&a, &b| levels[a].total_cmp(&levels[b]));
+    assert_eq!(loudest, Some(4));
+    assert!(levels[4] > 0.9, "Tone fills its band: {:?}", levels);
+    assert!(levels[0] < 0.1, "Low band stays empty: {:?}", levels);
+
+    assert!(spectrum_at(&movie, 1, 1.5).iter().all(|&l| l == 0.0));
+    assert!(spectrum_at(&movie, 1, 1.02).iter().all(|&l| l == 0.0));
+    let smoothed = spectrum_at(&movie, 2, 1.02);
+    assert!(
+        smoothed[4] > 0.0 && smoothed[4] < 0.9,
+        "Smoothing decays after the tone: {:?}",
+        smoothed
+    );
+}
+
+/// Test overview and scrolling waveforms of a loud then quiet track.
+///
+/// Validates:
+/// - Overview bars cover the whole track and the playhead follows the time
+/// - Scrolling bars cover a window centred on the current time, silent before the start
+/// - Gain scales the levels
+#[test]
+fn waveform_overview_and_scrolling() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    let script = r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(3.0);
+let track = scene.add_audio("missing.wav");
+scene.add_waveform(track, #{ bars: 10 });
+scene.add_waveform(track, #{ mode: "scrolling", bars: 10, window: 1.0 });
+scene.add_waveform(track, #{ bars: 10, gain: 2.0 });
+movie
+"##;
+
+    let mut samples = tone(1.0, 0.8, 1500.0);
+    samples.extend(tone(1.0, 0.2, 1500.0));
+    let movie = movie_with_track(&engine, script, samples);
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+
+    // IDs: 0=root, 1=overview, 2=scrolling, 3=overview with gain
+    let (levels, progress) = waveform_at(&movie, 1, 1.0);
+    assert!(levels[..5].iter().all(|&l| close(l, 0.8)), "{:?}", levels);
+    assert!(levels[5..].iter().all(|&l| close(l, 0.2)), "{:?}", levels);
+    assert!(close(progress, 0.5));
+    assert!(close(waveform_at(&movie, 1, 0.5).1, 0.25));
+
+    let (levels, progress) = waveform_at(&movie, 2, 1.0);
+    assert!(levels[..5].iter().all(|&l| close(l, 0.8)), "{:?}", levels);
+    assert!(levels[5..].iter().all(|&l| close(l, 0.2)), "{:?}", levels);
+    assert_eq!(progress, 0.5);
+    let (levels, _) = waveform_at(&movie, 2, 0.0);
+    assert!(levels[..5].iter().all(|&l| l == 0.0), "{:?}", levels);
+    assert!(levels[5..].iter().all(|&l| close(l, 0.8)), "{:?}", levels);
+
+    let (levels, _) = waveform_at(&movie, 3, 1.0);
+    assert!(
+        close(levels[0], 1.0) && close(levels[9], 0.4),
+        "{:?}",
+        levels
+    );
+}
+
+/// Renders `time` and returns which pixels are drawn (not the black the frame is cleared
+/// to), row by row.
+fn drawn_pixels(movie: &MovieHandle, time: f64) -> Vec<bool> {
+    let mut director = movie.director.lock().unwrap();
+    let mut surface = skia_safe::surfaces::raster_n32_premul((100, 100)).unwrap();
+    render_frame(&mut director, time, surface.canvas()).unwrap();
+    let info = skia_safe::ImageInfo::new(
+        (100, 100),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Premul,
+        Some(skia_safe::ColorSpace::new_srgb()),
+    );
+    let mut pixels = vec![0u8; 100 * 100 * 4];
+    assert!(surface.read_pixels(&info, &mut pixels, 100 * 4, (0, 0)));
+    pixels
+        .chunks(4)
+        .map(|px| px[..3].iter().any(|&c| c > 0))
+        .collect()
+}
+
+/// Test drawing spectrum styles and script errors.
+///
+/// Validates:
+/// - Bars rise only where the spectrum has energy
+/// - Circle and line styles draw
+/// - Unknown modes and types, bad colours and ranges, and tracks of other movies are
+///   script errors
+#[test]
+fn spectrum_styles_and_errors() {
+    let mut engine = Engine::new();
+    register_rhai_api(&mut engine, Arc::new(DefaultAssetLoader));
+
+    for kind in ["bars", "circle", "line"] {
+        let script = format!(
+            r##"
+let movie = new_director(100, 100, 30);
+let scene = movie.add_scene(3.0);
+let track = scene.add_audio("missing.wav");
+scene.add_spectrum(track, #{{
+    type: "{}",
+    width: 100.0,
+    height: 100.0,
+    bands: 4,
+    min_frequency: 250,
+    max_frequency: 4000,
+    smoothing: 0.0,
+    gap: 0.0,
+}});
+movie
+"##,
+            kind
+        );
+        let movie = movie_with_track(&engine, &script, tone(1.0, 0.8, 1500.0));
+        let drawn = drawn_pixels(&movie, 0.5);
+        assert!(drawn.iter().any(|&d| d), "{} draws", kind);
+        if kind == "bars" {
+            // Bands: 250-500, 500-1000, 1000-2000 and 2000-4000 Hz
+            assert!(drawn[50 * 100 + 62], "Band with the tone is full");
+            assert!(!drawn[50 * 100 + 12], "Empty band stays low");
+            assert!(!drawn[50 * 100 + 87], "Empty band stays low");
+        }
+    }
+
+    for script in [
+        r#"let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); scene.add_waveform(scene.add_audio("a.wav"), #{ mode: "sideways" });"#,
+        r#"let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); scene.add_spectrum(scene.add_audio("a.wav"), #{ type: "cube" });"#,
+        r#"let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); scene.add_spectrum(scene.add_audio("a.wav"), #{ color: "blue" });"#,
+        r#"let movie = new_director(100, 100, 30); let scene = movie.add_scene(1.0); scene.add_spectrum(scene.add_audio("a.wav"), #{ min_db: 0, max_db: -20 });"#,
+        r#"let other = new_director(100, 100, 30); let movie = new_director(100, 100, 30); movie.add_scene(1.0).add_waveform(other.add_audio("a.wav"), #{});"#,
+    ] {
+        assert!(engine.run(script).is_err(), "{} should fail", script);
+    }
+}
@@ -2,18 +2,23 @@ use director_core::animation::{Animated, EasingType};
 use director_core::element::TextSpan;
 use director_core::node::video_node::VideoSource;
 use director_core::node::{
-    BoxNode, CompositionNode, ImageNode, LottieNode, TextNode, VectorNode, VideoNode,
+    BoxNode, ChartNode, CodeNode, CodeTheme, CompositionNode, ImageNode, LottieNode, ShapeFill,
+    ShapeGeometry, ShapeNode, TextCounter, TextNode, VectorNode, VideoNode,
 };
 use director_core::node::{EffectNode, EffectType};
+use director_core::scene::{DriverInput, PropertyDriver};
 use director_core::systems::transitions::{Transition, TransitionType as CoreTransitionType};
-use director_core::types::{Color, NodeId, ObjectFit};
+use director_core::types::{ClipPath, ClipShape, Color, NodeId, ObjectFit};
 use director_core::video_wrapper::RenderMode;
 use director_core::{AssetLoader, Director, Element};
 use director_schema::{
-    Animation, EffectConfig, MovieRequest, Node, NodeKind, StyleMap, TransformMap, TransitionType,
+    Animation, ChartConfig, ClipPathConfig, ClipShapeConfig, CodeConfig, CounterConfig,
+    EffectConfig, FillConfig, MovieRequest, Node, NodeKind, ShapeGeometryConfig, StrokeConfig,
+    StyleMap, TransformMap, TransitionType,
 };
+use skia_safe::textlayout::FontCollection;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use taffy::geometry::{Line, Rect, Size};
 use taffy::prelude::*;
 use taffy::style::{
@@ -31,14 +36,22 @@ pub fn load_movie(request: MovieRequest, loader: Arc<dyn AssetLoader>) -> Direct
         RenderMode::Export,
         None,
     );
+    director.color_space = request.color_space;
+    director.color_interpolation = request.color_interpolation;
+    director.supersampling = request.supersampling.clamp(1, 4);
+    director.tile_size = request.tile_size;
+    director.tile_overlap = request.tile_overlap;
 
     // Build transition list from scene configs
     let mut scene_end_times = Vec::new();
     let mut cumulative_time = 0.0;
 
+    let mut built_roots = Vec::new();
+
     for scene_data in &request.scenes {
         // Build the scene graph for this scene
         let root_id = build_node_recursive(&mut director, &scene_data.root);
+        built_roots.push((root_id, &scene_data.root));
 
         // Calculate start time based on previous scenes
         let start_time = cumulative_time;
@@ -57,6 +70,9 @@ pub fn load_movie(request: MovieRequest, loader: Arc<dyn AssetLoader>) -> Direct
             });
     }
 
+    // Drivers may reference nodes in any scene, so wire them once everything exists
+    wire_drivers(&mut director, &built_roots);
+
     // Wire up transitions between scenes
     for (i, scene_data) in request.scenes.iter().enumerate() {
         if let Some(trans) = &scene_data.transition {
@@ -77,6 +93,200 @@ pub fn load_movie(request: MovieRequest, loader: Arc<dyn AssetLoader>) -> Direct
     director
 }
 
+/// Converts a schema clip config to a core ClipPath. Invalid SVG path data yields `None`.
+fn convert_clip_path(config: &ClipPathConfig) -> Option<ClipPath> {
+    let to_rect = |b: &[f32; 4]| skia_safe::Rect::from_xywh(b[0], b[1], b[2], b[3]);
+    let shape = match &config.shape {
+        ClipShapeConfig::Path { d } => ClipShape::Path(skia_safe::Path::from_svg(d)?),
+        ClipShapeConfig::Rect { bounds, radius } => ClipShape::Rect {
+            rect: bounds.as_ref().map(to_rect),
+            radius: *radius,
+        },
+        ClipShapeConfig::Ellipse { bounds } => ClipShape::Ellipse {
+            rect: bounds.as_ref().map(to_rect),
+        },
+        ClipShapeConfig::Polygon { points } => {
+            let points: Vec<skia_safe::Point> =
+                points.iter().map(|p| (p[0], p[1]).into()).collect();
+            let mut path = skia_safe::Path::new();
+            path.add_poly(&points, true);
+            ClipShape::Path(path)
+        }
+    };
+
+    let mut clip = ClipPath::new(shape);
+    clip.feather = Animated::new(config.feather.max(0.0));
+    clip.expansion = Animated::new(config.expansion);
+    clip.invert = config.invert;
+    Some(clip)
+}
+
+/// Builds a ShapeNode from the schema's geometry, fill and stroke.
+fn build_shape(
+    geometry: &ShapeGeometryConfig,
+    fill: Option<&FillConfig>,
+    stroke: Option<&StrokeConfig>,
+) -> ShapeNode {
+    let (kind, points, inner_radius, corner_radius) = match geometry {
+        ShapeGeometryConfig::Rect { corner_radius } => {
+            (ShapeGeometry::Rect, 5, 0.5, *corner_radius)
+        }
+        ShapeGeometryConfig::Ellipse => (ShapeGeometry::Ellipse, 5, 0.5, 0.0),
+        ShapeGeometryConfig::Polygon {
+            points,
+            corner_radius,
+        } => (ShapeGeometry::Polygon, *points, 0.5, *corner_radius),
+        ShapeGeometryConfig::Star {
+            points,
+            inner_radius,
+            corner_radius,
+        } => (ShapeGeometry::Star, *points, *inner_radius, *corner_radius),
+        ShapeGeometryConfig::Path { d } => (
+            // Invalid path data draws nothing
+            ShapeGeometry::Path(skia_safe::Path::from_svg(d).unwrap_or_default()),
+            5,
+            0.5,
+            0.0,
+        ),
+    };
+
+    let mut shape = ShapeNode::new(kind);
+    shape.points = Animated::new(points as f32);
+    shape.inner_radius = Animated::new(inner_radius);
+    shape.corner_radius = Animated::new(corner_radius);
+    shape.fill = fill.map(|f| match f {
+        FillConfig::Solid { color } => ShapeFill::Solid(Animated::new(*color)),
+        FillConfig::LinearGradient { gradient } => ShapeFill::LinearGradient(gradient.clone()),
+        FillConfig::RadialGradient { gradient } => ShapeFill::RadialGradient(gradient.clone()),
+    });
+    if let Some(stroke) = stroke {
+        shape.stroke_color = Some(Animated::new(stroke.color));
+        shape.stroke_width = Animated::new(stroke.width);
+        shape.stroke_cap = stroke.cap;
+        shape.stroke_join = stroke.join;
+        shape.dash = stroke.dash.clone();
+        shape.dash_offset = Animated::new(stroke.dash_offset);
+        if let Some(trim) = &stroke.trim {
+            shape.trim_start = Animated::new(trim.start);
+            shape.trim_end = Animated::new(trim.end);
+            shape.trim_offset = Animated::new(trim.offset);
+        }
+    }
+    shape
+}
+
+fn build_chart(config: &ChartConfig, font_collection: Arc<Mutex<FontCollection>>) -> ChartNode {
+    let series = config
+        .series
+        .iter()
+        .map(|s| (s.name.clone(), s.values.clone()))
+        .collect();
+    let mut chart = ChartNode::new(config.chart, config.labels.clone(), series, font_collection);
+    if !config.colors.is_empty() {
+        chart.colors = config.colors.clone();
+    }
+    chart.font_family = config.font_family.clone();
+    chart.font_size = config.font_size;
+    if let Some(color) = config.text_color {
+        chart.text_color = color;
+    }
+    if let Some(color) = config.axis_color {
+        chart.axis_color = color;
+    }
+    if !config.show_grid {
+        chart.grid_color = None;
+    } else if config.grid_color.is_some() {
+        chart.grid_color = config.grid_color;
+    }
+    chart.show_axes = config.show_axes;
+    chart.show_legend = config.show_legend;
+    chart.show_values = config.show_values;
+    chart.min = config.min;
+    chart.max = config.max;
+    chart.prefix = config.prefix.clone();
+    chart.suffix = config.suffix.clone();
+    chart.decimals = config.decimals;
+    chart.inner_radius = config.inner_radius;
+    if let Some(build) = &config.build_in {
+        chart.stagger = build.stagger;
+        chart.build = Animated::new(0.0);
+        chart.build.add_keyframe(1.0, build.duration, build.easing);
+    }
+    for transition in &config.transitions {
+        chart.animate_data(
+            transition.values.clone(),
+            transition.delay,
+            transition.duration,
+            transition.easing,
+        );
+    }
+    chart
+}
+
+fn build_code(config: &CodeConfig, font_collection: Arc<Mutex<FontCollection>>) -> CodeNode {
+    let mut code = CodeNode::new(&config.code, config.language, font_collection);
+    code.theme = CodeTheme::named(&config.theme).unwrap_or_default();
+    code.font_family = config.font_family.clone();
+    code.font_size = config.font_size;
+    code.line_height = config.line_height;
+    code.show_line_numbers = config.line_numbers;
+    if let Some(typing) = &config.type_in {
+        code.typed = Animated::new(0.0);
+        if typing.delay > 0.0 {
+            code.typed
+                .add_keyframe(0.0, typing.delay, EasingType::Linear);
+        }
+        code.typed.add_keyframe(1.0, typing.duration, typing.easing);
+    }
+    for transition in &config.transitions {
+        code.animate_code(
+            &transition.code,
+            transition.delay,
+            transition.duration,
+            transition.easing,
+        );
+    }
+    code.set_focus(&config.highlight);
+    for focus in &config.focus {
+        code.focus_lines(&focus.lines, focus.delay, focus.duration, focus.easing);
+    }
+    code
+}
+
+fn build_counter(config: &CounterConfig, font_collection: Arc<Mutex<FontCollection>>) -> TextNode {
+    let mut counter = TextCounter::new(config.value, config.format.clone());
+    for count in &config.counts {
+        let hold = count.start_time - counter.value.duration();
+        if hold > 0.0 {
+            let last = counter
+                .value
+                .raw_keyframes
+                .last()
+                .map_or(config.value, |k| k.0);
+            counter.value.add_keyframe(last, hold, EasingType::Linear);
+        }
+        match count.spring {
+            Some(spring) => counter.value.add_spring(count.to, spring),
+            None => counter
+                .value
+                .add_keyframe(count.to, count.duration, count.easing),
+        }
+    }
+
+    let span = TextSpan {
+        text: counter.text(),
+        font_size: Some(config.font_size),
+        font_family: config.font_family.clone(),
+        color: Some(config.color.unwrap_or(Color::WHITE)),
+        ..Default::default()
+    };
+    let mut text = TextNode::new(vec![span], font_collection);
+    text.counter = Some(counter);
+    text.tabular_figures = config.tabular;
+    text.init_paragraph();
+    text
+}
+
 /// Converts schema TransitionType to core TransitionType
 fn convert_transition_type(kind: &TransitionType) -> CoreTransitionType {
     match kind {
@@ -89,6 +299,89 @@ fn convert_transition_type(kind: &TransitionType) -> CoreTransitionType {
     }
 }
 
+/// Resolves schema `drivers` (which reference nodes by string ID) into core `PropertyDriver`s.
+fn wire_drivers(director: &mut Director, roots: &[(NodeId, &Node)]) {
+    let mut ids = HashMap::new();
+    for (root_id, root_def) in roots {
+        collect_node_ids(director, root_def, *root_id, &mut ids);
+    }
+    for (root_id, root_def) in roots {
+        apply_drivers_recursive(director, root_def, *root_id, &ids);
+    }
+}
+
+/// Maps schema node IDs to scene graph IDs. Children are built in order, so both trees align.
+fn collect_node_ids(
+    director: &Director,
+    node_def: &Node,
+    id: NodeId,
+    ids: &mut HashMap<String, NodeId>,
+) {
+    ids.insert(node_def.id.clone(), id);
+    if let Some(node) = director.scene.get_node(id) {
+        for (child_def, &child_id) in node_def.children.iter().zip(&node.children) {
+            collect_node_ids(director, child_def, child_id, ids);
+        }
+        if let (Some(mask_def), Some(mask_id)) = (&node_def.mask, node.mask_node) {
+            collect_node_ids(director, &mask_def.node, mask_id, ids);
+        }
+    }
+}
+
+fn apply_drivers_recursive(
+    director: &mut Director,
+    node_def: &Node,
+    id: NodeId,
+    ids: &HashMap<String, NodeId>,
+) {
+    let Some(node) = director.scene.get_node_mut(id) else {
+        return;
+    };
+
+    for config in &node_def.drivers {
+        let primary = config
+            .source
+            .iter()
+            .zip(&config.source_property)
+            .map(|(node, property)| (node, property, 1.0));
+        let extra = config
+            .sources
+            .iter()
+            .map(|s| (&s.node, &s.property, s.weight));
+        // Drivers pointing at unknown node IDs (or without sources) are ignored.
+        let inputs: Option<Vec<DriverInput>> = primary
+            .chain(extra)
+            .map(|(node, property, weight)| {
+                ids.get(node).map(|&source| DriverInput {
+                    source,
+                    property: property.clone(),
+                    weight,
+                })
+            })
+            .collect();
+        let Some(inputs) = inputs.filter(|inputs| !inputs.is_empty()) else {
+            continue;
+        };
+        let mut driver = PropertyDriver::with_inputs(&config.property, inputs);
+        driver.multiplier = config.multiplier;
+        driver.offset = config.offset;
+        driver.min = config.min;
+        driver.max = config.max;
+        driver.delay = config.delay.max(0.0);
+        driver.decimals = config.decimals;
+        node.drivers.push(driver);
+    }
+
+    let children = node.children.clone();
+    let mask_id = node.mask_node;
+    for (child_def, child_id) in node_def.children.iter().zip(children) {
+        apply_drivers_recursive(director, child_def, child_id, ids);
+    }
+    if let (Some(mask_def), Some(mask_id)) = (&node_def.mask, mask_id) {
+        apply_drivers_recursive(director, &mask_def.node, mask_id, ids);
+    }
+}
+
 fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
     // 1. Create Element based on NodeKind
     let mut element: Box<dyn Element> = match &node_def.kind {
@@ -149,10 +442,16 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
             }
             Box::new(vid)
         }
-        NodeKind::Vector { src } => {
+        NodeKind::Vector { src, trim } => {
             // Load SVG bytes and create VectorNode
             let bytes = director.assets.loader.load_bytes(src).unwrap_or_default();
-            Box::new(VectorNode::new(&bytes))
+            let mut vector = VectorNode::new(&bytes);
+            if let Some(trim) = trim {
+                vector.trim_start = Animated::new(trim.start);
+                vector.trim_end = Animated::new(trim.end);
+                vector.trim_offset = Animated::new(trim.offset);
+            }
+            Box::new(vector)
         }
         NodeKind::Lottie {
             src,
@@ -170,7 +469,10 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
                 Err(_) => Box::new(BoxNode::new()), // Fallback on error
             }
         }
-        NodeKind::Effect { effect_type } => {
+        NodeKind::Effect {
+            effect_type,
+            adjustment,
+        } => {
             // Color matrix presets
             const GRAYSCALE_MATRIX: [f32; 20] = [
                 0.2126, 0.7152, 0.0722, 0.0, 0.0, 0.2126, 0.7152, 0.0722, 0.0, 0.0, 0.2126, 0.7152,
@@ -217,8 +519,24 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
                 style: Style::DEFAULT,
                 shader_cache: Arc::new(std::sync::Mutex::new(HashMap::new())),
                 current_time: 0.0,
+                adjustment: *adjustment,
             })
         }
+        NodeKind::Shape {
+            geometry,
+            fill,
+            stroke,
+        } => Box::new(build_shape(geometry, fill.as_ref(), stroke.as_ref())),
+        NodeKind::Code { code } => {
+            Box::new(build_code(code, director.assets.font_collection.clone()))
+        }
+        NodeKind::Counter { counter } => Box::new(build_counter(
+            counter,
+            director.assets.font_collection.clone(),
+        )),
+        NodeKind::Chart { chart } => {
+            Box::new(build_chart(chart, director.assets.font_collection.clone()))
+        }
         NodeKind::Composition {
             width,
             height,
@@ -238,8 +556,10 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
 
             // Build each scene in the sub-composition
             let mut cumulative_time = 0.0;
+            let mut built_roots = Vec::new();
             for scene_data in scenes {
                 let root_id = build_node_recursive(&mut internal_director, &scene_data.root);
+                built_roots.push((root_id, &scene_data.root));
                 internal_director
                     .timeline
                     .push(director_core::director::TimelineItem {
@@ -252,6 +572,8 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
                 cumulative_time += scene_data.duration_secs;
             }
 
+            wire_drivers(&mut internal_director, &built_roots);
+
             let mut comp = CompositionNode::new(internal_director);
             comp.start_offset = *start_offset;
             Box::new(comp)
@@ -311,11 +633,25 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
             if let Some(o) = &node_def.style.overflow {
                 box_node.overflow = o.clone();
             }
+
+            // Backdrop
+            if let Some(b) = node_def.style.backdrop_blur {
+                box_node.backdrop_blur = Animated::new(b);
+            }
+            if let Some(s) = node_def.style.backdrop_saturation {
+                box_node.backdrop_saturation = Animated::new(s);
+            }
+            if let Some(t) = node_def.style.backdrop_tint {
+                box_node.backdrop_tint = Some(Animated::new(t));
+            }
         }
 
         // Apply Transform
         apply_transform_map(&mut node.transform, &node_def.transform);
 
+        // Apply Clip Path
+        node.clip_path = node_def.clip_path.as_ref().and_then(convert_clip_path);
+
         // Apply Animations (Must be done after layout/transform setup as requested)
         apply_animations(&mut node.element, &node_def.animations);
     }
@@ -326,6 +662,18 @@ fn build_node_recursive(director: &mut Director, node_def: &Node) -> NodeId {
         director.scene.add_child(id, child_id);
     }
 
+    // 5. Track matte (owned by this node, not one of its children)
+    if let Some(mask_def) = &node_def.mask {
+        let mask_id = build_node_recursive(director, &mask_def.node);
+        if let Some(mask) = director.scene.get_node_mut(mask_id) {
+            mask.parent = Some(id);
+        }
+        if let Some(node) = director.scene.get_node_mut(id) {
+            node.mask_node = Some(mask_id);
+            node.mask_mode = mask_def.mode;
+        }
+    }
+
     id
 }
 
@@ -1,5 +1,9 @@
 use director_core::animation::{EasingType, SpringConfig};
-use director_core::types::{Color, GradientConfig};
+use director_core::node::NumberFormat;
+use director_core::types::{
+    ChartKind, CodeLanguage, Color, ColorInterpolation, GradientConfig, MatteMode, StrokeCap,
+    StrokeJoin, WorkingColorSpace,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -11,6 +15,29 @@ pub struct MovieRequest {
     /// Global audio tracks for the movie
     #[serde(default)]
     pub audio_tracks: Vec<AudioTrack>,
+    /// Compositing colour space: "srgb" (default), "linear_srgb" or "linear_display_p3"
+    #[serde(default)]
+    pub color_space: WorkingColorSpace,
+    /// How colour animations blend: "srgb" (default) or "oklab"
+    #[serde(default)]
+    pub color_interpolation: ColorInterpolation,
+    /// Export render scale for anti-aliasing: 1 (off), 2 or 4
+    #[serde(default = "default_supersampling")]
+    pub supersampling: u32,
+    /// Render very large frames in tiles of this many pixels (default: untiled)
+    #[serde(default)]
+    pub tile_size: Option<u32>,
+    /// Margin rendered around each tile for blur bleed (default: 64)
+    #[serde(default = "default_tile_overlap")]
+    pub tile_overlap: u32,
+}
+
+fn default_supersampling() -> u32 {
+    1
+}
+
+fn default_tile_overlap() -> u32 {
+    64
 }
 
 /// Visual transition type between scenes.
@@ -67,6 +94,15 @@ pub struct Node {
     /// Audio-reactive bindings for this node
     #[serde(default)]
     pub audio_bindings: Vec<AudioReactiveBinding>,
+    /// Links from other nodes' properties to this node's properties
+    #[serde(default)]
+    pub drivers: Vec<DriverConfig>,
+    /// Optional track matte applied to this node and its children
+    #[serde(default)]
+    pub mask: Option<MaskConfig>,
+    /// Optional geometry clip applied to this node and its children
+    #[serde(default)]
+    pub clip_path: Option<ClipPathConfig>,
 
     // The specific type (Box, Text, Image, Video, Vector, Lottie, Effect)
     #[serde(flatten)]
@@ -92,6 +128,11 @@ pub enum NodeKind {
         #[serde(default)]
         animators: Vec<TextAnimator>,
     },
+    /// A number that counts to new values, formatted for a locale.
+    Counter {
+        #[serde(flatten)]
+        counter: CounterConfig,
+    },
     /// An image element loaded from a file path.
     Image {
         src: String,
@@ -110,6 +151,9 @@ pub enum NodeKind {
     Vector {
         /// Path to SVG file
         src: String,
+        /// Trims every stroke in the SVG
+        #[serde(default)]
+        trim: Option<TrimConfig>,
     },
     /// A Lottie animation (JSON-based vector animation).
     Lottie {
@@ -123,7 +167,34 @@ pub enum NodeKind {
         loop_animation: bool,
     },
     /// A visual effect wrapper (blur, shadows, etc.)
-    Effect { effect_type: EffectConfig },
+    Effect {
+        effect_type: EffectConfig,
+        /// Apply the effect to everything drawn beneath this node (within its bounds)
+        /// instead of to its children
+        #[serde(default)]
+        adjustment: bool,
+    },
+    /// A vector shape (rect, ellipse, polygon, star or SVG path) with fill and stroke.
+    Shape {
+        #[serde(flatten)]
+        geometry: ShapeGeometryConfig,
+        /// Fill colour or gradient (default: no fill)
+        #[serde(default)]
+        fill: Option<FillConfig>,
+        /// Outline (default: no stroke)
+        #[serde(default)]
+        stroke: Option<StrokeConfig>,
+    },
+    /// A data chart (bar, line, area or pie) with axes, labels and legend.
+    Chart {
+        #[serde(flatten)]
+        chart: ChartConfig,
+    },
+    /// Highlighted source code with typing, edit transitions and line focus.
+    Code {
+        #[serde(flatten)]
+        code: CodeConfig,
+    },
     /// A nested composition with its own timeline (pre-comp).
     Composition {
         /// Width of the composition canvas
@@ -149,6 +220,337 @@ fn default_comp_fps() -> u32 {
     30
 }
 
+/// Geometry of a shape node. All kinds except `Path` are fitted to the node's box.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum ShapeGeometryConfig {
+    Rect {
+        #[serde(default)]
+        corner_radius: f32,
+    },
+    Ellipse,
+    /// Regular polygon with `points` corners
+    Polygon {
+        #[serde(default = "default_shape_points")]
+        points: u32,
+        #[serde(default)]
+        corner_radius: f32,
+    },
+    /// Star with `points` tips
+    Star {
+        #[serde(default = "default_shape_points")]
+        points: u32,
+        /// Inner radius as a fraction of the outer radius
+        #[serde(default = "default_inner_radius")]
+        inner_radius: f32,
+        #[serde(default)]
+        corner_radius: f32,
+    },
+    /// SVG path data in node-local pixels
+    Path {
+        d: String,
+    },
+}
+
+fn default_shape_points() -> u32 {
+    5
+}
+
+fn default_inner_radius() -> f32 {
+    0.5
+}
+
+/// How the inside of a shape is painted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FillConfig {
+    Solid {
+        color: Color,
+    },
+    /// Gradient from `start` to `end` (relative to the node's box)
+    LinearGradient {
+        #[serde(flatten)]
+        gradient: GradientConfig,
+    },
+    /// Gradient centred on `start`, reaching its last colour at `end`
+    RadialGradient {
+        #[serde(flatten)]
+        gradient: GradientConfig,
+    },
+}
+
+/// Outline of a shape node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StrokeConfig {
+    pub color: Color,
+    /// Stroke width in pixels (default: 1.0)
+    #[serde(default = "default_stroke_width")]
+    pub width: f32,
+    /// "butt" (default), "round" or "square"
+    #[serde(default)]
+    pub cap: StrokeCap,
+    /// "miter" (default), "round" or "bevel"
+    #[serde(default)]
+    pub join: StrokeJoin,
+    /// Alternating dash and gap lengths in pixels
+    #[serde(default)]
+    pub dash: Vec<f32>,
+    #[serde(default)]
+    pub dash_offset: f32,
+    /// Draws only part of the stroke
+    #[serde(default)]
+    pub trim: Option<TrimConfig>,
+}
+
+fn default_stroke_width() -> f32 {
+    1.0
+}
+
+/// Visible part of a stroke, as fractions of the path length.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct TrimConfig {
+    #[serde(default)]
+    pub start: f32,
+    #[serde(default = "default_trim_end")]
+    pub end: f32,
+    /// Shifts the visible range along the path, wrapping past the end
+    #[serde(default)]
+    pub offset: f32,
+}
+
+fn default_trim_end() -> f32 {
+    1.0
+}
+
+/// Data and styling of a chart node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartConfig {
+    /// "bar" (default), "line", "area" or "pie"
+    #[serde(default)]
+    pub chart: ChartKind,
+    /// One label per value
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub series: Vec<ChartSeriesConfig>,
+    /// Datasets the values animate to, in order
+    #[serde(default)]
+    pub transitions: Vec<ChartTransitionConfig>,
+    /// Animates the chart in: bars grow, lines draw on, pies sweep round
+    #[serde(default)]
+    pub build_in: Option<ChartBuildConfig>,
+    /// Series colours (slice colours for pies); defaults to a built-in palette
+    #[serde(default)]
+    pub colors: Vec<Color>,
+    #[serde(default)]
+    pub font_family: Option<String>,
+    #[serde(default = "default_chart_font_size")]
+    pub font_size: f32,
+    #[serde(default)]
+    pub text_color: Option<Color>,
+    #[serde(default)]
+    pub axis_color: Option<Color>,
+    #[serde(default)]
+    pub grid_color: Option<Color>,
+    #[serde(default = "default_true")]
+    pub show_axes: bool,
+    #[serde(default = "default_true")]
+    pub show_grid: bool,
+    #[serde(default = "default_true")]
+    pub show_legend: bool,
+    /// Writes each value by its bar, point or slice
+    #[serde(default)]
+    pub show_values: bool,
+    /// Value axis bounds (default: fit the data, including zero)
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+    /// Text before and after formatted values, e.g. "$" or "%"
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub suffix: String,
+    #[serde(default)]
+    pub decimals: Option<usize>,
+    /// Donut hole as a fraction of the pie radius
+    #[serde(default)]
+    pub inner_radius: f32,
+}
+
+/// One named set of chart values.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartSeriesConfig {
+    #[serde(default)]
+    pub name: String,
+    pub values: Vec<f32>,
+}
+
+/// A change of every series' values.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartTransitionConfig {
+    /// New values, one array per series
+    pub values: Vec<Vec<f32>>,
+    pub duration: f64,
+    /// Seconds to hold the previous values first
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default = "default_chart_easing")]
+    pub easing: EasingType,
+}
+
+/// Build-in animation of a chart.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartBuildConfig {
+    pub duration: f64,
+    #[serde(default = "default_chart_easing")]
+    pub easing: EasingType,
+    /// How far through the build the last label starts (default: 0.3)
+    #[serde(default = "default_chart_stagger")]
+    pub stagger: f32,
+}
+
+/// Source, styling and animations of a code node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CodeConfig {
+    pub code: String,
+    /// "rust", "javascript", "python", "go", "c", "json", "shell", "sql" or "plain" (default)
+    #[serde(default)]
+    pub language: CodeLanguage,
+    /// "dark" (default), "light" or "monokai"
+    #[serde(default = "default_code_theme")]
+    pub theme: String,
+    #[serde(default)]
+    pub font_family: Option<String>,
+    #[serde(default = "default_code_font_size")]
+    pub font_size: f32,
+    /// Line spacing as a multiple of the font size
+    #[serde(default = "default_code_line_height")]
+    pub line_height: f32,
+    #[serde(default = "default_true")]
+    pub line_numbers: bool,
+    /// Lines (numbered from 1) focused from the start
+    #[serde(default)]
+    pub highlight: Vec<usize>,
+    /// Types the code in
+    #[serde(default)]
+    pub type_in: Option<CodeTypingConfig>,
+    /// New versions of the code, in order
+    #[serde(default)]
+    pub transitions: Vec<CodeTransitionConfig>,
+    /// Focus changes, in order
+    #[serde(default)]
+    pub focus: Vec<CodeFocusConfig>,
+}
+
+/// Typing animation of a code node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CodeTypingConfig {
+    pub duration: f64,
+    /// Seconds before typing starts
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default = "default_code_typing_easing")]
+    pub easing: EasingType,
+}
+
+/// Transition of a code node to a new version of its code.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CodeTransitionConfig {
+    pub code: String,
+    pub duration: f64,
+    /// Seconds to hold the previous version first
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default = "default_chart_easing")]
+    pub easing: EasingType,
+}
+
+/// Focus change of a code node; no lines clears the focus.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CodeFocusConfig {
+    pub lines: Vec<usize>,
+    pub duration: f64,
+    /// Seconds to hold the previous focus first
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default = "default_chart_easing")]
+    pub easing: EasingType,
+}
+
+fn default_code_theme() -> String {
+    "dark".to_string()
+}
+
+fn default_code_font_size() -> f32 {
+    20.0
+}
+
+fn default_code_line_height() -> f32 {
+    1.5
+}
+
+fn default_code_typing_easing() -> EasingType {
+    EasingType::Linear
+}
+
+/// Value, formatting and counts of a counter node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CounterConfig {
+    /// Value shown before the first count
+    #[serde(default)]
+    pub value: f64,
+    pub font_size: f32,
+    #[serde(default)]
+    pub font_family: Option<String>,
+    #[serde(default)]
+    pub color: Option<Color>,
+    /// Equal-width digits, so the number doesn't shift while counting
+    #[serde(default = "default_true")]
+    pub tabular: bool,
+    /// `decimals`, `grouping`, `prefix`, `suffix`, `currency`, `percent` and `locale`
+    #[serde(flatten)]
+    pub format: NumberFormat,
+    /// Values to count to, in order
+    #[serde(default)]
+    pub counts: Vec<CounterCountConfig>,
+}
+
+/// One count of a counter node: a tween, or a spring when `spring` is set.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CounterCountConfig {
+    pub to: f64,
+    /// Ignored by springs, which take as long as they need to settle
+    #[serde(default)]
+    pub duration: f64,
+    /// Relative to scene start; counts never start before the previous one ends
+    #[serde(default)]
+    pub start_time: f64,
+    #[serde(default = "default_counter_easing")]
+    pub easing: EasingType,
+    #[serde(default)]
+    pub spring: Option<SpringConfig>,
+}
+
+fn default_counter_easing() -> EasingType {
+    EasingType::EaseOut
+}
+
+fn default_chart_font_size() -> f32 {
+    16.0
+}
+
+fn default_chart_easing() -> EasingType {
+    EasingType::EaseInOut
+}
+
+fn default_chart_stagger() -> f32 {
+    0.3
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// Configuration for visual effects applied to nodes.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "effect", rename_all = "snake_case")]
@@ -293,6 +695,14 @@ pub struct StyleMap {
     /// Overflow behavior: "visible" (default) or "hidden"
     pub overflow: Option<String>,
 
+    // Backdrop (frosted glass)
+    /// Blur applied to whatever is behind the box
+    pub backdrop_blur: Option<f32>,
+    /// Saturation of the backdrop (1.0 = unchanged)
+    pub backdrop_saturation: Option<f32>,
+    /// Colour mixed over the backdrop, weighted by its alpha
+    pub backdrop_tint: Option<Color>,
+
     // Spacing (uniform)
     pub padding: Option<f32>,
     pub margin: Option<f32>,
@@ -410,6 +820,102 @@ pub struct AudioReactiveBinding {
     pub smoothing: f32,
 }
 
+/// Drives a property of this node from properties of other nodes.
+///
+/// The driven value is `clamp(sum(weight * source(t - delay)) * multiplier + offset, min, max)`,
+/// summed over `source` (weight 1) and `sources`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DriverConfig {
+    /// Property to set on this node: "x", "y", "scale", "rotation", "opacity", "content", ...
+    pub property: String,
+    /// ID of the source node (may live in another scene)
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Property to read on the source node
+    #[serde(default)]
+    pub source_property: Option<String>,
+    /// Further weighted sources, summed with `source`
+    #[serde(default)]
+    pub sources: Vec<DriverSourceConfig>,
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f32,
+    #[serde(default)]
+    pub offset: f32,
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+    /// Lag behind the source, in seconds
+    #[serde(default)]
+    pub delay: f64,
+    /// Decimal places used when driving text `content`
+    #[serde(default)]
+    pub decimals: usize,
+}
+
+/// One weighted source of a `DriverConfig`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DriverSourceConfig {
+    /// ID of the source node
+    pub node: String,
+    /// Property to read on the source node
+    pub property: String,
+    #[serde(default = "default_multiplier")]
+    pub weight: f32,
+}
+
+/// A track matte: another node whose alpha or luminance controls this node's visibility.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaskConfig {
+    /// The matte node (rendered only as a mask, never on its own)
+    pub node: Box<Node>,
+    /// "alpha" (default), "alpha_inverted", "luma", "luma_inverted"
+    #[serde(default)]
+    pub mode: MatteMode,
+}
+
+/// A clip mask built directly from geometry, without a separate mask node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClipPathConfig {
+    #[serde(flatten)]
+    pub shape: ClipShapeConfig,
+    /// Edge softness in pixels
+    #[serde(default)]
+    pub feather: f32,
+    /// Grows (positive) or shrinks (negative) the shape, in pixels
+    #[serde(default)]
+    pub expansion: f32,
+    /// Keep the content outside the shape instead of inside
+    #[serde(default)]
+    pub invert: bool,
+}
+
+/// Clip geometry in node-local pixels.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum ClipShapeConfig {
+    /// SVG path data
+    Path { d: String },
+    /// Rectangle `[x, y, width, height]`; omitted bounds use the node's box
+    Rect {
+        #[serde(default)]
+        bounds: Option<[f32; 4]>,
+        #[serde(default)]
+        radius: f32,
+    },
+    /// Ellipse inscribed in `[x, y, width, height]`; omitted bounds use the node's box
+    Ellipse {
+        #[serde(default)]
+        bounds: Option<[f32; 4]>,
+    },
+    /// Closed polygon through `[x, y]` points
+    Polygon { points: Vec<[f32; 2]> },
+}
+
+fn default_multiplier() -> f32 {
+    1.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -437,6 +943,9 @@ mod tests {
                     transform: TransformMap::default(),
                     animations: vec![],
                     audio_bindings: vec![],
+                    drivers: vec![],
+                    mask: None,
+                    clip_path: None,
                     children: vec![Node {
                         id: "text_1".to_string(),
                         kind: NodeKind::Text {
@@ -451,12 +960,20 @@ mod tests {
                         transform: TransformMap::default(),
                         animations: vec![],
                         audio_bindings: vec![],
+                        drivers: vec![],
+                        mask: None,
+                        clip_path: None,
                         children: vec![],
                     }],
                 },
                 transition: None,
             }],
             audio_tracks: vec![],
+            color_space: WorkingColorSpace::default(),
+            color_interpolation: ColorInterpolation::default(),
+            supersampling: 2,
+            tile_size: Some(2048),
+            tile_overlap: 64,
         };
 
         let json = serde_json::to_string_pretty(&movie).unwrap();
@@ -566,6 +1083,9 @@ mod tests {
                         transform: TransformMap::default(),
                         animations: vec![],
                         audio_bindings: vec![],
+                        drivers: vec![],
+                        mask: None,
+                        clip_path: None,
                         children: vec![],
                     },
                     transition: None,
@@ -576,6 +1096,9 @@ mod tests {
             transform: TransformMap::default(),
             animations: vec![],
             audio_bindings: vec![],
+            drivers: vec![],
+            mask: None,
+            clip_path: None,
             children: vec![],
         };
 
@@ -603,4 +1126,293 @@ mod tests {
             _ => panic!("Expected Composition NodeKind"),
         }
     }
+
+    #[test]
+    fn test_driver_config_defaults() {
+        let json = r#"{
+            "id": "shadow",
+            "type": "box",
+            "drivers": [
+                { "property": "x", "source": "card", "source_property": "x", "offset": 8.0 },
+                { "property": "opacity", "source": "card", "source_property": "y",
+                  "multiplier": 0.01, "min": 0.0, "max": 1.0, "delay": 0.25 },
+                { "property": "y", "sources": [
+                    { "node": "card", "property": "y", "weight": 0.5 },
+                    { "node": "badge", "property": "y" }
+                ] }
+            ]
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert_eq!(node.drivers.len(), 3);
+
+        let follow = &node.drivers[0];
+        assert_eq!(follow.multiplier, 1.0);
+        assert_eq!(follow.offset, 8.0);
+        assert_eq!(follow.min, None);
+        assert_eq!(follow.delay, 0.0);
+
+        let fade = &node.drivers[1];
+        assert_eq!(fade.max, Some(1.0));
+        assert_eq!(fade.delay, 0.25);
+
+        let average = &node.drivers[2];
+        assert_eq!(average.source, None);
+        assert_eq!(average.sources.len(), 2);
+        assert_eq!(average.sources[0].weight, 0.5);
+        assert_eq!(average.sources[1].weight, 1.0);
+    }
+
+    #[test]
+    fn test_mask_config_modes() {
+        let json = r#"{
+            "id": "title",
+            "type": "box",
+            "mask": {
+                "mode": "luma_inverted",
+                "node": { "id": "wipe", "type": "box" }
+            }
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        let mask = node.mask.expect("mask should parse");
+        assert_eq!(mask.mode, MatteMode::LumaInverted);
+        assert_eq!(mask.node.id, "wipe");
+
+        let json =
+            r#"{ "id": "a", "type": "box", "mask": { "node": { "id": "b", "type": "box" } } }"#;
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert_eq!(node.mask.unwrap().mode, MatteMode::Alpha);
+    }
+
+    #[test]
+    fn test_adjustment_layer_flag() {
+        let json = r#"{
+            "id": "grade",
+            "type": "effect",
+            "effect_type": { "effect": "grayscale" },
+            "adjustment": true
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        match node.kind {
+            NodeKind::Effect { adjustment, .. } => assert!(adjustment),
+            other => panic!("Expected effect node, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_clip_path_config() {
+        let json = r#"{
+            "id": "photo",
+            "type": "box",
+            "clip_path": { "shape": "ellipse", "feather": 12.0, "invert": true }
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        let clip = node.clip_path.expect("clip_path should parse");
+        assert!(matches!(
+            clip.shape,
+            ClipShapeConfig::Ellipse { bounds: None }
+        ));
+        assert_eq!(clip.feather, 12.0);
+        assert_eq!(clip.expansion, 0.0);
+        assert!(clip.invert);
+
+        let json = r#"{ "shape": "polygon", "points": [[0, 0], [100, 0], [50, 80]] }"#;
+        let clip: ClipPathConfig = serde_json::from_str(json).unwrap();
+        match clip.shape {
+            ClipShapeConfig::Polygon { points } => assert_eq!(points.len(), 3),
+            other => panic!("Expected polygon, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_shape_node_config() {
+        let json = r#"{
+            "id": "badge",
+            "type": "shape",
+            "shape": "star",
+            "inner_radius": 0.4,
+            "fill": {
+                "type": "radial_gradient",
+                "colors": [{ "r": 1.0, "g": 0.9, "b": 0.3, "a": 1.0 }, { "r": 1.0, "g": 0.6, "b": 0.3, "a": 1.0 }],
+                "start": [0.5, 0.5],
+                "end": [0.5, 1.0]
+            },
+            "stroke": { "color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 }, "cap": "round", "dash": [8, 4], "trim": { "end": 0.25 } }
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        match node.kind {
+            NodeKind::Shape {
+                geometry,
+                fill,
+                stroke,
+            } => {
+                assert!(matches!(
+                    geometry,
+                    ShapeGeometryConfig::Star { points: 5, inner_radius, corner_radius }
+                        if inner_radius == 0.4 && corner_radius == 0.0
+                ));
+                match fill {
+                    Some(FillConfig::RadialGradient { gradient }) => {
+                        assert_eq!(gradient.colors.len(), 2);
+                        assert_eq!(gradient.end, (0.5, 1.0));
+                    }
+                    other => panic!("Expected radial gradient, got {:?}", other),
+                }
+                let stroke = stroke.expect("stroke should parse");
+                assert_eq!(stroke.width, 1.0);
+                assert_eq!(stroke.cap, StrokeCap::Round);
+                assert_eq!(stroke.join, StrokeJoin::Miter);
+                assert_eq!(stroke.dash, vec![8.0, 4.0]);
+                let trim = stroke.trim.expect("trim should parse");
+                assert_eq!((trim.start, trim.end, trim.offset), (0.0, 0.25, 0.0));
+            }
+            other => panic!("Expected shape node, got {:?}", other),
+        }
+
+        let json = r#"{ "id": "arrow", "type": "shape", "shape": "path", "d": "M0 0 L10 10" }"#;
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            node.kind,
+            NodeKind::Shape {
+                geometry: ShapeGeometryConfig::Path { .. },
+                fill: None,
+                stroke: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_chart_node_config() {
+        let json = r#"{
+            "id": "revenue",
+            "type": "chart",
+            "chart": "line",
+            "labels": ["Q1", "Q2", "Q3"],
+            "series": [{ "name": "2024", "values": [1, 4, 2] }, { "values": [2, 3, 5] }],
+            "transitions": [{ "values": [[3, 3, 3], [1, 1, 1]], "duration": 1.0, "delay": 2.0 }],
+            "build_in": { "duration": 1.5 },
+            "show_grid": false,
+            "suffix": "%"
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        match node.kind {
+            NodeKind::Chart { chart } => {
+                assert_eq!(chart.chart, ChartKind::Line);
+                assert_eq!(chart.labels.len(), 3);
+                assert_eq!(chart.series[0].name, "2024");
+                assert_eq!(chart.series[1].name, "");
+                assert_eq!(chart.transitions[0].values[1], vec![1.0, 1.0, 1.0]);
+                assert_eq!(chart.transitions[0].delay, 2.0);
+                assert_eq!(chart.transitions[0].easing, EasingType::EaseInOut);
+                let build = chart.build_in.expect("build_in should parse");
+                assert_eq!((build.duration, build.stagger), (1.5, 0.3));
+                assert_eq!(chart.font_size, 16.0);
+                assert!(chart.show_axes && chart.show_legend && !chart.show_grid);
+                assert!(!chart.show_values);
+                assert_eq!(chart.suffix, "%");
+            }
+            other => panic!("Expected chart node, got {:?}", other),
+        }
+
+        let json = r#"{ "id": "share", "type": "chart", "chart": "pie", "series": [{ "values": [1, 2] }] }"#;
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            node.kind,
+            NodeKind::Chart { chart } if chart.chart == ChartKind::Pie && chart.labels.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_code_node_config() {
+        let json = r#"{
+            "id": "snippet",
+            "type": "code",
+            "code": "fn main() {}",
+            "language": "rust",
+            "highlight": [1],
+            "type_in": { "duration": 2.0 },
+            "transitions": [{ "code": "fn main() {\n    run();\n}", "duration": 1.0, "delay": 1.0 }],
+            "focus": [{ "lines": [2], "duration": 0.5 }]
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        match node.kind {
+            NodeKind::Code { code } => {
+                assert_eq!(code.language, CodeLanguage::Rust);
+                assert_eq!(code.theme, "dark");
+                assert_eq!((code.font_size, code.line_height), (20.0, 1.5));
+                assert!(code.line_numbers);
+                assert_eq!(code.highlight, vec![1]);
+                let typing = code.type_in.expect("type_in should parse");
+                assert_eq!((typing.duration, typing.easing), (2.0, EasingType::Linear));
+                assert_eq!(code.transitions[0].code.lines().count(), 3);
+                assert_eq!(code.transitions[0].easing, EasingType::EaseInOut);
+                assert_eq!(code.focus[0].lines, vec![2]);
+            }
+            other => panic!("Expected code node, got {:?}", other),
+        }
+
+        let json = r#"{ "id": "q", "type": "code", "code": "SELECT 1", "language": "ts" }"#;
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            node.kind,
+            NodeKind::Code { code } if code.language == CodeLanguage::JavaScript
+        ));
+    }
+
+    #[test]
+    fn test_counter_node_config() {
+        let json = r#"{
+            "id": "revenue",
+            "type": "counter",
+            "font_size": 64,
+            "currency": "EUR",
+            "locale": "de-DE",
+            "counts": [
+                { "to": 1000000, "duration": 2.0 },
+                { "to": 1250000, "start_time": 3.0, "spring": { "stiffness": 120, "damping": 14, "mass": 1, "velocity": 0 } }
+            ]
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        match node.kind {
+            NodeKind::Counter { counter } => {
+                assert_eq!(counter.value, 0.0);
+                assert!(counter.tabular);
+                assert_eq!(counter.format.currency.as_deref(), Some("EUR"));
+                assert_eq!(counter.format.locale, "de-DE");
+                assert!(counter.format.grouping);
+                assert_eq!(counter.counts[0].easing, EasingType::EaseOut);
+                assert!(counter.counts[0].spring.is_none());
+                assert_eq!(counter.counts[1].start_time, 3.0);
+                assert!(counter.counts[1].spring.is_some());
+            }
+            other => panic!("Expected counter node, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_movie_color_space() {
+        let json = r#"{ "width": 640, "height": 360, "fps": 30, "scenes": [] }"#;
+        let movie: MovieRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(movie.color_space, WorkingColorSpace::Srgb);
+        assert_eq!(movie.color_interpolation, ColorInterpolation::Srgb);
+        assert_eq!(movie.supersampling, 1);
+        assert_eq!(movie.tile_size, None);
+        assert_eq!(movie.tile_overlap, 64);
+
+        let json = r#"{
+            "width": 640, "height": 360, "fps": 30, "scenes": [],
+            "color_space": "linear_display_p3",
+            "color_interpolation": "oklab"
+        }"#;
+        let movie: MovieRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(movie.color_space, WorkingColorSpace::LinearDisplayP3);
+        assert_eq!(movie.color_interpolation, ColorInterpolation::Oklab);
+    }
 }